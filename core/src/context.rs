@@ -0,0 +1,347 @@
+#[cfg(feature = "cel")]
+use std::collections::HashMap;
+
+#[cfg(feature = "cel")]
+use cel::Value as CelValue;
+
+use proto_types::FieldMask;
+
+use crate::{field_data::PathElement, violations::ViolationRecord};
+
+/// The runtime context that is passed down to every nested message during validation.
+///
+/// It can be used to bind extra variables (such as request-scoped data) to the Cel programs being executed. These variables must also be declared at build time with `protocheck_build::declare_cel_variables`, so that the expressions referencing them can be checked during compilation.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationContext {
+  #[cfg(feature = "cel")]
+  cel_variables: HashMap<String, CelValue>,
+  options: ValidationOptions,
+  fail_fast: bool,
+  field_mask_paths: Option<Vec<Vec<String>>>,
+}
+
+impl ValidationContext {
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the [`ValidationOptions`] for this context.
+  #[must_use]
+  pub fn with_options(mut self, options: ValidationOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  /// Returns the [`ValidationOptions`] for this context.
+  pub fn options(&self) -> &ValidationOptions {
+    &self.options
+  }
+
+  /// Limits validation to the paths listed in the given [`FieldMask`] and to their sub-paths, which is useful for partial updates.
+  ///
+  /// Fields that lead to a masked path (such as `address` for the `address.street` path) are validated as well, along with their own rules.
+  /// Message-level Cel rules only run if all of the fields that they reference with `this.<field>` are covered by the mask, meaning that the mask contains the field itself or one of its parents. Rules that use `this` as a whole only run if all of the fields of the message are covered, such as when the mask contains one of its parents or lists every field.
+  #[must_use]
+  pub fn with_field_mask(mut self, mask: &FieldMask) -> Self {
+    self.field_mask_paths = Some(
+      mask
+        .paths
+        .iter()
+        .map(|path| path.split('.').map(str::to_string).collect())
+        .collect(),
+    );
+    self
+  }
+
+  /// Returns `true` if a field mask was set for this context.
+  pub fn has_field_mask(&self) -> bool {
+    self.field_mask_paths.is_some()
+  }
+
+  /// Makes validation stop as soon as the first violation is found, regardless of the [`max_violations`](ValidationOptions::with_max_violations) option.
+  /// Disabling it restores the limit set in the options, if there is one.
+  #[must_use]
+  pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+    self.fail_fast = fail_fast;
+    self
+  }
+
+  /// Returns `true` if validation stops at the first violation.
+  pub fn is_fail_fast(&self) -> bool {
+    self.max_violations() == Some(1)
+  }
+
+  /// Returns the maximum amount of violations that will be collected, taking fail-fast mode into account.
+  pub fn max_violations(&self) -> Option<usize> {
+    if self.fail_fast {
+      Some(1)
+    } else {
+      self.options.max_violations
+    }
+  }
+
+  /// Used by the generated validators to check whether they should stop collecting violations.
+  #[doc(hidden)]
+  pub fn should_stop(&self, violations: &[ViolationRecord]) -> bool {
+    self
+      .max_violations()
+      .is_some_and(|max| violations.len() >= max)
+  }
+
+  /// Used by the generated validators to check whether a violation passes the rule filters, for the rules that could not be checked before being evaluated.
+  #[doc(hidden)]
+  pub fn should_report(&self, violation: &ViolationRecord) -> bool {
+    self
+      .options
+      .is_rule_enabled(RuleCategory::of(violation), violation.rule_id())
+  }
+
+  /// Used by the generated validators to check whether a rule should be evaluated at all.
+  #[doc(hidden)]
+  pub fn is_rule_enabled(&self, category: RuleCategory, rule_id: &str) -> bool {
+    self.options.is_rule_enabled(category, rule_id)
+  }
+
+  /// Used by the generated validators to check whether a field is included in the field mask (if there is one), either because it is covered by one of the masked paths or because it leads to one of them.
+  #[doc(hidden)]
+  pub fn is_field_in_mask(&self, parent_elements: &[PathElement], field_name: &str) -> bool {
+    let Some(mask_paths) = &self.field_mask_paths else {
+      return true;
+    };
+
+    mask_paths.iter().any(|mask_path| {
+      let field_path = parent_elements
+        .iter()
+        .map(|element| element.name)
+        .chain(std::iter::once(field_name));
+
+      // One of the two paths must be a prefix of the other
+      mask_path
+        .iter()
+        .zip(field_path)
+        .all(|(mask_segment, field_segment)| mask_segment == field_segment)
+    })
+  }
+
+  /// Used by the generated validators to check whether a message-level Cel rule should run with the current field mask, which is the case when all of the fields that it references are covered by the mask. The rules that use `this` as a whole reference all of the fields of the message.
+  #[doc(hidden)]
+  pub fn is_message_rule_in_mask(
+    &self,
+    parent_elements: &[PathElement],
+    referenced_fields: &[&str],
+  ) -> bool {
+    let Some(mask_paths) = &self.field_mask_paths else {
+      return true;
+    };
+
+    referenced_fields.iter().all(|field_name| {
+      mask_paths.iter().any(|mask_path| {
+        let field_path = parent_elements
+          .iter()
+          .map(|element| element.name)
+          .chain(std::iter::once(*field_name));
+
+        // Unlike for field rules, the field must be covered entirely, so the masked path must be a prefix of the field path
+        mask_path.len() <= parent_elements.len() + 1
+          && mask_path
+            .iter()
+            .zip(field_path)
+            .all(|(mask_segment, field_segment)| mask_segment == field_segment)
+      })
+    })
+  }
+
+  /// Used by the generated validators to check whether they can descend into a nested message, given the current number of parent messages.
+  #[doc(hidden)]
+  pub fn is_max_depth_exceeded(&self, depth: usize) -> bool {
+    self
+      .options
+      .max_depth
+      .is_some_and(|max| depth > max)
+  }
+
+  /// Used by the generated validators to check whether the items of a repeated field or a map should be validated in parallel.
+  /// Fail-fast validation always stays sequential, since it stops at the first violation anyway.
+  #[cfg(feature = "rayon")]
+  #[doc(hidden)]
+  pub fn should_validate_in_parallel(&self, items_count: usize) -> bool {
+    !self.is_fail_fast() && items_count >= self.options.parallel_threshold()
+  }
+
+  /// Adds a variable that will be available in every Cel expression under the given name.
+  #[cfg(feature = "cel")]
+  #[must_use]
+  pub fn with_cel_variable(mut self, name: impl Into<String>, value: impl Into<CelValue>) -> Self {
+    self.add_cel_variable(name, value);
+    self
+  }
+
+  /// Adds a variable that will be available in every Cel expression under the given name.
+  #[cfg(feature = "cel")]
+  pub fn add_cel_variable(&mut self, name: impl Into<String>, value: impl Into<CelValue>) {
+    self
+      .cel_variables
+      .insert(name.into(), value.into());
+  }
+
+  /// Returns the Cel variables that were bound to this context.
+  #[cfg(feature = "cel")]
+  pub fn cel_variables(&self) -> &HashMap<String, CelValue> {
+    &self.cel_variables
+  }
+}
+
+/// The category of a validation rule, which can be used to enable or disable entire groups of rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RuleCategory {
+  /// The standard protovalidate rules, such as `string.min_len` or `required`.
+  Standard,
+  /// Custom rules defined with Cel expressions.
+  Cel,
+}
+
+impl RuleCategory {
+  /// Returns the category of the rule that produced a given violation.
+  pub fn of(violation: &ViolationRecord) -> Self {
+    if violation.is_cel() {
+      Self::Cel
+    } else {
+      Self::Standard
+    }
+  }
+}
+
+/// Options that control how a message is validated.
+///
+/// ```rust,ignore
+/// let options = ValidationOptions::new()
+///   .with_max_depth(32)
+///   .with_max_violations(10)
+///   .skip_category(RuleCategory::Cel);
+///
+/// msg.validate_with(&options)?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ValidationOptions {
+  max_depth: Option<usize>,
+  max_violations: Option<usize>,
+  skipped_categories: Vec<RuleCategory>,
+  skipped_rules: Vec<String>,
+  included_rules: Vec<String>,
+  #[cfg(feature = "rayon")]
+  parallel_threshold: Option<usize>,
+}
+
+impl ValidationOptions {
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the maximum amount of nested messages that will be validated. When a message is nested more deeply than this, validation stops and a violation with the `max_depth` rule id is returned for it.
+  #[must_use]
+  pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = Some(max_depth);
+    self
+  }
+
+  /// Sets the maximum amount of violations that will be collected. Validation stops as soon as this amount is reached.
+  /// A value of 0 means that there is no limit.
+  #[must_use]
+  pub fn with_max_violations(mut self, max_violations: usize) -> Self {
+    self.max_violations = (max_violations > 0).then_some(max_violations);
+    self
+  }
+
+  /// Skips all of the rules that belong to the given category. Skipped Cel rules are not executed at all.
+  #[must_use]
+  pub fn skip_category(mut self, category: RuleCategory) -> Self {
+    self.skipped_categories.push(category);
+    self
+  }
+
+  /// Skips the rules whose id matches the given pattern. Patterns can contain `*` as a wildcard, so that `string.*` matches all string rules.
+  #[must_use]
+  pub fn skip_rule(mut self, pattern: impl Into<String>) -> Self {
+    self.skipped_rules.push(pattern.into());
+    self
+  }
+
+  /// Only reports the rules whose id matches one of the included patterns. Patterns can contain `*` as a wildcard.
+  /// If no patterns are included, all rules are reported (except for the ones that are skipped).
+  #[must_use]
+  pub fn include_rule(mut self, pattern: impl Into<String>) -> Self {
+    self.included_rules.push(pattern.into());
+    self
+  }
+
+  /// Sets the amount of items that a repeated field or a map must contain for its items to be validated in parallel. Defaults to [`DEFAULT_PARALLEL_THRESHOLD`](crate::parallel::DEFAULT_PARALLEL_THRESHOLD).
+  #[cfg(feature = "rayon")]
+  #[must_use]
+  pub fn with_parallel_threshold(mut self, threshold: usize) -> Self {
+    self.parallel_threshold = Some(threshold);
+    self
+  }
+
+  /// Returns the amount of items above which repeated fields and maps are validated in parallel.
+  #[cfg(feature = "rayon")]
+  pub fn parallel_threshold(&self) -> usize {
+    self
+      .parallel_threshold
+      .unwrap_or(crate::parallel::DEFAULT_PARALLEL_THRESHOLD)
+  }
+
+  /// Returns the maximum nesting depth, if one was set.
+  pub fn max_depth(&self) -> Option<usize> {
+    self.max_depth
+  }
+
+  /// Returns the maximum amount of violations, if one was set.
+  pub fn max_violations(&self) -> Option<usize> {
+    self.max_violations
+  }
+
+  /// Returns `true` if a rule with the given category and id should be evaluated.
+  pub fn is_rule_enabled(&self, category: RuleCategory, rule_id: &str) -> bool {
+    if self.skipped_categories.contains(&category) {
+      return false;
+    }
+
+    if self
+      .skipped_rules
+      .iter()
+      .any(|pattern| matches_pattern(pattern, rule_id))
+    {
+      return false;
+    }
+
+    self.included_rules.is_empty()
+      || self
+        .included_rules
+        .iter()
+        .any(|pattern| matches_pattern(pattern, rule_id))
+  }
+}
+
+fn matches_pattern(pattern: &str, rule_id: &str) -> bool {
+  if !pattern.contains('*') {
+    return pattern == rule_id;
+  }
+
+  let parts: Vec<&str> = pattern.split('*').collect();
+  let last_index = parts.len() - 1;
+
+  let Some(mut remaining) = rule_id.strip_prefix(parts[0]) else {
+    return false;
+  };
+
+  for part in &parts[1..last_index] {
+    match remaining.find(part) {
+      Some(position) => remaining = &remaining[position + part.len()..],
+      None => return false,
+    };
+  }
+
+  remaining.ends_with(parts[last_index])
+}
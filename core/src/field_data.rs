@@ -1,22 +1,195 @@
+use std::{borrow::Cow, fmt, marker::PhantomData};
+
 use proto_types::FieldType;
 
 use crate::{
-  protovalidate::{field_path_element::Subscript, FieldPathElement},
+  protovalidate::{field_path_element::Subscript, FieldPathElement, Violation},
   ProtoType,
 };
 
 /// The context for the field being validated.
 #[derive(Clone, Debug)]
 pub struct FieldContext<'a> {
-  pub proto_name: &'a str,
+  pub proto_name: &'static str,
   pub tag: u32,
-  pub parent_elements: &'a [FieldPathElement],
-  pub subscript: Option<Subscript>,
+  pub parent_elements: &'a [PathElement<'a>],
+  pub subscript: Option<PathSubscript<'a>>,
   pub key_type: Option<ProtoType>,
   pub value_type: Option<ProtoType>,
   pub field_kind: FieldKind,
 }
 
+/// A compact element of a field path, which borrows from the message being validated rather than allocating.
+/// It only gets turned into a [`FieldPathElement`] when a violation is converted into its protovalidate representation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathElement<'a> {
+  pub name: &'static str,
+  pub tag: Option<u32>,
+  pub field_type: Option<i32>,
+  pub key_type: Option<i32>,
+  pub value_type: Option<i32>,
+  pub subscript: Option<PathSubscript<'a>>,
+}
+
+/// The borrowed equivalent of a protovalidate [`Subscript`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSubscript<'a> {
+  Index(u64),
+  BoolKey(bool),
+  IntKey(i64),
+  UintKey(u64),
+  StringKey(Cow<'a, str>),
+}
+
+impl PathSubscript<'_> {
+  /// Copies the borrowed data (if there is any) so that the subscript can outlive the message being validated.
+  pub fn into_owned(self) -> PathSubscript<'static> {
+    match self {
+      PathSubscript::Index(v) => PathSubscript::Index(v),
+      PathSubscript::BoolKey(v) => PathSubscript::BoolKey(v),
+      PathSubscript::IntKey(v) => PathSubscript::IntKey(v),
+      PathSubscript::UintKey(v) => PathSubscript::UintKey(v),
+      PathSubscript::StringKey(v) => PathSubscript::StringKey(Cow::Owned(v.into_owned())),
+    }
+  }
+
+  pub fn to_subscript(&self) -> Subscript {
+    match self {
+      PathSubscript::Index(v) => Subscript::Index(*v),
+      PathSubscript::BoolKey(v) => Subscript::BoolKey(*v),
+      PathSubscript::IntKey(v) => Subscript::IntKey(*v),
+      PathSubscript::UintKey(v) => Subscript::UintKey(*v),
+      PathSubscript::StringKey(v) => Subscript::StringKey(v.to_string()),
+    }
+  }
+}
+
+impl PathElement<'_> {
+  /// Copies the borrowed data (if there is any) so that the element can outlive the message being validated.
+  pub fn into_owned(self) -> PathElement<'static> {
+    PathElement {
+      name: self.name,
+      tag: self.tag,
+      field_type: self.field_type,
+      key_type: self.key_type,
+      value_type: self.value_type,
+      subscript: self.subscript.map(PathSubscript::into_owned),
+    }
+  }
+
+  pub fn to_field_path_element(&self) -> FieldPathElement {
+    FieldPathElement {
+      field_name: Some(self.name.to_string()),
+      field_number: self.tag.map(|tag| tag as i32),
+      field_type: self.field_type,
+      key_type: self.key_type,
+      value_type: self.value_type,
+      subscript: self
+        .subscript
+        .as_ref()
+        .map(PathSubscript::to_subscript),
+    }
+  }
+}
+
+/// A path to a field of the message `M`, which can be used to look up violations with [`ViolationsExt`](crate::violations::ViolationsExt).
+///
+/// These are built with the field enums that are generated for every message, such as `JediFightField::ObiWan` or `JediFightField::obi_wan(ObiWanField::HasHighGround)` for nested fields, so that renaming or removing a field in the schema turns the lookups that use it into compile errors.
+pub struct FieldSelector<M> {
+  names: Vec<&'static str>,
+  _message: PhantomData<fn() -> M>,
+}
+
+impl<M> FieldSelector<M> {
+  /// Used by the generated field enums to create a path to one of the fields of the message.
+  #[doc(hidden)]
+  pub fn new(name: &'static str) -> Self {
+    Self {
+      names: vec![name],
+      _message: PhantomData,
+    }
+  }
+
+  /// Used by the generated field enums to create a path to a field of a nested message.
+  #[doc(hidden)]
+  pub fn nested<N>(name: &'static str, path: FieldSelector<N>) -> Self {
+    let mut names = Vec::with_capacity(path.names.len() + 1);
+    names.push(name);
+    names.extend(path.names);
+
+    Self {
+      names,
+      _message: PhantomData,
+    }
+  }
+
+  /// Returns the names of the fields in this path.
+  pub fn names(&self) -> &[&'static str] {
+    &self.names
+  }
+
+  /// Returns `true` if the field path of the violation (ignoring list indexes and map keys) is the same as this path.
+  pub fn matches(&self, violation: &Violation) -> bool {
+    violation
+      .field
+      .as_ref()
+      .is_some_and(|field_path| {
+        field_path.elements.len() == self.names.len()
+          && field_path
+            .elements
+            .iter()
+            .zip(&self.names)
+            .all(|(element, name)| element.field_name() == *name)
+      })
+  }
+}
+
+/// Converts a value into a [`FieldSelector`] for the given message. This is implemented by the generated field enums, such as `JediFightField`.
+pub trait IntoFieldSelector {
+  type Message;
+
+  fn into_field_selector(self) -> FieldSelector<Self::Message>;
+}
+
+impl<M> IntoFieldSelector for FieldSelector<M> {
+  type Message = M;
+
+  fn into_field_selector(self) -> Self {
+    self
+  }
+}
+
+impl<M> Clone for FieldSelector<M> {
+  fn clone(&self) -> Self {
+    Self {
+      names: self.names.clone(),
+      _message: PhantomData,
+    }
+  }
+}
+
+impl<M> PartialEq for FieldSelector<M> {
+  fn eq(&self, other: &Self) -> bool {
+    self.names == other.names
+  }
+}
+
+impl<M> Eq for FieldSelector<M> {}
+
+impl<M> fmt::Debug for FieldSelector<M> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("FieldSelector")
+      .field(&self.names)
+      .finish()
+  }
+}
+
+impl<M> fmt::Display for FieldSelector<M> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.names.join("."))
+  }
+}
+
 /// The kind of field being validated. This extra context helps generating more precise violation reports.
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum FieldKind {
@@ -3,11 +3,27 @@
 
 pub(crate) use proto_types::{field_descriptor_proto::Type as ProtoType, protovalidate};
 
+/// The runtime context that is passed down to nested messages during validation.
+pub mod context;
+
 /// The context about the field being validated that is passed to the validators.
 pub mod field_data;
 
+/// The helpers used to validate the items of large repeated fields and maps in parallel.
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
+/// The static description of the rules of each message, returned by the generated `validation_schema` methods.
+pub mod schema;
+
+/// The validation tables and the shared interpreter used by the messages that opt into the table-driven validation mode.
+pub mod table;
+
 /// The functions executing the validation logic. These are called by the validators added by [`protocheck-proc-macro`](https://docs.rs/protocheck-proc-macro/0.1.0/protocheck_proc_macro/) to the target structs.
 pub mod validators;
 
+/// The compact violation records collected internally by the validators.
+pub mod violations;
+
 #[cfg(feature = "cel")]
 pub use cel;
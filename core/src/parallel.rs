@@ -0,0 +1,41 @@
+use rayon::prelude::*;
+
+use crate::{context::ValidationContext, field_data::PathElement, violations::ViolationRecord};
+
+/// The amount of items that a repeated field or a map must contain for its items to be validated in parallel, unless a different threshold is set with [`ValidationOptions::with_parallel_threshold`](crate::context::ValidationOptions::with_parallel_threshold).
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 1024;
+
+/// Validates a list of items in parallel, calling `validate_item` with the index of each item, the item itself, and its own copy of the parent path and of the list of violations.
+///
+/// The violations of each item are merged back in the same order as the items, so the result is the same as the one produced by the sequential validators.
+pub fn validate_items<'a, T, F>(
+  items: &'a [T],
+  validation_context: &ValidationContext,
+  parent_elements: &[PathElement<'a>],
+  violations: &mut Vec<ViolationRecord>,
+  validate_item: F,
+) where
+  T: Sync,
+  F: Fn(usize, &'a T, &mut Vec<PathElement<'a>>, &mut Vec<ViolationRecord>) + Sync,
+{
+  let items_violations: Vec<Vec<ViolationRecord>> = items
+    .par_iter()
+    .enumerate()
+    .map_init(
+      || parent_elements.to_vec(),
+      |parent_messages, (index, item)| {
+        let mut item_violations = Vec::new();
+        validate_item(index, item, parent_messages, &mut item_violations);
+        item_violations
+      },
+    )
+    .collect();
+
+  for violation in items_violations.into_iter().flatten() {
+    violations.push(violation);
+
+    if validation_context.should_stop(violations) {
+      return;
+    }
+  }
+}
@@ -0,0 +1,131 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// The rules defined for a message, its fields and its oneofs. This is returned by the `validation_schema` method that is generated for every message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MessageRulesInfo {
+  /// The full name of the message, such as `myapp.v1.User`.
+  pub full_name: &'static str,
+  pub fields: &'static [FieldRulesInfo],
+  pub oneofs: &'static [OneofRulesInfo],
+  /// The Cel rules that apply to the message as a whole.
+  pub cel: &'static [CelRuleInfo],
+}
+
+impl MessageRulesInfo {
+  /// Returns the info for the field with the given name, if there is one.
+  pub fn field(&self, name: &str) -> Option<&FieldRulesInfo> {
+    self
+      .fields
+      .iter()
+      .find(|field| field.name == name)
+  }
+}
+
+/// The rules defined for a field.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FieldRulesInfo {
+  pub name: &'static str,
+  /// The name of the field in the JSON representation of the message, which is either the `json_name` option or the name of the field in lowerCamelCase.
+  pub json_name: &'static str,
+  pub tag: u32,
+  /// The name of the protobuf type of the field, such as `string` or `message`. For repeated fields and maps, this is the type of the items or of the values.
+  pub field_type: &'static str,
+  pub is_repeated: bool,
+  pub is_map: bool,
+  pub required: bool,
+  /// The standard rules of the field, such as `string.min_len`.
+  pub rules: &'static [RuleInfo],
+  pub cel: &'static [CelRuleInfo],
+  /// The rules for the items of a repeated field.
+  pub items: Option<&'static FieldRulesInfo>,
+  /// The rules for the keys of a map.
+  pub keys: Option<&'static FieldRulesInfo>,
+  /// The rules for the values of a map.
+  pub values: Option<&'static FieldRulesInfo>,
+  /// The function that returns the schema of the message type of the field (or of its items or values), when that message is validated as well. This is not set for the fields in a oneof.
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub message: Option<fn() -> &'static MessageRulesInfo>,
+}
+
+// The linked message schemas are compared by their full name, since function pointers cannot be compared reliably
+impl PartialEq for FieldRulesInfo {
+  fn eq(&self, other: &Self) -> bool {
+    let message_name = |info: &Self| {
+      info
+        .message_schema()
+        .map(|schema| schema.full_name)
+    };
+
+    self.name == other.name
+      && self.json_name == other.json_name
+      && self.tag == other.tag
+      && self.field_type == other.field_type
+      && self.is_repeated == other.is_repeated
+      && self.is_map == other.is_map
+      && self.required == other.required
+      && self.rules == other.rules
+      && self.cel == other.cel
+      && self.items == other.items
+      && self.keys == other.keys
+      && self.values == other.values
+      && message_name(self) == message_name(other)
+  }
+}
+
+impl FieldRulesInfo {
+  /// Returns the schema of the message type of the field, if it is linked (see [`message`](FieldRulesInfo::message)).
+  pub fn message_schema(&self) -> Option<&'static MessageRulesInfo> {
+    self.message.map(|schema| schema())
+  }
+
+  /// Returns the rule with the given id (such as `string.max_len`), if it is defined for this field.
+  pub fn rule(&self, id: &str) -> Option<&RuleInfo> {
+    self.rules.iter().find(|rule| rule.id == id)
+  }
+}
+
+/// A standard rule, with its id and the value that it was set to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RuleInfo {
+  /// The id of the rule, such as `string.min_len` or `int32.in`.
+  pub id: &'static str,
+  pub value: RuleValue,
+}
+
+/// The value of a standard rule.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum RuleValue {
+  Bool(bool),
+  Int(i64),
+  Uint(u64),
+  Double(f64),
+  String(&'static str),
+  Bytes(&'static [u8]),
+  Duration { seconds: i64, nanos: i32 },
+  Timestamp { seconds: i64, nanos: i32 },
+  List(&'static [RuleValue]),
+}
+
+/// A custom rule defined with a Cel expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CelRuleInfo {
+  pub id: &'static str,
+  pub message: &'static str,
+  pub expression: &'static str,
+}
+
+/// The rules defined for a oneof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct OneofRulesInfo {
+  pub name: &'static str,
+  pub required: bool,
+  pub fields: &'static [&'static str],
+}
@@ -0,0 +1,136 @@
+use crate::{
+  context::{RuleCategory, ValidationContext},
+  field_data::{FieldContext, FieldKind, PathElement},
+  validators::required::required,
+  violations::ViolationRecord,
+};
+
+/// A value extracted from a message field by the accessor of a [`TableField`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableValue<'a> {
+  String(&'a str),
+  Bool(bool),
+  Int32(i32),
+  Int64(i64),
+  Uint32(u32),
+  Uint64(u64),
+  Float(f32),
+  Double(f64),
+}
+
+impl TableValue<'_> {
+  /// Returns `true` if the value is the protobuf default for its type. Used for fields with `ignore = IGNORE_IF_ZERO_VALUE`.
+  pub fn is_zero(&self) -> bool {
+    match self {
+      Self::String(v) => v.is_empty(),
+      Self::Bool(v) => !v,
+      Self::Int32(v) => *v == 0,
+      Self::Int64(v) => *v == 0,
+      Self::Uint32(v) => *v == 0,
+      Self::Uint64(v) => *v == 0,
+      Self::Float(v) => *v == 0.0,
+      Self::Double(v) => *v == 0.0,
+    }
+  }
+}
+
+/// A single rule in a validation table.
+///
+/// The check is generated by the macro from the same validator call that would be expanded inline, so both modes run the same logic. Values of a different type than the one the rule was generated for are ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct TableRule {
+  pub rule_id: &'static str,
+  pub check: fn(&FieldContext, TableValue) -> Result<(), ViolationRecord>,
+}
+
+/// The static description of a field and its rules, which is generated for messages using the table-driven validation mode.
+#[derive(Debug)]
+pub struct TableField<M> {
+  pub proto_name: &'static str,
+  pub tag: u32,
+  pub field_kind: FieldKind,
+  pub is_required: bool,
+  pub ignore_if_zero: bool,
+  /// Extracts the value of the field from the message, returning `None` if an optional field is not set.
+  pub accessor: fn(&M) -> Option<TableValue<'_>>,
+  pub rules: &'static [TableRule],
+}
+
+/// Validates a message by walking its validation table. This is the shared interpreter that is called by the messages that use the table-driven validation mode, instead of having the validators for each field expanded inline.
+pub fn validate_table<M>(
+  message: &M,
+  table: &[TableField<M>],
+  validation_context: &ValidationContext,
+  parent_elements: &[PathElement],
+  violations: &mut Vec<ViolationRecord>,
+) {
+  for field in table {
+    let value = (field.accessor)(message);
+
+    if validate_table_field(
+      field,
+      value,
+      validation_context,
+      parent_elements,
+      violations,
+    ) {
+      return;
+    }
+  }
+}
+
+/// Validates the value of a single field of a validation table, where `None` means that an optional field is not set. Returns `true` if the validation should stop.
+pub fn validate_table_field<M>(
+  field: &TableField<M>,
+  value: Option<TableValue>,
+  validation_context: &ValidationContext,
+  parent_elements: &[PathElement],
+  violations: &mut Vec<ViolationRecord>,
+) -> bool {
+  if !validation_context.is_field_in_mask(parent_elements, field.proto_name) {
+    return false;
+  }
+
+  let field_context = FieldContext {
+    proto_name: field.proto_name,
+    tag: field.tag,
+    parent_elements,
+    subscript: None,
+    key_type: None,
+    value_type: None,
+    field_kind: field.field_kind,
+  };
+
+  let Some(value) = value else {
+    if field.is_required
+      && validation_context.is_rule_enabled(RuleCategory::Standard, "field.required")
+    {
+      violations.push(required(&field_context));
+
+      return validation_context.should_stop(violations);
+    }
+
+    return false;
+  };
+
+  if field.ignore_if_zero && value.is_zero() {
+    return false;
+  }
+
+  for rule in field.rules {
+    // The filters are checked before running the rule, just like the inline validators do
+    if !validation_context.is_rule_enabled(RuleCategory::Standard, rule.rule_id) {
+      continue;
+    }
+
+    if let Err(violation) = (rule.check)(&field_context, value) {
+      violations.push(violation);
+
+      if validation_context.should_stop(violations) {
+        return true;
+      }
+    }
+  }
+
+  false
+}
@@ -6,8 +6,8 @@ use regex::Regex;
 use super::well_known_strings::{is_valid_ip, is_valid_ipv4, is_valid_ipv6};
 use crate::{
   field_data::FieldContext,
-  protovalidate::Violation,
   validators::static_data::{base_violations::create_violation, bytes_violations::*},
+  violations::ViolationRecord,
 };
 
 macro_rules! create_bytes_violation {
@@ -28,7 +28,7 @@ macro_rules! well_known_rule {
     $definition:literal
   ) => {
     paste! {
-      pub fn $name(field_context: &FieldContext, value: &Bytes) -> Result<(), Violation> {
+      pub fn $name(field_context: &FieldContext, value: &Bytes) -> Result<(), ViolationRecord> {
         let string_val = parse_bytes_input(value, field_context)?;
         let check = [<is_valid _ $name>](string_val);
 
@@ -61,7 +61,7 @@ macro_rules! bytes_validator {
       value: &Bytes,
       target: $target_type,
       error_message: &'static str,
-    ) -> Result<(), Violation> {
+    ) -> Result<(), ViolationRecord> {
       let check = _generate_check!($mode, $validation_expression, value, target, field_context);
 
       create_bytes_violation!(check, field_context, $name, error_message)
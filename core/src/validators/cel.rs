@@ -1,14 +1,27 @@
-use std::{sync::LazyLock, vec};
+use std::{
+  cell::RefCell,
+  collections::BTreeMap,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    RwLock,
+  },
+};
 
-use cel::{Context, Program, Value as CelValue};
+use cel::{Context, ExecutionError, Program, Value as CelValue};
 use chrono::Utc;
 use proto_types::cel::CelConversionError;
 
 use crate::{
-  field_data::FieldContext,
-  protovalidate::{FieldPath, FieldPathElement, Violation},
-  validators::static_data::base_violations::create_violation,
-  ProtoType,
+  context::ValidationContext,
+  field_data::{FieldContext, PathElement},
+  validators::{
+    native_cel::{
+      create_cel_message_violation, CEL_INTERNAL_ERROR_ID, CEL_INTERNAL_ERROR_MESSAGE,
+      CEL_VIOLATION,
+    },
+    static_data::base_violations::create_violation,
+  },
+  violations::ViolationRecord,
 };
 
 pub struct CelRule {
@@ -18,11 +31,103 @@ pub struct CelRule {
   pub item_full_name: &'static str,
 }
 
+/// A function that registers a custom function in a Cel [`Context`].
+pub type CelFunctionRegistration = fn(&mut Context<'static>);
+
+static CEL_FUNCTIONS: RwLock<BTreeMap<String, CelFunctionRegistration>> =
+  RwLock::new(BTreeMap::new());
+
+// Incremented after every registration, so that the cached base contexts know when they must be rebuilt
+static CEL_FUNCTIONS_VERSION: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+  // The context with the builtin and the custom functions, which is built once per thread (and rebuilt only if new functions are registered). Each evaluation only adds its variables in an inner scope of this context.
+  static BASE_CEL_CONTEXT: RefCell<Option<(usize, Context<'static>)>> = const { RefCell::new(None) };
+}
+
+/// Registers a custom rust function that will be available in every Cel program executed during validation.
+/// Registering a function with a name that is already in use replaces the previous function.
+/// The [`register_cel_functions`](crate::register_cel_functions) macro provides a more concise way of calling this.
+///
+/// The functions must also be declared at build time with `protocheck_build::declare_cel_functions`, so that the expressions that use them can be checked during compilation.
+pub fn register_cel_function(name: impl Into<String>, registration: CelFunctionRegistration) {
+  let mut functions = CEL_FUNCTIONS
+    .write()
+    .unwrap_or_else(|e| e.into_inner());
+
+  functions.insert(name.into(), registration);
+  CEL_FUNCTIONS_VERSION.fetch_add(1, Ordering::Release);
+}
+
+/// Registers custom rust functions that will be available in every Cel program executed during validation.
+/// Registering a function with a name that is already in use replaces the previous function.
+///
+/// ```rust,ignore
+/// fn is_valid_sku(sku: Arc<String>) -> bool {
+///   sku.starts_with("SKU-")
+/// }
+///
+/// protocheck::register_cel_functions!("isValidSku" => is_valid_sku);
+/// ```
+#[macro_export]
+macro_rules! register_cel_functions {
+  ($($name:literal => $func:expr),* $(,)?) => {
+    $(
+      $crate::validators::cel::register_cel_function($name, |cel_context| {
+        cel_context.add_function($name, $func);
+      });
+    )*
+  };
+}
+
+fn execute_cel_program(
+  validation_context: &ValidationContext,
+  program: &Program,
+  this: CelValue,
+) -> Result<CelValue, ExecutionError> {
+  BASE_CEL_CONTEXT.with(|base_context| {
+    let version = CEL_FUNCTIONS_VERSION.load(Ordering::Acquire);
+    let is_stale =
+      !matches!(&*base_context.borrow(), Some((built_version, _)) if *built_version == version);
+
+    if is_stale {
+      let mut cel_context = Context::default();
+
+      for registration in CEL_FUNCTIONS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+      {
+        registration(&mut cel_context);
+      }
+
+      *base_context.borrow_mut() = Some((version, cel_context));
+    }
+
+    let cached_context = base_context.borrow();
+    let (_, base_context) = cached_context
+      .as_ref()
+      .expect("the base Cel context is initialized above");
+
+    let mut cel_context = base_context.new_inner_scope();
+
+    for (name, value) in validation_context.cel_variables() {
+      cel_context.add_variable_from_value(name.as_str(), value.clone());
+    }
+
+    cel_context.add_variable_from_value("now", CelValue::Timestamp(Utc::now().into()));
+    cel_context.add_variable_from_value("this", this);
+
+    program.execute(&cel_context)
+  })
+}
+
 pub fn validate_cel_field_with_val(
+  validation_context: &ValidationContext,
   field_context: &FieldContext,
   rule: CelRule,
   value: CelValue,
-) -> Result<(), Violation>
+) -> Result<(), ViolationRecord>
 where
 {
   let CelRule {
@@ -32,17 +137,7 @@ where
     ..
   } = rule;
 
-  let error_prefix = format!(
-    "Error during Cel validation for field {}:",
-    field_context.proto_name
-  );
-
-  let mut cel_context = Context::default();
-  cel_context.add_variable_from_value("now", CelValue::Timestamp(Utc::now().into()));
-
-  cel_context.add_variable_from_value("this", value);
-
-  let result = program.execute(&cel_context);
+  let result = execute_cel_program(validation_context, program, value);
 
   match result {
     Ok(value) => {
@@ -59,35 +154,39 @@ where
         }
       } else {
         eprintln!(
-          "{} expected boolean result from expression, got `{:?}`",
-          error_prefix,
+          "Error during Cel validation for field {}: expected boolean result from expression, got `{:?}`",
+          field_context.proto_name,
           value.type_of()
         );
         Err(create_violation(
           field_context,
           &CEL_VIOLATION,
-          "internal server error",
-          "internal server error",
+          CEL_INTERNAL_ERROR_ID,
+          CEL_INTERNAL_ERROR_MESSAGE,
         ))
       }
     }
     Err(e) => {
-      eprintln!("{} {:?}", error_prefix, e);
+      eprintln!(
+        "Error during Cel validation for field {}: {:?}",
+        field_context.proto_name, e
+      );
       Err(create_violation(
         field_context,
         &CEL_VIOLATION,
-        "internal server error",
-        "internal server error",
+        CEL_INTERNAL_ERROR_ID,
+        CEL_INTERNAL_ERROR_MESSAGE,
       ))
     }
   }
 }
 
 pub fn validate_cel_field_try_into<T>(
+  validation_context: &ValidationContext,
   field_context: &FieldContext,
   rule: CelRule,
   value: T,
-) -> Result<(), Violation>
+) -> Result<(), ViolationRecord>
 where
   T: TryInto<CelValue> + Clone,
   <T as std::convert::TryInto<cel::Value>>::Error: std::fmt::Display,
@@ -95,7 +194,7 @@ where
   let cel_conversion: Result<CelValue, _> = value.try_into();
 
   match cel_conversion {
-    Ok(cel_val) => validate_cel_field_with_val(field_context, rule, cel_val),
+    Ok(cel_val) => validate_cel_field_with_val(validation_context, field_context, rule, cel_val),
     Err(e) => {
       eprintln!(
         "Failed to convert field {} to Cel value: {}",
@@ -105,18 +204,19 @@ where
       Err(create_violation(
         field_context,
         &CEL_VIOLATION,
-        "internal server error",
-        "internal server error",
+        CEL_INTERNAL_ERROR_ID,
+        CEL_INTERNAL_ERROR_MESSAGE,
       ))
     }
   }
 }
 
 pub fn validate_cel_message<T>(
-  parent_elements: &[FieldPathElement],
+  validation_context: &ValidationContext,
+  parent_elements: &[PathElement],
   rule: CelRule,
   value: T,
-) -> Result<(), Violation>
+) -> Result<(), ViolationRecord>
 where
   T: TryInto<CelValue, Error = CelConversionError>,
 {
@@ -127,17 +227,11 @@ where
     item_full_name: message_name,
   } = rule;
 
-  let error_prefix = format!("Error during Cel validation for message {}:", message_name);
-
-  let mut cel_context = Context::default();
-  cel_context.add_variable_from_value("now", CelValue::Timestamp(Utc::now().into()));
-
   let cel_conversion: Result<CelValue, CelConversionError> = value.try_into();
 
   match cel_conversion {
     Ok(cel_val) => {
-      cel_context.add_variable_from_value("this", cel_val);
-      let result = program.execute(&cel_context);
+      let result = execute_cel_program(validation_context, program, cel_val);
 
       match result {
         Ok(value) => {
@@ -153,22 +247,25 @@ where
             }
           } else {
             eprintln!(
-              "{} expected boolean result from expression, got `{:?}`",
-              error_prefix,
+              "Error during Cel validation for message {}: expected boolean result from expression, got `{:?}`",
+              message_name,
               value.type_of()
             );
             Err(create_cel_message_violation(
-              "internal_server_error",
-              "internal server error",
+              CEL_INTERNAL_ERROR_ID,
+              CEL_INTERNAL_ERROR_MESSAGE,
               parent_elements,
             ))
           }
         }
         Err(e) => {
-          eprintln!("{} program failed to compile: {:?}", error_prefix, e);
+          eprintln!(
+            "Error during Cel validation for message {}: program failed to compile: {:?}",
+            message_name, e
+          );
           Err(create_cel_message_violation(
-            "internal_server_error",
-            "internal server error",
+            CEL_INTERNAL_ERROR_ID,
+            CEL_INTERNAL_ERROR_MESSAGE,
             parent_elements,
           ))
         }
@@ -176,46 +273,14 @@ where
     }
     Err(e) => {
       eprintln!(
-        "{} could not convert message to Cel value: {:?}",
-        error_prefix, e
+        "Error during Cel validation for message {}: could not convert message to Cel value: {:?}",
+        message_name, e
       );
       Err(create_cel_message_violation(
-        "internal_server_error",
-        "internal server error",
+        CEL_INTERNAL_ERROR_ID,
+        CEL_INTERNAL_ERROR_MESSAGE,
         parent_elements,
       ))
     }
   }
 }
-
-fn create_cel_message_violation(
-  rule_id: &str,
-  error_message: &str,
-  parent_elements: &[FieldPathElement],
-) -> Violation {
-  let is_nested = !parent_elements.is_empty();
-  let field_path = is_nested.then(|| FieldPath {
-    elements: parent_elements.to_vec(),
-  });
-
-  Violation {
-    message: Some(error_message.to_string()),
-    rule_id: Some(rule_id.to_string()),
-    rule: Some(FieldPath {
-      elements: CEL_VIOLATION.clone(),
-    }),
-    field: field_path,
-    for_key: None,
-  }
-}
-
-static CEL_VIOLATION: LazyLock<Vec<FieldPathElement>> = LazyLock::new(|| {
-  vec![FieldPathElement {
-    field_name: Some("cel".to_string()),
-    field_number: Some(23),
-    field_type: Some(ProtoType::Message as i32),
-    key_type: None,
-    value_type: None,
-    subscript: None,
-  }]
-});
@@ -3,35 +3,35 @@ use proto_types::{Duration, Timestamp};
 
 use crate::{
   field_data::FieldContext,
-  protovalidate::Violation,
   validators::static_data::{
     base_violations::create_violation, gt_violations::*, gte_violations::*, lt_violations::*,
     lte_violations::*,
   },
+  violations::ViolationRecord,
 };
 
 macro_rules! comparable_validator {
   ($proto_type:ident, $rust_type:ty) => {
     paste! {
-      pub fn [< $proto_type _lt >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [< $proto_type _lt >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = value < target;
 
         create_violation!($proto_type, check, field_context, lt, error_message)
       }
 
-      pub fn [< $proto_type _lte >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [< $proto_type _lte >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = value <= target;
 
         create_violation!($proto_type, check, field_context, lte, error_message)
       }
 
-      pub fn [< $proto_type _gt >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [< $proto_type _gt >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = value > target;
 
         create_violation!($proto_type, check, field_context, gt, error_message)
       }
 
-      pub fn [< $proto_type _gte >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [< $proto_type _gte >](field_context: &FieldContext, value: $rust_type, target: $rust_type, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = value >= target;
 
         create_violation!($proto_type, check, field_context, gte, error_message)
@@ -3,14 +3,14 @@ use proto_types::{Duration, Timestamp};
 
 use crate::{
   field_data::FieldContext,
-  protovalidate::Violation,
   validators::static_data::{base_violations::create_violation, const_violations::*},
+  violations::ViolationRecord,
 };
 
 macro_rules! const_validator {
   ($proto_type:ident, $value_type:ty) => {
     paste! {
-      pub fn [< $proto_type _const>](field_context: &FieldContext, value: $value_type, target: $value_type, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [< $proto_type _const>](field_context: &FieldContext, value: $value_type, target: $value_type, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = value == target;
 
         create_violation!($proto_type, check, field_context, const, error_message)
@@ -25,7 +25,7 @@ pub fn bytes_const(
   value: &bytes::Bytes,
   target: &'static [u8],
   error_message: &'static str,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = value == target;
 
   create_violation!(bytes, check, field_context, const, error_message)
@@ -5,10 +5,10 @@ use proto_types::{Any, Duration};
 
 use crate::{
   field_data::FieldContext,
-  protovalidate::Violation,
   validators::static_data::{
     base_violations::create_violation, in_violations::*, not_in_violations::*,
   },
+  violations::ViolationRecord,
 };
 
 macro_rules! in_slice_list_validator {
@@ -33,6 +33,30 @@ macro_rules! in_hashset_list_validator {
   };
 }
 
+macro_rules! in_sorted_list_validator {
+  (
+    $name_ty:ident,
+    $value_ty:ty,
+    $target_ty:ty
+  ) => {
+    in_list_validator!(
+      sorted,
+      $name_ty,
+      $value_ty,
+      [$target_ty],
+      |value: $value_ty, target: &'static [$target_ty]| target.binary_search(&value).is_ok()
+    );
+  };
+  (
+    $name_ty:ident,
+    $value_ty:ty,
+    $target_ty:ty,
+    $value_override:expr
+  ) => {
+    in_list_validator!(sorted, $name_ty, $value_ty, [$target_ty], $value_override);
+  };
+}
+
 macro_rules! in_list_validator {
   (
     $list_ty:ident,
@@ -46,7 +70,7 @@ macro_rules! in_list_validator {
       (, $value:ident, $target:ident) => { $target.contains(&$value) };
     }
     paste! {
-      pub fn [<$name_ty _in_ $list_ty _list>](field_context: &FieldContext, value: $value_ty, target: &'static $target_ty, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [<$name_ty _in_ $list_ty _list>](field_context: &FieldContext, value: $value_ty, target: &'static $target_ty, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = _get_value_for_contains!($($value_override)?, value, target);
 
         if check {
@@ -61,7 +85,7 @@ macro_rules! in_list_validator {
         }
       }
 
-      pub fn [<$name_ty _not_in_ $list_ty _list>](field_context: &FieldContext, value: $value_ty, target: &'static $target_ty, error_message: &'static str) -> Result<(), Violation> {
+      pub fn [<$name_ty _not_in_ $list_ty _list>](field_context: &FieldContext, value: $value_ty, target: &'static $target_ty, error_message: &'static str) -> Result<(), ViolationRecord> {
         let check = !_get_value_for_contains!($($value_override)?, value, target);
 
         if check {
@@ -82,6 +106,14 @@ macro_rules! in_list_validator {
 
 in_hashset_list_validator!(string, &str, &'static str);
 in_slice_list_validator!(string, &str, &'static str);
+in_sorted_list_validator!(
+  string,
+  &str,
+  &'static str,
+  |value: &str, target: &'static [&'static str]| target
+    .binary_search_by(|probe| (*probe).cmp(value))
+    .is_ok()
+);
 
 #[cfg(feature = "bytes")]
 in_hashset_list_validator!(
@@ -110,9 +142,18 @@ in_slice_list_validator!(any, &Any, &'static str, |value: &Any, target: &[&str]|
 
 in_hashset_list_validator!(duration, Duration, Duration);
 in_slice_list_validator!(duration, Duration, Duration);
+in_sorted_list_validator!(
+  duration,
+  Duration,
+  Duration,
+  |value: Duration, target: &'static [Duration]| target
+    .binary_search_by(|probe| (probe.seconds, probe.nanos).cmp(&(value.seconds, value.nanos)))
+    .is_ok()
+);
 
 in_hashset_list_validator!(enum, i32, i32);
 in_slice_list_validator!(enum, i32, i32);
+in_sorted_list_validator!(enum, i32, i32);
 
 in_hashset_list_validator!(
   float,
@@ -131,22 +172,32 @@ in_slice_list_validator!(double, f64, f64);
 
 in_hashset_list_validator!(int64, i64, i64);
 in_slice_list_validator!(int64, i64, i64);
+in_sorted_list_validator!(int64, i64, i64);
 in_hashset_list_validator!(int32, i32, i32);
 in_slice_list_validator!(int32, i32, i32);
+in_sorted_list_validator!(int32, i32, i32);
 in_hashset_list_validator!(sint64, i64, i64);
 in_slice_list_validator!(sint64, i64, i64);
+in_sorted_list_validator!(sint64, i64, i64);
 in_hashset_list_validator!(sint32, i32, i32);
 in_slice_list_validator!(sint32, i32, i32);
+in_sorted_list_validator!(sint32, i32, i32);
 in_hashset_list_validator!(sfixed64, i64, i64);
 in_slice_list_validator!(sfixed64, i64, i64);
+in_sorted_list_validator!(sfixed64, i64, i64);
 in_hashset_list_validator!(sfixed32, i32, i32);
 in_slice_list_validator!(sfixed32, i32, i32);
+in_sorted_list_validator!(sfixed32, i32, i32);
 
 in_hashset_list_validator!(fixed64, u64, u64);
 in_slice_list_validator!(fixed64, u64, u64);
+in_sorted_list_validator!(fixed64, u64, u64);
 in_hashset_list_validator!(fixed32, u32, u32);
 in_slice_list_validator!(fixed32, u32, u32);
+in_sorted_list_validator!(fixed32, u32, u32);
 in_hashset_list_validator!(uint64, u64, u64);
 in_slice_list_validator!(uint64, u64, u64);
+in_sorted_list_validator!(uint64, u64, u64);
 in_hashset_list_validator!(uint32, u32, u32);
 in_slice_list_validator!(uint32, u32, u32);
+in_sorted_list_validator!(uint32, u32, u32);
@@ -0,0 +1,23 @@
+use std::borrow::Cow;
+
+use crate::{field_data::PathElement, violations::ViolationRecord};
+
+/// Creates the violation for a nested message that exceeds the maximum depth set in the [`ValidationOptions`](crate::context::ValidationOptions).
+/// The parent elements must already include the element for the nested message itself.
+pub fn max_depth(parent_elements: &[PathElement], max_depth: usize) -> ViolationRecord {
+  ViolationRecord {
+    rule_id: "max_depth",
+    message: Cow::Owned(format!(
+      "exceeds the maximum nesting depth of {}",
+      max_depth
+    )),
+    field: parent_elements
+      .iter()
+      .cloned()
+      .map(PathElement::into_owned)
+      .collect(),
+    rule_prefix: &[],
+    rule: &[],
+    for_key: false,
+  }
+}
@@ -1,13 +1,12 @@
 use std::sync::LazyLock;
 
 use crate::{
-  field_data::FieldContext,
-  protovalidate::{FieldPathElement, Violation},
-  validators::static_data::base_violations::create_violation,
+  field_data::FieldContext, protovalidate::FieldPathElement,
+  validators::static_data::base_violations::create_violation, violations::ViolationRecord,
   ProtoType,
 };
 
-pub fn defined_only(field_context: &FieldContext, error_message: &'static str) -> Violation {
+pub fn defined_only(field_context: &FieldContext, error_message: &'static str) -> ViolationRecord {
   create_violation(
     field_context,
     &ENUM_DEFINED_ONLY_VIOLATION,
@@ -1,13 +1,12 @@
 use std::sync::LazyLock;
 
 use crate::{
-  field_data::FieldContext,
-  protovalidate::{FieldPathElement, Violation},
-  validators::static_data::base_violations::create_violation,
+  field_data::FieldContext, protovalidate::FieldPathElement,
+  validators::static_data::base_violations::create_violation, violations::ViolationRecord,
   ProtoType,
 };
 
-pub fn float_is_finite(field_context: &FieldContext, value: f32) -> Result<(), Violation> {
+pub fn float_is_finite(field_context: &FieldContext, value: f32) -> Result<(), ViolationRecord> {
   let check = !value.is_nan();
 
   if check {
@@ -22,7 +21,7 @@ pub fn float_is_finite(field_context: &FieldContext, value: f32) -> Result<(), V
   }
 }
 
-pub fn double_is_finite(field_context: &FieldContext, value: f64) -> Result<(), Violation> {
+pub fn double_is_finite(field_context: &FieldContext, value: f64) -> Result<(), ViolationRecord> {
   let check = !value.is_nan();
 
   if check {
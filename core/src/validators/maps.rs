@@ -1,9 +1,8 @@
 use std::{collections::HashMap, sync::LazyLock};
 
 use crate::{
-  field_data::FieldContext,
-  protovalidate::{FieldPathElement, Violation},
-  validators::static_data::base_violations::create_violation,
+  field_data::FieldContext, protovalidate::FieldPathElement,
+  validators::static_data::base_violations::create_violation, violations::ViolationRecord,
   ProtoType,
 };
 
@@ -12,7 +11,7 @@ pub fn min_pairs<K, V>(
   value: &HashMap<K, V>,
   min_pairs: u64,
   error_message: &'static str,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = value.len() >= min_pairs as usize;
 
   if check {
@@ -32,7 +31,7 @@ pub fn max_pairs<K, V>(
   value: &HashMap<K, V>,
   max_pairs: u64,
   error_message: &'static str,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = value.len() <= max_pairs as usize;
 
   if check {
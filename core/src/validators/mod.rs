@@ -21,6 +21,9 @@ pub mod constants;
 /// Validators for lists of allowed (or forbidden) values.
 pub mod containing;
 
+/// Violations for nested messages that exceed the maximum validation depth.
+pub mod depth;
+
 /// Validators for enum fields.
 pub mod enums;
 
@@ -30,6 +33,9 @@ pub mod floats;
 /// Validators for protobuf maps.
 pub mod maps;
 
+/// Validators for Cel rules that were compiled to native rust code, which do not require the `cel` feature.
+pub mod native_cel;
+
 /// Validators for protobuf oneofs.
 pub mod oneofs;
 
@@ -0,0 +1,108 @@
+use std::{borrow::Cow, sync::LazyLock};
+
+use crate::{
+  field_data::{FieldContext, PathElement},
+  protovalidate::FieldPathElement,
+  validators::static_data::base_violations::create_violation,
+  violations::ViolationRecord,
+  ProtoType,
+};
+
+/// The rule id of the violations produced when a Cel expression fails to evaluate.
+pub const CEL_INTERNAL_ERROR_ID: &str = "internal_server_error";
+
+/// The message of the violations produced when a Cel expression fails to evaluate.
+pub const CEL_INTERNAL_ERROR_MESSAGE: &str = "internal server error";
+
+/// Validates a field with a Cel rule that was compiled to native rust code.
+///
+/// The result is `None` if the expression could not be evaluated (for example because of an integer overflow), which is treated the same way as a failed Cel program.
+pub fn validate_native_cel_field(
+  field_context: &FieldContext,
+  rule_id: &'static str,
+  error_message: &'static str,
+  result: Option<bool>,
+) -> Result<(), ViolationRecord> {
+  match result {
+    Some(true) => Ok(()),
+    Some(false) => Err(create_violation(
+      field_context,
+      &CEL_VIOLATION,
+      rule_id,
+      error_message,
+    )),
+    None => {
+      eprintln!(
+        "Error during Cel validation for field {}: the expression failed to evaluate",
+        field_context.proto_name
+      );
+      Err(create_violation(
+        field_context,
+        &CEL_VIOLATION,
+        CEL_INTERNAL_ERROR_ID,
+        CEL_INTERNAL_ERROR_MESSAGE,
+      ))
+    }
+  }
+}
+
+/// Validates a message with a Cel rule that was compiled to native rust code.
+///
+/// The result is `None` if the expression could not be evaluated (for example because of an integer overflow), which is treated the same way as a failed Cel program.
+pub fn validate_native_cel_message(
+  parent_elements: &[PathElement],
+  rule_id: &'static str,
+  error_message: &'static str,
+  message_name: &str,
+  result: Option<bool>,
+) -> Result<(), ViolationRecord> {
+  match result {
+    Some(true) => Ok(()),
+    Some(false) => Err(create_cel_message_violation(
+      rule_id,
+      error_message,
+      parent_elements,
+    )),
+    None => {
+      eprintln!(
+        "Error during Cel validation for message {}: the expression failed to evaluate",
+        message_name
+      );
+      Err(create_cel_message_violation(
+        CEL_INTERNAL_ERROR_ID,
+        CEL_INTERNAL_ERROR_MESSAGE,
+        parent_elements,
+      ))
+    }
+  }
+}
+
+pub(crate) fn create_cel_message_violation(
+  rule_id: &'static str,
+  error_message: &'static str,
+  parent_elements: &[PathElement],
+) -> ViolationRecord {
+  ViolationRecord {
+    rule_id,
+    message: Cow::Borrowed(error_message),
+    field: parent_elements
+      .iter()
+      .cloned()
+      .map(PathElement::into_owned)
+      .collect(),
+    rule_prefix: &[],
+    rule: &CEL_VIOLATION,
+    for_key: false,
+  }
+}
+
+pub(crate) static CEL_VIOLATION: LazyLock<Vec<FieldPathElement>> = LazyLock::new(|| {
+  vec![FieldPathElement {
+    field_name: Some("cel".to_string()),
+    field_number: Some(23),
+    field_type: Some(ProtoType::Message as i32),
+    key_type: None,
+    value_type: None,
+    subscript: None,
+  }]
+});
@@ -1,34 +1,42 @@
+use std::{borrow::Cow, sync::LazyLock};
+
 use crate::{
-  protovalidate::{FieldPath, FieldPathElement, Violation},
-  ProtoType,
+  field_data::PathElement, protovalidate::FieldPathElement, violations::ViolationRecord, ProtoType,
 };
 
-pub fn required(name: &str, parent_elements: &[FieldPathElement]) -> Violation {
-  let mut elements = parent_elements.to_vec();
+pub fn required(name: &'static str, parent_elements: &[PathElement]) -> ViolationRecord {
+  let mut elements: Vec<PathElement<'static>> = parent_elements
+    .iter()
+    .cloned()
+    .map(PathElement::into_owned)
+    .collect();
 
-  elements.push(FieldPathElement {
-    field_name: Some(name.to_string()),
-    field_number: None,
+  elements.push(PathElement {
+    name,
+    tag: None,
     field_type: None,
-    subscript: None,
     key_type: None,
     value_type: None,
+    subscript: None,
   });
 
-  Violation {
-    rule_id: Some("oneof.required".to_string()),
-    message: Some("at least one value is required".to_string()),
-    for_key: None,
-    field: Some(FieldPath { elements }),
-    rule: Some(FieldPath {
-      elements: vec![FieldPathElement {
-        field_name: Some("required".to_string()),
-        field_number: Some(1),
-        field_type: Some(ProtoType::Bool as i32),
-        subscript: None,
-        key_type: None,
-        value_type: None,
-      }],
-    }),
+  ViolationRecord {
+    rule_id: "oneof.required",
+    message: Cow::Borrowed("at least one value is required"),
+    for_key: false,
+    field: elements,
+    rule_prefix: &[],
+    rule: &ONEOF_REQUIRED_VIOLATION,
   }
 }
+
+static ONEOF_REQUIRED_VIOLATION: LazyLock<Vec<FieldPathElement>> = LazyLock::new(|| {
+  vec![FieldPathElement {
+    field_name: Some("required".to_string()),
+    field_number: Some(1),
+    field_type: Some(ProtoType::Bool as i32),
+    subscript: None,
+    key_type: None,
+    value_type: None,
+  }]
+});
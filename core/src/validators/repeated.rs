@@ -1,9 +1,8 @@
 use std::{collections::HashSet, hash::Hash, sync::LazyLock};
 
 use crate::{
-  field_data::FieldContext,
-  protovalidate::{FieldPathElement, Violation},
-  validators::static_data::base_violations::create_violation,
+  field_data::FieldContext, protovalidate::FieldPathElement,
+  validators::static_data::base_violations::create_violation, violations::ViolationRecord,
   ProtoType,
 };
 
@@ -12,7 +11,7 @@ pub fn min_items<T>(
   value: &[T],
   min_items: u64,
   error_message: &'static str,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = value.len() >= min_items as usize;
 
   if check {
@@ -32,7 +31,7 @@ pub fn max_items<T>(
   value: &[T],
   max_items: u64,
   error_message: &'static str,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = value.len() <= max_items as usize;
 
   if check {
@@ -51,7 +50,7 @@ pub fn unique<T>(
   field_context: &FieldContext,
   value: T,
   processed_values: &mut HashSet<T>,
-) -> Result<(), Violation>
+) -> Result<(), ViolationRecord>
 where
   T: Eq + Hash + Clone,
 {
@@ -73,7 +72,7 @@ pub fn unique_f64(
   field_context: &FieldContext,
   value: f64,
   processed_values: &mut HashSet<u64>,
-) -> Result<(), Violation>
+) -> Result<(), ViolationRecord>
 where
 {
   let bits = value.to_bits();
@@ -95,7 +94,7 @@ pub fn unique_f32(
   field_context: &FieldContext,
   value: f32,
   processed_values: &mut HashSet<u32>,
-) -> Result<(), Violation>
+) -> Result<(), ViolationRecord>
 where
 {
   let bits = value.to_bits();
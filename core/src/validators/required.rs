@@ -1,13 +1,12 @@
 use std::sync::LazyLock;
 
 use crate::{
-  field_data::FieldContext,
-  protovalidate::{FieldPathElement, Violation},
-  validators::static_data::base_violations::create_violation,
+  field_data::FieldContext, protovalidate::FieldPathElement,
+  validators::static_data::base_violations::create_violation, violations::ViolationRecord,
   ProtoType,
 };
 
-pub fn required(field_context: &FieldContext) -> Violation {
+pub fn required(field_context: &FieldContext) -> ViolationRecord {
   create_violation(
     field_context,
     &REQUIRED_VIOLATION,
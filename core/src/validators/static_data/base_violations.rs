@@ -1,10 +1,9 @@
-use std::sync::LazyLock;
-
-use proto_types::protovalidate::{FieldPath, Violation};
+use std::{borrow::Cow, sync::LazyLock};
 
 use crate::{
-  field_data::{FieldContext, FieldKind},
+  field_data::{FieldContext, FieldKind, PathElement, PathSubscript},
   protovalidate::FieldPathElement,
+  violations::ViolationRecord,
   ProtoType,
 };
 
@@ -28,54 +27,52 @@ macro_rules! create_violation {
 pub fn create_violation(
   field_context: &FieldContext,
   violation_path: &'static [FieldPathElement],
-  rule_id: &str,
-  error_message: &str,
-) -> Violation {
-  let elements = get_violation_elements(field_context);
-
-  let mut rule_elements = get_base_violations_path(field_context.field_kind);
-
-  rule_elements.extend(violation_path.to_vec());
-
-  Violation {
-    rule_id: Some(rule_id.to_string()),
-    message: Some(error_message.to_string()),
-    for_key: field_context.field_kind.is_map_key().then_some(true),
-    field: Some(FieldPath { elements }),
-    rule: Some(FieldPath {
-      elements: rule_elements,
-    }),
+  rule_id: &'static str,
+  error_message: &'static str,
+) -> ViolationRecord {
+  ViolationRecord {
+    rule_id,
+    message: Cow::Borrowed(error_message),
+    for_key: field_context.field_kind.is_map_key(),
+    field: get_violation_elements(field_context),
+    rule_prefix: get_base_violations_path(field_context.field_kind),
+    rule: violation_path,
   }
 }
 
-pub fn get_violation_elements(field_context: &FieldContext) -> Vec<FieldPathElement> {
-  let mut elements = field_context.parent_elements.to_vec();
-  let current_elem = FieldPathElement {
+pub fn get_violation_elements(field_context: &FieldContext) -> Vec<PathElement<'static>> {
+  let mut elements: Vec<PathElement<'static>> = field_context
+    .parent_elements
+    .iter()
+    .cloned()
+    .map(PathElement::into_owned)
+    .collect();
+
+  elements.push(PathElement {
+    name: field_context.proto_name,
+    tag: Some(field_context.tag),
     field_type: Some(field_context.field_kind.inner_type().into()),
-    field_name: Some(field_context.proto_name.to_string()),
     key_type: field_context.key_type.map(|t| t as i32),
     value_type: field_context.value_type.map(|t| t as i32),
-    field_number: Some(field_context.tag as i32),
-    subscript: field_context.subscript.clone(),
-  };
-
-  elements.push(current_elem);
+    subscript: field_context
+      .subscript
+      .clone()
+      .map(PathSubscript::into_owned),
+  });
 
   elements
 }
 
-pub fn get_base_violations_path(field_kind: FieldKind) -> Vec<FieldPathElement> {
-  let mut violations_path = vec![];
-
+pub fn get_base_violations_path(field_kind: FieldKind) -> &'static [FieldPathElement] {
   if field_kind.is_repeated_item() {
-    violations_path.extend(REPEATED_ITEM_VIOLATION.clone());
+    REPEATED_ITEM_VIOLATION.as_slice()
   } else if field_kind.is_map_key() {
-    violations_path.extend(MAP_KEY_VIOLATION.clone());
+    MAP_KEY_VIOLATION.as_slice()
   } else if field_kind.is_map_value() {
-    violations_path.extend(MAP_VALUE_VIOLATION.clone());
+    MAP_VALUE_VIOLATION.as_slice()
+  } else {
+    &[]
   }
-
-  violations_path
 }
 
 static MAP_KEY_VIOLATION: LazyLock<Vec<FieldPathElement>> = LazyLock::new(|| {
@@ -1,29 +1,29 @@
-use std::{str::from_utf8, sync::LazyLock};
+use std::{borrow::Cow, str::from_utf8, sync::LazyLock};
 
 use bytes::Bytes;
-use proto_types::{
-  field_descriptor_proto::Type as ProtoType,
-  protovalidate::{FieldPath, FieldPathElement, Violation},
-};
+use proto_types::{field_descriptor_proto::Type as ProtoType, protovalidate::FieldPathElement};
 
 use crate::{
-  field_data::FieldContext, validators::static_data::base_violations::get_violation_elements,
+  field_data::{FieldContext, PathElement},
+  validators::static_data::base_violations::get_violation_elements,
+  violations::ViolationRecord,
 };
 
-fn get_invalid_bytes_violation(elements: Vec<FieldPathElement>) -> Violation {
-  Violation {
-    rule_id: Some("utf8_error".to_string()),
-    message: Some("invalid utf8 bytes".to_string()),
-    field: Some(FieldPath { elements }),
-    rule: None,
-    for_key: None,
+fn get_invalid_bytes_violation(elements: Vec<PathElement<'static>>) -> ViolationRecord {
+  ViolationRecord {
+    rule_id: "utf8_error",
+    message: Cow::Borrowed("invalid utf8 bytes"),
+    field: elements,
+    rule_prefix: &[],
+    rule: &[],
+    for_key: false,
   }
 }
 
 pub(crate) fn parse_bytes_input<'a>(
   value: &'a Bytes,
   field_context: &'a FieldContext<'a>,
-) -> Result<&'a str, Violation> {
+) -> Result<&'a str, ViolationRecord> {
   from_utf8(value).map_err(|_| {
     let elements = get_violation_elements(field_context);
     get_invalid_bytes_violation(elements)
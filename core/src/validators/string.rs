@@ -5,8 +5,8 @@ use super::well_known_strings::ip::*;
 use super::well_known_strings::*;
 use crate::{
   field_data::FieldContext,
-  protovalidate::Violation,
   validators::static_data::{base_violations::create_violation, strings_violations::*},
+  violations::ViolationRecord,
 };
 
 macro_rules! create_string_violation {
@@ -27,7 +27,7 @@ macro_rules! well_known_rule {
     $definition:literal
   ) => {
     paste! {
-      pub fn $name(field_context: &FieldContext, value: &str) -> Result<(), Violation> {
+      pub fn $name(field_context: &FieldContext, value: &str) -> Result<(), ViolationRecord> {
         let check = [<is_valid _ $name>](value);
 
         create_string_violation!(check, field_context, $name, concat!("must be a valid ", $definition))
@@ -47,7 +47,7 @@ macro_rules! string_validator {
       value: &str,
       target: $target_type,
       error_message: &'static str,
-    ) -> Result<(), Violation> {
+    ) -> Result<(), ViolationRecord> {
       let check = ($validation_expression)(value, target);
 
       create_string_violation!(check, field_context, $name, error_message)
@@ -132,7 +132,7 @@ pub fn header_name(
   field_context: &FieldContext,
   value: &str,
   strict: bool,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = is_valid_http_header_name(value, strict);
 
   if check {
@@ -152,7 +152,7 @@ pub fn header_value(
   field_context: &FieldContext,
   value: &str,
   strict: bool,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = is_valid_http_header_value(value, strict);
 
   if check {
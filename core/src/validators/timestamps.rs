@@ -1,10 +1,11 @@
 use std::sync::LazyLock;
 
-use proto_types::{protovalidate::Violation, Duration, Timestamp};
+use proto_types::{Duration, Timestamp};
 
 use crate::{
   field_data::FieldContext,
   validators::{static_data::base_violations::create_violation, FieldPathElement},
+  violations::ViolationRecord,
   ProtoType,
 };
 
@@ -13,7 +14,7 @@ pub fn within(
   value: Timestamp,
   time_range: Duration,
   error_message: &'static str,
-) -> Result<(), Violation> {
+) -> Result<(), ViolationRecord> {
   let check = value.is_within_range_from_now(time_range);
 
   if check {
@@ -28,7 +29,7 @@ pub fn within(
   }
 }
 
-pub fn lt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Violation> {
+pub fn lt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), ViolationRecord> {
   let check = value.is_past();
 
   if check {
@@ -43,7 +44,7 @@ pub fn lt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Viol
   }
 }
 
-pub fn gt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), Violation> {
+pub fn gt_now(field_context: &FieldContext, value: Timestamp) -> Result<(), ViolationRecord> {
   let check = value.is_future();
 
   if check {
@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use crate::{
+  field_data::{IntoFieldSelector, PathElement},
+  protovalidate::{FieldPath, FieldPathElement, Violation, Violations},
+};
+
+/// A compact record of a violation, which is what the generated validators collect internally.
+///
+/// Rule ids and rule paths are static and the field path is made of [`PathElement`]s, so building one of these is much cheaper than building a full [`Violation`]. The conversion into the protovalidate representation only happens when the violations are returned to the caller.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViolationRecord {
+  pub rule_id: &'static str,
+  pub message: Cow<'static, str>,
+  pub field: Vec<PathElement<'static>>,
+  /// The path to the rule container, such as `repeated.items` for the rules applied to the items of a repeated field.
+  pub rule_prefix: &'static [FieldPathElement],
+  pub rule: &'static [FieldPathElement],
+  pub for_key: bool,
+}
+
+impl ViolationRecord {
+  pub fn rule_id(&self) -> &str {
+    self.rule_id
+  }
+
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Returns `true` if the violation was produced by a Cel rule.
+  pub fn is_cel(&self) -> bool {
+    self
+      .rule
+      .last()
+      .is_some_and(|element| element.field_name() == "cel")
+  }
+
+  /// Converts the record into a protovalidate [`Violation`].
+  pub fn into_violation(self) -> Violation {
+    let field = (!self.field.is_empty()).then(|| FieldPath {
+      elements: self
+        .field
+        .iter()
+        .map(PathElement::to_field_path_element)
+        .collect(),
+    });
+
+    let rule = (!self.rule_prefix.is_empty() || !self.rule.is_empty()).then(|| FieldPath {
+      elements: self
+        .rule_prefix
+        .iter()
+        .chain(self.rule)
+        .cloned()
+        .collect(),
+    });
+
+    Violation {
+      rule_id: Some(self.rule_id.to_string()),
+      message: Some(self.message.into_owned()),
+      for_key: self.for_key.then_some(true),
+      field,
+      rule,
+    }
+  }
+}
+
+impl From<ViolationRecord> for Violation {
+  fn from(value: ViolationRecord) -> Self {
+    value.into_violation()
+  }
+}
+
+/// Converts a list of records into [`Violations`].
+pub fn into_violations(records: Vec<ViolationRecord>) -> Violations {
+  Violations {
+    violations: records
+      .into_iter()
+      .map(ViolationRecord::into_violation)
+      .collect(),
+  }
+}
+
+/// Helpers for looking up violations with the typed paths built from the generated field enums, such as `JediFightField::ObiWan`.
+///
+/// ```rust,ignore
+/// use protocheck::violations::ViolationsExt;
+///
+/// let violations = jedi_fight.validate().unwrap_err();
+///
+/// let obi_wan_violations =
+///   violations.for_field(JediFightField::obi_wan(ObiWanField::HasHighGround));
+/// ```
+pub trait ViolationsExt {
+  /// Returns the violations whose field path matches the given path, ignoring list indexes and map keys.
+  fn for_field(&self, path: impl IntoFieldSelector) -> Vec<&Violation>;
+
+  /// Returns `true` if there is at least one violation for the given path.
+  fn has_field(&self, path: impl IntoFieldSelector) -> bool;
+}
+
+impl ViolationsExt for Violations {
+  fn for_field(&self, path: impl IntoFieldSelector) -> Vec<&Violation> {
+    let path = path.into_field_selector();
+
+    self
+      .violations
+      .iter()
+      .filter(|violation| path.matches(violation))
+      .collect()
+  }
+
+  fn has_field(&self, path: impl IntoFieldSelector) -> bool {
+    let path = path.into_field_selector();
+
+    self
+      .violations
+      .iter()
+      .any(|violation| path.matches(violation))
+  }
+}
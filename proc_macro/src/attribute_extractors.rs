@@ -83,3 +83,44 @@ impl syn::parse::Parse for ProstAttrData {
     Ok(ProstAttrData { enum_path })
   }
 }
+
+pub struct ValidateAttrData {
+  pub message_name: LitStr,
+  pub table_mode: bool,
+  pub example: bool,
+}
+
+impl syn::parse::Parse for ValidateAttrData {
+  fn parse(input: ParseStream) -> Result<Self, Error> {
+    let message_name: LitStr = input.parse()?;
+    let mut table_mode = false;
+    let mut example = false;
+
+    while input.peek(Token![,]) {
+      input.parse::<Token![,]>()?;
+
+      if input.is_empty() {
+        break;
+      }
+
+      let ident: syn::Ident = input.parse()?;
+
+      if ident == "table" {
+        table_mode = true;
+      } else if ident == "example" {
+        example = true;
+      } else {
+        return Err(Error::new_spanned(
+          &ident,
+          format!("Unknown argument {} for protobuf_validate", ident),
+        ));
+      }
+    }
+
+    Ok(ValidateAttrData {
+      message_name,
+      table_mode,
+      example,
+    })
+  }
+}
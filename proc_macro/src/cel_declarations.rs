@@ -0,0 +1,186 @@
+use std::{
+  collections::HashMap,
+  mem::discriminant,
+  sync::{Arc, LazyLock},
+};
+
+use cel::{
+  extractors::Arguments, objects::Key as CelKey, Context, ExecutionError, Value as CelValue,
+};
+use proc_macro2::TokenStream;
+use proto_types::{Duration, Timestamp};
+use quote::quote;
+
+/// The variables declared with `protocheck_build::declare_cel_variables`, along with a default value for their type, which is used when testing the Cel programs at compile time.
+///
+/// The variable is read when the macro is expanded, so cargo does not know about it. To make sure that the crate is rebuilt when it changes, the expanded code reads it again with `option_env!` (see [`env_tracking_tokens`]).
+pub static CEL_VARIABLES: LazyLock<Result<Vec<(String, CelValue)>, String>> = LazyLock::new(|| {
+  let declarations = match std::env::var(CEL_VARIABLES_ENV) {
+    Ok(declarations) => declarations,
+    Err(_) => return Ok(Vec::new()),
+  };
+
+  declarations
+    .split(';')
+    .filter(|declaration| !declaration.is_empty())
+    .map(|declaration| {
+      let (name, type_name) = declaration.split_once(':').ok_or_else(|| {
+        format!(
+          "Invalid Cel variable declaration `{}` in {}",
+          declaration, CEL_VARIABLES_ENV
+        )
+      })?;
+
+      let default_value = default_value_for_type(type_name).ok_or_else(|| {
+        format!(
+          "Unknown type `{}` for the Cel variable `{}`",
+          type_name, name
+        )
+      })?;
+
+      Ok((name.to_string(), default_value))
+    })
+    .collect()
+});
+
+const CEL_VARIABLES_ENV: &str = "PROTOCHECK_CEL_VARIABLES";
+const CEL_FUNCTIONS_ENV: &str = "PROTOCHECK_CEL_FUNCTIONS";
+
+/// Reads the declaration variables with `option_env!` in the expanded code, so that rustc records them as dependencies of the crate and cargo rebuilds it when they change.
+pub fn env_tracking_tokens() -> TokenStream {
+  quote! {
+    const _: Option<&str> = ::core::option_env!(#CEL_VARIABLES_ENV);
+    const _: Option<&str> = ::core::option_env!(#CEL_FUNCTIONS_ENV);
+  }
+}
+
+/// A custom function declared with `protocheck_build::declare_cel_functions`.
+pub struct CelFunctionDeclaration {
+  pub name: String,
+  /// A default value for the type of each argument.
+  pub args: Vec<CelValue>,
+  pub return_value: CelValue,
+}
+
+/// The custom functions declared with `protocheck_build::declare_cel_functions`.
+/// Since the actual rust functions are not available at compile time, these are replaced with stubs that check the number and the types of the arguments and return a default value for the declared return type.
+pub static CEL_FUNCTIONS: LazyLock<Result<Vec<CelFunctionDeclaration>, String>> =
+  LazyLock::new(|| {
+    let declarations = match std::env::var(CEL_FUNCTIONS_ENV) {
+      Ok(declarations) => declarations,
+      Err(_) => return Ok(Vec::new()),
+    };
+
+    declarations
+      .split(';')
+      .filter(|declaration| !declaration.is_empty())
+      .map(|declaration| {
+        let invalid_declaration = || {
+          format!(
+            "Invalid Cel function declaration `{}` in {}",
+            declaration, CEL_FUNCTIONS_ENV
+          )
+        };
+
+        let (signature, return_type) = declaration
+          .rsplit_once(':')
+          .ok_or_else(invalid_declaration)?;
+        let (name, args) = signature
+          .strip_suffix(')')
+          .and_then(|signature| signature.split_once('('))
+          .ok_or_else(invalid_declaration)?;
+
+        let args = args
+          .split(',')
+          .filter(|arg| !arg.is_empty())
+          .map(|arg_type| {
+            default_value_for_type(arg_type).ok_or_else(|| {
+              format!(
+                "Unknown type `{}` for an argument of the Cel function `{}`",
+                arg_type, name
+              )
+            })
+          })
+          .collect::<Result<Vec<CelValue>, String>>()?;
+
+        let return_value = default_value_for_type(return_type).ok_or_else(|| {
+          format!(
+            "Unknown return type `{}` for the Cel function `{}`",
+            return_type, name
+          )
+        })?;
+
+        Ok(CelFunctionDeclaration {
+          name: name.to_string(),
+          args,
+          return_value,
+        })
+      })
+      .collect()
+  });
+
+pub fn default_value_for_type(type_name: &str) -> Option<CelValue> {
+  let value = match type_name {
+    "int" => CelValue::Int(0),
+    "uint" => CelValue::UInt(0),
+    "double" => CelValue::Float(0.0),
+    "bool" => CelValue::Bool(false),
+    "string" => CelValue::String(Arc::new(String::new())),
+    "bytes" => CelValue::Bytes(Arc::new(Vec::new())),
+    "timestamp" => CelValue::Timestamp(Timestamp::default().try_into().unwrap()),
+    "duration" => CelValue::Duration(Duration::default().try_into().unwrap()),
+    "list" => CelValue::List(Arc::new(Vec::new())),
+    "map" => CelValue::Map(HashMap::<CelKey, CelValue>::new().into()),
+    _ => return None,
+  };
+
+  Some(value)
+}
+
+/// Adds the declared variables and functions to the context used for the compile-time check of a Cel program.
+pub fn add_cel_declarations(context: &mut Context) -> Result<(), String> {
+  for (name, value) in CEL_VARIABLES.as_ref().map_err(Clone::clone)? {
+    context.add_variable_from_value(name.as_str(), value.clone());
+  }
+
+  for function in CEL_FUNCTIONS.as_ref().map_err(Clone::clone)? {
+    let name = function.name.clone();
+    let declared_args = function.args.clone();
+    let return_value = function.return_value.clone();
+
+    context.add_function(
+      function.name.as_str(),
+      move |Arguments(args): Arguments| -> Result<CelValue, ExecutionError> {
+        if args.len() != declared_args.len() {
+          return Err(ExecutionError::function_error(
+            &name,
+            format!(
+              "expected {} arguments, got {}",
+              declared_args.len(),
+              args.len()
+            ),
+          ));
+        }
+
+        for (index, (arg, declared_arg)) in args.iter().zip(&declared_args).enumerate() {
+          // Null values are accepted, since unset fields can be null
+          if !matches!(arg, CelValue::Null) && discriminant(arg) != discriminant(declared_arg) {
+            return Err(ExecutionError::function_error(
+              &name,
+              format!(
+                "expected argument {} to be of type {:?}, got {:?}",
+                index + 1,
+                declared_arg.type_of(),
+                arg.type_of()
+              ),
+            ));
+          }
+        }
+
+        Ok(return_value.clone())
+      },
+    );
+  }
+
+  Ok(())
+}
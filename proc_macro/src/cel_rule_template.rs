@@ -7,6 +7,7 @@ use crate::validation_data::ValidationData;
 pub enum CelRuleTemplateTarget<'a> {
   Message {
     message_desc: &'a MessageDescriptor,
+    validation_context_ident: Ident,
     parent_messages_ident: Ident,
     violations_ident: Ident,
   },
@@ -33,19 +34,25 @@ impl CelRuleTemplateTarget<'_> {
     }
   }
 
-  pub fn get_idents(&self) -> (&Ident, &Ident) {
+  pub fn get_idents(&self) -> (&Ident, &Ident, &Ident) {
     match self {
       CelRuleTemplateTarget::Field {
         validation_data, ..
       } => (
+        validation_data.validation_context_ident,
         validation_data.parent_messages_ident,
         validation_data.violations_ident,
       ),
       CelRuleTemplateTarget::Message {
+        validation_context_ident,
         parent_messages_ident,
         violations_ident,
         ..
-      } => (parent_messages_ident, violations_ident),
+      } => (
+        validation_context_ident,
+        parent_messages_ident,
+        violations_ident,
+      ),
     }
   }
 }
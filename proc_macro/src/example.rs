@@ -0,0 +1,961 @@
+use std::{
+  collections::HashMap,
+  ops::{Add, Div, Sub},
+};
+
+use convert_case::{Case, Casing};
+use proc_macro2::Literal;
+use prost_reflect::{
+  prost::Message, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, Value as ProstValue,
+};
+use quote::{format_ident, quote};
+use regex::Regex;
+use syn::{DeriveInput, Error, Type};
+
+use crate::{
+  extract_validators::field_is_boxed,
+  field_enum::message_type_path,
+  pool_loader::{
+    FIELD_RULES_EXT_DESCRIPTOR, MESSAGE_RULES_EXT_DESCRIPTOR, ONEOF_RULES_EXT_DESCRIPTOR,
+  },
+  rules::protovalidate::{FieldRules, Ignore, MessageRules, OneofRules},
+  schema::{decode, extension_message},
+  special_field_names::proto_name_to_rust_ident,
+  TokenStream2,
+};
+
+pub const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Generates the body of the `valid_example` constructor, which builds an instance of the message that satisfies all of its standard rules.
+///
+/// The values are taken from the `const`, `in` and `example` rules when they are defined, and they are otherwise derived from the other rules. Each value is checked against the rules of its field, so that the constraints that cannot be satisfied automatically (such as Cel rules or complex patterns) are reported as compile errors.
+pub fn get_example_tokens(
+  input_tokens: &DeriveInput,
+  message_desc: &MessageDescriptor,
+) -> Result<TokenStream2, Error> {
+  let error = |message: String| {
+    Error::new_spanned(
+      &input_tokens.ident,
+      format!(
+        "Cannot generate a valid example for {}: {}",
+        message_desc.full_name(),
+        message
+      ),
+    )
+  };
+
+  let message_rules = extension_message(
+    message_desc
+      .options()
+      .get_extension(&MESSAGE_RULES_EXT_DESCRIPTOR),
+  )
+  .map(|rules| decode::<MessageRules>(&rules, "message"))
+  .transpose()?;
+
+  if let Some(message_rules) = message_rules
+    && !message_rules.cel.is_empty()
+  {
+    return Err(error(
+      "the message has Cel rules, which cannot be satisfied automatically".to_string(),
+    ));
+  }
+
+  let mut rust_field_types: HashMap<String, &Type> = HashMap::new();
+
+  if let syn::Data::Struct(syn::DataStruct { fields, .. }) = &input_tokens.data {
+    for field in fields {
+      if let Some(ident) = &field.ident {
+        rust_field_types.insert(ident.to_string(), &field.ty);
+      }
+    }
+  }
+
+  let rust_type = |proto_name: &str| {
+    let ident = proto_name_to_rust_ident(proto_name);
+
+    rust_field_types
+      .get(ident.to_string().as_str())
+      .copied()
+      .ok_or_else(|| error(format!("could not find the rust type of `{}`", proto_name)))
+  };
+
+  let mut fields_tokens = Vec::new();
+
+  for field in message_desc.fields() {
+    if field
+      .containing_oneof()
+      .is_some_and(|oneof| !oneof.is_synthetic())
+    {
+      continue;
+    }
+
+    let field_type = rust_type(field.name())?;
+
+    if let Some(value) = field_example(&field, message_desc, field_type)
+      .map_err(|e| error(format!("field `{}` {}", field.name(), e)))?
+    {
+      let field_ident = proto_name_to_rust_ident(field.name());
+
+      fields_tokens.push(quote! { #field_ident: #value });
+    }
+  }
+
+  for oneof in message_desc.oneofs() {
+    if oneof.is_synthetic() {
+      continue;
+    }
+
+    let is_required = extension_message(
+      oneof
+        .options()
+        .get_extension(&ONEOF_RULES_EXT_DESCRIPTOR),
+    )
+    .map(|rules| decode::<OneofRules>(&rules, "oneof"))
+    .transpose()?
+    .is_some_and(|rules| rules.required());
+
+    if !is_required {
+      continue;
+    }
+
+    let oneof_type = rust_type(oneof.name())?;
+    let oneof_path = message_type_path(oneof_type).ok_or_else(|| {
+      error(format!(
+        "could not find the rust type of `{}`",
+        oneof.name()
+      ))
+    })?;
+
+    let mut errors = Vec::new();
+    let mut variant_tokens = None;
+
+    // The first variant that can be generated is used. The rust types of the variants are not known here, so the message variants are skipped
+    for field in oneof.fields() {
+      match single_example(&field, field_rules(&field).as_ref(), None) {
+        Ok(value) => {
+          let variant_ident = format_ident!("{}", field.name().to_case(Case::Pascal));
+
+          variant_tokens = Some(quote! { Some(#oneof_path::#variant_ident(#value)) });
+          break;
+        }
+        Err(e) => errors.push(format!("`{}` {}", field.name(), e)),
+      }
+    }
+
+    let Some(variant_tokens) = variant_tokens else {
+      return Err(error(format!(
+        "none of the fields of the required oneof `{}` can be generated ({})",
+        oneof.name(),
+        errors.join("; ")
+      )));
+    };
+
+    let oneof_ident = proto_name_to_rust_ident(oneof.name());
+
+    fields_tokens.push(quote! { #oneof_ident: #variant_tokens });
+  }
+
+  let default_tokens = (fields_tokens.len() < rust_field_types.len())
+    .then(|| quote! { ..::core::default::Default::default() });
+
+  Ok(quote! {
+    Self {
+      #(#fields_tokens,)*
+      #default_tokens
+    }
+  })
+}
+
+// Returns the value for a field outside of a oneof, or None if the default value is already valid
+fn field_example(
+  field: &FieldDescriptor,
+  message_desc: &MessageDescriptor,
+  rust_type: &Type,
+) -> Result<Option<TokenStream2>, String> {
+  let rules = field_rules(field);
+
+  let Some(rules) = rules else {
+    return Ok(None);
+  };
+
+  let decoded_rules = FieldRules::decode(rules.encode_to_vec().as_slice())
+    .map_err(|e| format!("has rules that could not be decoded: {}", e))?;
+
+  let is_required = decoded_rules.required() && field.supports_presence();
+
+  if matches!(decoded_rules.ignore(), Ignore::Always) {
+    return Ok(None);
+  }
+
+  if field.is_list() || field.is_map() {
+    if !decoded_rules.cel.is_empty() {
+      return Err("has Cel rules, which cannot be satisfied automatically".to_string());
+    }
+
+    let Some((rules_type, type_rules)) = type_rules(&rules) else {
+      return Ok(None);
+    };
+
+    if rules_type != "repeated" && rules_type != "map" {
+      return Ok(None);
+    }
+
+    let (min_name, items_names) = if field.is_map() {
+      ("min_pairs", ["keys", "values"].as_slice())
+    } else {
+      ("min_items", ["items"].as_slice())
+    };
+
+    let min_items = match set_rule(&type_rules, min_name) {
+      Some(ProstValue::U64(min_items)) => min_items,
+      _ => 0,
+    };
+
+    if min_items == 0 {
+      return Ok(None);
+    }
+
+    if field.is_map() {
+      if min_items > 1 {
+        return Err(format!(
+          "requires {} distinct keys, which cannot be generated automatically",
+          min_items
+        ));
+      }
+
+      let Kind::Message(entry_desc) = field.kind() else {
+        return Ok(None);
+      };
+
+      let key_rules = nested_field_rules(&type_rules, items_names[0]);
+      let value_rules = nested_field_rules(&type_rules, items_names[1]);
+
+      let key = single_example(
+        &entry_desc.map_entry_key_field(),
+        key_rules.as_ref(),
+        Some(rust_type),
+      )?;
+      let value = single_example(
+        &entry_desc.map_entry_value_field(),
+        value_rules.as_ref(),
+        Some(rust_type),
+      )?;
+
+      return Ok(Some(quote! {
+        {
+          let mut map = <#rust_type as ::core::default::Default>::default();
+          map.insert(#key, #value);
+          map
+        }
+      }));
+    }
+
+    if set_rule(&type_rules, "unique") == Some(ProstValue::Bool(true)) && min_items > 1 {
+      return Err(format!(
+        "requires {} unique items, which cannot be generated automatically",
+        min_items
+      ));
+    }
+
+    let items_rules = nested_field_rules(&type_rules, items_names[0]);
+    let item = single_example(field, items_rules.as_ref(), Some(rust_type))?;
+    let min_items = usize::try_from(min_items).map_err(|e| e.to_string())?;
+
+    return Ok(Some(quote! { ::std::vec![#item; #min_items] }));
+  }
+
+  // Fields with presence are only validated when they are set, and scalar fields that are ignored when empty can keep their zero value
+  if (field.supports_presence() || !matches!(decoded_rules.ignore(), Ignore::Unspecified))
+    && !is_required
+  {
+    return Ok(None);
+  }
+
+  let value = single_example(field, Some(&rules), Some(rust_type))?;
+
+  let value = if field_is_boxed(field, message_desc) {
+    quote! { ::std::boxed::Box::new(#value) }
+  } else {
+    value
+  };
+
+  if field.supports_presence() {
+    Ok(Some(quote! { Some(#value) }))
+  } else {
+    Ok(Some(value))
+  }
+}
+
+// Returns the value for a single field, list item, map key or map value
+fn single_example(
+  field: &FieldDescriptor,
+  rules: Option<&DynamicMessage>,
+  rust_type: Option<&Type>,
+) -> Result<TokenStream2, String> {
+  if let Some(rules) = rules {
+    let decoded_rules = FieldRules::decode(rules.encode_to_vec().as_slice())
+      .map_err(|e| format!("has rules that could not be decoded: {}", e))?;
+
+    let has_examples =
+      type_rules(rules).is_some_and(|(_, type_rules)| type_rules.has_field_by_name("example"));
+
+    if !decoded_rules.cel.is_empty() && !has_examples {
+      return Err(
+        "has Cel rules, which cannot be satisfied automatically. An `example` value can be added to its rules to be used instead".to_string(),
+      );
+    }
+  }
+
+  let type_rules = rules
+    .and_then(type_rules)
+    .map(|(_, type_rules)| type_rules);
+  let type_rules = type_rules.as_ref();
+
+  let kind = field.kind();
+
+  match &kind {
+    Kind::Message(message_desc) => match message_desc.full_name() {
+      "google.protobuf.Duration" | "google.protobuf.Timestamp" => {
+        let is_timestamp = message_desc.full_name() == "google.protobuf.Timestamp";
+
+        if let Some(type_rules) = type_rules
+          && (type_rules.has_field_by_name("gt_now") || type_rules.has_field_by_name("within"))
+        {
+          return Err(
+            "has rules relative to the current time, which cannot be satisfied by a static value"
+              .to_string(),
+          );
+        }
+
+        let mut rules = NumericRules::new(type_rules, duration_nanos);
+
+        // The epoch is always in the past
+        if type_rules.is_some_and(|type_rules| type_rules.has_field_by_name("lt_now")) {
+          rules.lte = Some(rules.lte.map_or(0, |lte| lte.min(0)));
+        }
+
+        let value = pick(rules.candidates(0, 1), |value| rules.satisfied_by(value))?;
+
+        let (seconds, nanos) = if is_timestamp {
+          (
+            value.div_euclid(NANOS_PER_SECOND),
+            value.rem_euclid(NANOS_PER_SECOND),
+          )
+        } else {
+          (value / NANOS_PER_SECOND, value % NANOS_PER_SECOND)
+        };
+
+        let seconds = i64::try_from(seconds).map_err(|e| e.to_string())?;
+        let nanos = i32::try_from(nanos).map_err(|e| e.to_string())?;
+
+        if is_timestamp {
+          Ok(quote! { ::protocheck::types::Timestamp { seconds: #seconds, nanos: #nanos } })
+        } else {
+          Ok(quote! { ::protocheck::types::Duration { seconds: #seconds, nanos: #nanos } })
+        }
+      }
+      "google.protobuf.Any" if type_rules.is_some() => {
+        Err("has `any` rules, which cannot be satisfied automatically".to_string())
+      }
+      full_name if full_name.starts_with("google.protobuf.") => {
+        Ok(quote! { ::core::default::Default::default() })
+      }
+      _ => {
+        let message_path = rust_type
+          .and_then(message_type_path)
+          .ok_or_else(|| "is a message whose rust type could not be found".to_string())?;
+
+        Ok(quote! { #message_path::valid_example() })
+      }
+    },
+    Kind::Bool => {
+      let value = match type_rules.and_then(|rules| set_rule(rules, "const")) {
+        Some(ProstValue::Bool(value)) => value,
+        _ => false,
+      };
+
+      Ok(quote! { #value })
+    }
+    Kind::String => {
+      let value = string_example(type_rules)?;
+
+      Ok(quote! { ::std::string::String::from(#value) })
+    }
+    Kind::Bytes => {
+      let value = bytes_example(type_rules)?;
+      let value = Literal::byte_string(&value);
+
+      Ok(quote! { ::core::convert::From::from(#value.to_vec()) })
+    }
+    Kind::Enum(enum_desc) => {
+      let rules = NumericRules::new(type_rules, int_value);
+      let defined_only = type_rules
+        .and_then(|rules| set_rule(rules, "defined_only"))
+        .is_some_and(|defined_only| defined_only == ProstValue::Bool(true));
+      let defined_values: Vec<i128> = enum_desc
+        .values()
+        .map(|value| i128::from(value.number()))
+        .collect();
+
+      let mut candidates = rules.candidates(0, 1);
+      candidates.extend(defined_values.iter().copied());
+
+      let value = pick(candidates, |value| {
+        rules.satisfied_by(value) && (!defined_only || defined_values.contains(&value))
+      })?;
+
+      let value = Literal::i128_unsuffixed(value);
+
+      Ok(quote! { #value })
+    }
+    Kind::Float | Kind::Double => {
+      let rules = NumericRules::new(type_rules, float_value);
+      let (min, max) = if matches!(kind, Kind::Float) {
+        (f64::from(f32::MIN), f64::from(f32::MAX))
+      } else {
+        (f64::MIN, f64::MAX)
+      };
+
+      let is_float = matches!(kind, Kind::Float);
+      let mut candidates = rules.candidates(0.0, 1.0);
+
+      for bound in [rules.gt, rules.gte, rules.lt, rules.lte]
+        .into_iter()
+        .flatten()
+      {
+        candidates.extend(adjacent_floats(bound, is_float));
+      }
+
+      // The values of float fields are checked after being rounded to f32, since that is what the example will contain
+      if is_float {
+        for candidate in &mut candidates {
+          *candidate = f64::from(*candidate as f32);
+        }
+      }
+
+      let value = pick(candidates, |value| {
+        value.is_finite() && (min..=max).contains(&value) && rules.satisfied_by(value)
+      })?;
+
+      // The values of float fields are written as f32, so that they are not longer than an f32 can hold
+      let value = if is_float {
+        Literal::f32_unsuffixed(value as f32)
+      } else {
+        Literal::f64_unsuffixed(value)
+      };
+
+      Ok(quote! { #value })
+    }
+    _ => {
+      let (min, max) = match &kind {
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => (i128::from(i32::MIN), i128::from(i32::MAX)),
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => (i128::from(i64::MIN), i128::from(i64::MAX)),
+        Kind::Uint32 | Kind::Fixed32 => (0, i128::from(u32::MAX)),
+        _ => (0, i128::from(u64::MAX)),
+      };
+
+      let rules = NumericRules::new(type_rules, int_value);
+
+      let value = pick(rules.candidates(0, 1), |value| {
+        (min..=max).contains(&value) && rules.satisfied_by(value)
+      })?;
+
+      let value = Literal::i128_unsuffixed(value);
+
+      Ok(quote! { #value })
+    }
+  }
+}
+
+// The closest values on each side of a float bound, which are in range when the others are too far from it
+fn adjacent_floats(bound: f64, is_float: bool) -> [f64; 2] {
+  if is_float {
+    let bound = bound as f32;
+
+    [
+      f64::from(next_f32(bound, true)),
+      f64::from(next_f32(bound, false)),
+    ]
+  } else {
+    [next_f64(bound, true), next_f64(bound, false)]
+  }
+}
+
+fn next_f64(value: f64, up: bool) -> f64 {
+  if !value.is_finite() {
+    return value;
+  }
+
+  if value == 0.0 {
+    let smallest = f64::from_bits(1);
+    return if up { smallest } else { -smallest };
+  }
+
+  // The bits grow as the value moves away from zero, for both positive and negative values
+  if (value > 0.0) == up {
+    f64::from_bits(value.to_bits() + 1)
+  } else {
+    f64::from_bits(value.to_bits() - 1)
+  }
+}
+
+fn next_f32(value: f32, up: bool) -> f32 {
+  if !value.is_finite() {
+    return value;
+  }
+
+  if value == 0.0 {
+    let smallest = f32::from_bits(1);
+    return if up { smallest } else { -smallest };
+  }
+
+  if (value > 0.0) == up {
+    f32::from_bits(value.to_bits() + 1)
+  } else {
+    f32::from_bits(value.to_bits() - 1)
+  }
+}
+
+fn pick<T: Copy>(candidates: Vec<T>, is_valid: impl Fn(T) -> bool) -> Result<T, String> {
+  candidates
+    .into_iter()
+    .find(|candidate| is_valid(*candidate))
+    .ok_or_else(|| {
+      "has rules that cannot be satisfied automatically. An `example` value can be added to its rules to be used instead".to_string()
+    })
+}
+
+// The rules shared by numeric fields, enums, durations and timestamps
+pub struct NumericRules<T> {
+  pub const_value: Option<T>,
+  pub in_list: Vec<T>,
+  pub not_in_list: Vec<T>,
+  pub examples: Vec<T>,
+  pub gt: Option<T>,
+  pub gte: Option<T>,
+  pub lt: Option<T>,
+  pub lte: Option<T>,
+}
+
+impl<T> NumericRules<T>
+where
+  T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Div<Output = T>,
+{
+  pub fn new(rules: Option<&DynamicMessage>, convert: fn(&ProstValue) -> Option<T>) -> Self {
+    let single = |name: &str| {
+      rules
+        .and_then(|rules| set_rule(rules, name))
+        .and_then(|value| convert(&value))
+    };
+
+    let list = |name: &str| match rules.and_then(|rules| set_rule(rules, name)) {
+      Some(ProstValue::List(values)) => values.iter().filter_map(convert).collect(),
+      _ => Vec::new(),
+    };
+
+    Self {
+      const_value: single("const"),
+      in_list: list("in"),
+      not_in_list: list("not_in"),
+      examples: list("example"),
+      gt: single("gt"),
+      gte: single("gte"),
+      lt: single("lt"),
+      lte: single("lte"),
+    }
+  }
+
+  // The values to try, starting from the explicit ones and followed by the ones derived from the bounds
+  pub fn candidates(&self, zero: T, one: T) -> Vec<T> {
+    if let Some(const_value) = self.const_value {
+      return vec![const_value];
+    }
+
+    if !self.in_list.is_empty() {
+      return self.in_list.clone();
+    }
+
+    let mut candidates = self.examples.clone();
+
+    let bases = [
+      self.gt.map(|gt| gt + one),
+      self.gte,
+      self.lt.map(|lt| lt - one),
+      self.lte,
+      Some(zero),
+    ];
+
+    let mut offset = zero;
+
+    // Moving away from the bounds as many times as there are excluded values is enough to find one that is allowed
+    for _ in 0..=self.not_in_list.len() {
+      for base in bases.iter().flatten() {
+        candidates.push(*base + offset);
+        candidates.push(*base - offset);
+      }
+
+      offset = offset + one;
+    }
+
+    // Ranges narrower than the steps above (such as `gt: 0, lt: 1` for floats) can still contain their midpoint
+    if let (Some(lower), Some(upper)) = (self.gt.or(self.gte), self.lt.or(self.lte)) {
+      candidates.push(lower + (upper - lower) / (one + one));
+    }
+
+    candidates
+  }
+
+  pub fn satisfied_by(&self, value: T) -> bool {
+    if self
+      .const_value
+      .is_some_and(|const_value| value != const_value)
+    {
+      return false;
+    }
+
+    if !self.in_list.is_empty() && !self.in_list.contains(&value) {
+      return false;
+    }
+
+    if self.not_in_list.contains(&value) {
+      return false;
+    }
+
+    let lower = self
+      .gt
+      .map(|gt| (gt, value > gt, false))
+      .or_else(|| self.gte.map(|gte| (gte, value >= gte, true)));
+    let upper = self
+      .lt
+      .map(|lt| (lt, value < lt, false))
+      .or_else(|| self.lte.map(|lte| (lte, value <= lte, true)));
+
+    match (lower, upper) {
+      (Some((lower, lower_ok, lower_inclusive)), Some((upper, upper_ok, upper_inclusive))) => {
+        // When the lower bound is above the upper bound, the range is exclusive
+        if upper > lower || (upper == lower && lower_inclusive && upper_inclusive) {
+          lower_ok && upper_ok
+        } else {
+          lower_ok || upper_ok
+        }
+      }
+      (Some((_, lower_ok, _)), None) => lower_ok,
+      (None, Some((_, upper_ok, _))) => upper_ok,
+      (None, None) => true,
+    }
+  }
+}
+
+pub fn int_value(value: &ProstValue) -> Option<i128> {
+  match value {
+    ProstValue::I32(v) | ProstValue::EnumNumber(v) => Some(i128::from(*v)),
+    ProstValue::I64(v) => Some(i128::from(*v)),
+    ProstValue::U32(v) => Some(i128::from(*v)),
+    ProstValue::U64(v) => Some(i128::from(*v)),
+    _ => None,
+  }
+}
+
+pub fn float_value(value: &ProstValue) -> Option<f64> {
+  match value {
+    ProstValue::F32(v) => Some(f64::from(*v)),
+    ProstValue::F64(v) => Some(*v),
+    _ => None,
+  }
+}
+
+// Durations and timestamps are converted to nanoseconds, so that they can be compared like integers
+pub fn duration_nanos(value: &ProstValue) -> Option<i128> {
+  let ProstValue::Message(msg) = value else {
+    return None;
+  };
+
+  let seconds = match msg.get_field_by_name("seconds").as_deref() {
+    Some(ProstValue::I64(seconds)) => i128::from(*seconds),
+    _ => 0,
+  };
+  let nanos = match msg.get_field_by_name("nanos").as_deref() {
+    Some(ProstValue::I32(nanos)) => i128::from(*nanos),
+    _ => 0,
+  };
+
+  Some(seconds * NANOS_PER_SECOND + nanos)
+}
+
+// The values used for the well known string formats
+pub const WELL_KNOWN_STRINGS: &[(&str, &str)] = &[
+  ("email", "user@example.com"),
+  ("hostname", "example.com"),
+  ("address", "example.com"),
+  ("ip", "127.0.0.1"),
+  ("ipv4", "127.0.0.1"),
+  ("ipv6", "::1"),
+  ("uri", "https://example.com"),
+  ("uri_ref", "/example"),
+  ("uuid", "00000000-0000-4000-8000-000000000000"),
+  ("tuuid", "00000000000040008000000000000000"),
+  ("ip_with_prefixlen", "127.0.0.1/8"),
+  ("ipv4_with_prefixlen", "127.0.0.1/8"),
+  ("ipv6_with_prefixlen", "::1/128"),
+  ("ip_prefix", "127.0.0.0/8"),
+  ("ipv4_prefix", "127.0.0.0/8"),
+  ("ipv6_prefix", "::/128"),
+  ("host_and_port", "example.com:8080"),
+];
+
+fn string_example(rules: Option<&DynamicMessage>) -> Result<String, String> {
+  let Some(rules) = rules else {
+    return Ok(String::new());
+  };
+
+  let string_rule = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::String(value)) => Some(value),
+    _ => None,
+  };
+  let string_list = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::List(values)) => values
+      .iter()
+      .filter_map(|value| value.as_str().map(str::to_string))
+      .collect(),
+    _ => Vec::new(),
+  };
+  let length_rule = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::U64(value)) => usize::try_from(value).ok(),
+    _ => None,
+  };
+
+  let const_value = string_rule("const");
+  let prefix = string_rule("prefix").unwrap_or_default();
+  let suffix = string_rule("suffix").unwrap_or_default();
+  let contains = string_rule("contains").unwrap_or_default();
+  let not_contains = string_rule("not_contains");
+  let in_list = string_list("in");
+  let not_in_list = string_list("not_in");
+  let pattern = match string_rule("pattern") {
+    Some(pattern) => {
+      Some(Regex::new(&pattern).map_err(|e| format!("has an invalid pattern: {}", e))?)
+    }
+    None => None,
+  };
+
+  let exact_len = length_rule("len");
+  let min_len = exact_len
+    .or_else(|| length_rule("min_len"))
+    .unwrap_or(0);
+  let max_len = exact_len.or_else(|| length_rule("max_len"));
+  let exact_bytes = length_rule("len_bytes");
+  let min_bytes = exact_bytes
+    .or_else(|| length_rule("min_bytes"))
+    .unwrap_or(0);
+  let max_bytes = exact_bytes.or_else(|| length_rule("max_bytes"));
+
+  let well_known = WELL_KNOWN_STRINGS
+    .iter()
+    .find(|(name, _)| set_rule(rules, name) == Some(ProstValue::Bool(true)))
+    .map(|(_, value)| *value)
+    .or_else(|| match set_rule(rules, "well_known_regex") {
+      Some(ProstValue::EnumNumber(1)) => Some("x-example"),
+      Some(ProstValue::EnumNumber(2)) => Some("example"),
+      _ => None,
+    });
+
+  let is_valid = |value: &str| {
+    let chars = value.chars().count();
+
+    const_value
+      .as_ref()
+      .is_none_or(|const_value| value == const_value)
+      && (in_list.is_empty() || in_list.iter().any(|item| item == value))
+      && !not_in_list.iter().any(|item| item == value)
+      && chars >= min_len
+      && max_len.is_none_or(|max_len| chars <= max_len)
+      && value.len() >= min_bytes
+      && max_bytes.is_none_or(|max_bytes| value.len() <= max_bytes)
+      && value.starts_with(&prefix)
+      && value.ends_with(&suffix)
+      && value.contains(&contains)
+      && not_contains
+        .as_ref()
+        .is_none_or(|not_contains| !value.contains(not_contains))
+      && pattern
+        .as_ref()
+        .is_none_or(|pattern| pattern.is_match(value))
+  };
+
+  let mut candidates: Vec<String> = Vec::new();
+
+  candidates.extend(const_value.iter().cloned());
+
+  candidates.extend(in_list.iter().cloned());
+  candidates.extend(string_list("example"));
+
+  if let Some(well_known) = well_known {
+    candidates.push(well_known.to_string());
+  } else {
+    let start = format!("{}{}", prefix, contains);
+    let target_len = min_len.max(min_bytes);
+
+    for pad_char in ['a', 'b', 'c', 'x'] {
+      for extra in 0..=1 {
+        let pad_len = target_len.saturating_sub(start.len() + suffix.len()) + extra;
+        let padding: String = std::iter::repeat_n(pad_char, pad_len).collect();
+
+        candidates.push(format!("{}{}{}", start, padding, suffix));
+      }
+    }
+  }
+
+  candidates
+    .into_iter()
+    .find(|candidate| is_valid(candidate))
+    .ok_or_else(|| {
+      if pattern.is_some() {
+        "has a pattern that cannot be satisfied automatically. An `example` value can be added to its rules to be used instead".to_string()
+      } else {
+        "has rules that cannot be satisfied automatically. An `example` value can be added to its rules to be used instead".to_string()
+      }
+    })
+}
+
+fn bytes_example(rules: Option<&DynamicMessage>) -> Result<Vec<u8>, String> {
+  let Some(rules) = rules else {
+    return Ok(Vec::new());
+  };
+
+  let bytes_rule = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::Bytes(value)) => Some(value.to_vec()),
+    _ => None,
+  };
+  let bytes_list = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::List(values)) => values
+      .iter()
+      .filter_map(|value| value.as_bytes().map(|bytes| bytes.to_vec()))
+      .collect(),
+    _ => Vec::new(),
+  };
+  let length_rule = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::U64(value)) => usize::try_from(value).ok(),
+    _ => None,
+  };
+
+  let const_value = bytes_rule("const");
+  let prefix = bytes_rule("prefix").unwrap_or_default();
+  let suffix = bytes_rule("suffix").unwrap_or_default();
+  let contains = bytes_rule("contains").unwrap_or_default();
+  let in_list = bytes_list("in");
+  let not_in_list = bytes_list("not_in");
+  let pattern = match set_rule(rules, "pattern") {
+    Some(ProstValue::String(pattern)) => {
+      Some(Regex::new(&pattern).map_err(|e| format!("has an invalid pattern: {}", e))?)
+    }
+    _ => None,
+  };
+
+  let exact_len = length_rule("len");
+  let min_len = exact_len
+    .or_else(|| length_rule("min_len"))
+    .unwrap_or(0);
+  let max_len = exact_len.or_else(|| length_rule("max_len"));
+
+  let well_known: Option<Vec<u8>> = if set_rule(rules, "ipv6") == Some(ProstValue::Bool(true)) {
+    let mut ipv6 = vec![0; 16];
+    ipv6[15] = 1;
+    Some(ipv6)
+  } else if set_rule(rules, "ip") == Some(ProstValue::Bool(true))
+    || set_rule(rules, "ipv4") == Some(ProstValue::Bool(true))
+  {
+    Some(vec![127, 0, 0, 1])
+  } else {
+    None
+  };
+
+  let is_valid = |value: &[u8]| {
+    const_value
+      .as_ref()
+      .is_none_or(|const_value| value == const_value.as_slice())
+      && (in_list.is_empty() || in_list.iter().any(|item| item == value))
+      && !not_in_list.iter().any(|item| item == value)
+      && value.len() >= min_len
+      && max_len.is_none_or(|max_len| value.len() <= max_len)
+      && value.starts_with(&prefix)
+      && value.ends_with(&suffix)
+      && (contains.is_empty()
+        || value
+          .windows(contains.len())
+          .any(|window| window == contains))
+      && pattern
+        .as_ref()
+        .is_none_or(|pattern| std::str::from_utf8(value).is_ok_and(|value| pattern.is_match(value)))
+  };
+
+  let mut candidates: Vec<Vec<u8>> = Vec::new();
+
+  candidates.extend(const_value.iter().cloned());
+
+  candidates.extend(in_list.iter().cloned());
+  candidates.extend(bytes_list("example"));
+
+  if let Some(well_known) = well_known {
+    candidates.push(well_known);
+  } else {
+    for pad_byte in [b'a', b'b', b'c', b'x'] {
+      for extra in 0..=1 {
+        let pad_len = min_len.saturating_sub(prefix.len() + contains.len() + suffix.len()) + extra;
+
+        let mut candidate = prefix.clone();
+        candidate.extend_from_slice(&contains);
+        candidate.extend(std::iter::repeat_n(pad_byte, pad_len));
+        candidate.extend_from_slice(&suffix);
+
+        candidates.push(candidate);
+      }
+    }
+  }
+
+  candidates
+    .into_iter()
+    .find(|candidate| is_valid(candidate))
+    .ok_or_else(|| {
+      "has rules that cannot be satisfied automatically. An `example` value can be added to its rules to be used instead".to_string()
+    })
+}
+
+pub fn field_rules(field: &FieldDescriptor) -> Option<DynamicMessage> {
+  extension_message(
+    field
+      .options()
+      .get_extension(&FIELD_RULES_EXT_DESCRIPTOR),
+  )
+}
+
+// Returns the rules for the items of a list, or for the keys or values of a map
+pub fn nested_field_rules(type_rules: &DynamicMessage, name: &str) -> Option<DynamicMessage> {
+  match set_rule(type_rules, name) {
+    Some(ProstValue::Message(rules)) => Some(rules),
+    _ => None,
+  }
+}
+
+// Returns the name of the rules type (i.e. `string`) and the rules set for it
+pub fn type_rules(rules: &DynamicMessage) -> Option<(String, DynamicMessage)> {
+  rules.fields().find_map(|(rules_field, value)| {
+    let is_type_rule = rules_field
+      .containing_oneof()
+      .is_some_and(|oneof| oneof.name() == "type");
+
+    match value {
+      ProstValue::Message(type_rules) if is_type_rule => {
+        Some((rules_field.name().to_string(), type_rules.clone()))
+      }
+      _ => None,
+    }
+  })
+}
+
+pub fn set_rule(rules: &DynamicMessage, name: &str) -> Option<ProstValue> {
+  rules
+    .has_field_by_name(name)
+    .then(|| rules.get_field_by_name(name))
+    .flatten()
+    .map(|value| value.into_owned())
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use convert_case::{Case, Casing};
+use prost_reflect::MessageDescriptor;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Error, GenericArgument, Ident, Path, PathArguments, Type};
+
+use crate::{
+  extract_validators::field_needs_validation, special_field_names::proto_name_to_rust_ident,
+  TokenStream2,
+};
+
+/// Generates the enum with the fields of a message (i.e. `JediFightField`), which can be used to build typed paths for looking up violations.
+pub fn get_field_enum_tokens(
+  input_tokens: &DeriveInput,
+  message_desc: &MessageDescriptor,
+) -> Result<TokenStream2, Error> {
+  let struct_ident = &input_tokens.ident;
+  let enum_ident = field_enum_ident(struct_ident);
+
+  // The enum is generated next to the message, so it would clash with a message or enum with the same name in the same scope
+  let sibling_name = format!(
+    "{}{}",
+    message_desc
+      .full_name()
+      .strip_suffix(message_desc.name())
+      .unwrap_or_default(),
+    enum_ident
+  );
+  let pool = message_desc.parent_pool();
+
+  if pool.get_message_by_name(&sibling_name).is_some()
+    || pool.get_enum_by_name(&sibling_name).is_some()
+  {
+    return Err(Error::new_spanned(
+      struct_ident,
+      format!(
+        "Cannot generate the {} enum for {}, because {} is already defined",
+        enum_ident,
+        message_desc.full_name(),
+        sibling_name
+      ),
+    ));
+  }
+  let selector_path = quote! { ::protocheck::field_data::FieldSelector };
+
+  let mut rust_field_types: HashMap<String, &Type> = HashMap::new();
+
+  if let syn::Data::Struct(syn::DataStruct { fields, .. }) = &input_tokens.data {
+    for field in fields {
+      if let Some(ident) = &field.ident {
+        rust_field_types.insert(ident.to_string(), &field.ty);
+      }
+    }
+  }
+
+  let mut variants = Vec::new();
+  let mut proto_names = Vec::new();
+  let mut tags = Vec::new();
+  let mut nested_builders = TokenStream2::new();
+
+  for field in message_desc.fields() {
+    let proto_name = field.name();
+    let variant_ident = field_variant_ident(proto_name);
+
+    variants.push(variant_ident);
+    proto_names.push(proto_name.to_string());
+    tags.push(field.number());
+
+    // Nested messages with rules are always processed by the macro, so their field enum is guaranteed to exist
+    if field
+      .containing_oneof()
+      .is_none_or(|oneof| oneof.is_synthetic())
+      && field_needs_validation(&field.kind())
+    {
+      let builder_ident = proto_name_to_rust_ident(proto_name);
+
+      let nested_message_path = rust_field_types
+        .get(builder_ident.to_string().as_str())
+        .and_then(|ty| message_type_path(ty));
+
+      if let Some(nested_message_path) = nested_message_path {
+        let doc = format!("Returns a path to a field of the `{}` message.", proto_name);
+
+        nested_builders.extend(quote! {
+          #[doc = #doc]
+          pub fn #builder_ident(
+            path: impl ::protocheck::field_data::IntoFieldSelector<Message = #nested_message_path>
+          ) -> #selector_path<#struct_ident> {
+            #selector_path::nested(#proto_name, path.into_field_selector())
+          }
+        });
+      }
+    }
+  }
+
+  let enum_doc = format!(
+    "The fields of the [`{}`] message, which can be used to look up violations with typed paths.",
+    struct_ident
+  );
+
+  Ok(quote! {
+    #[doc = #enum_doc]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum #enum_ident {
+      #(#variants),*
+    }
+
+    impl #enum_ident {
+      /// Returns the name of the field in the protobuf schema.
+      pub const fn proto_name(&self) -> &'static str {
+        match *self {
+          #(Self::#variants => #proto_names),*
+        }
+      }
+
+      /// Returns the tag of the field in the protobuf schema.
+      pub const fn tag(&self) -> u32 {
+        match *self {
+          #(Self::#variants => #tags),*
+        }
+      }
+
+      /// Returns the path to this field.
+      pub fn path(self) -> #selector_path<#struct_ident> {
+        #selector_path::new(self.proto_name())
+      }
+
+      #nested_builders
+    }
+
+    impl ::protocheck::field_data::IntoFieldSelector for #enum_ident {
+      type Message = #struct_ident;
+
+      fn into_field_selector(self) -> #selector_path<#struct_ident> {
+        self.path()
+      }
+    }
+  })
+}
+
+/// The ident of the enum with the fields of a message.
+pub fn field_enum_ident(struct_ident: &Ident) -> Ident {
+  format_ident!("{}Field", struct_ident)
+}
+
+/// The ident of the variant for a field in the enum with the fields of a message.
+pub fn field_variant_ident(proto_name: &str) -> Ident {
+  let variant_name = proto_name.to_case(Case::Pascal);
+
+  // Self cannot be used as an identifier, so it gets the same suffix that prost uses for the fields named `self`
+  if variant_name == "Self" {
+    format_ident!("Self_")
+  } else {
+    format_ident!("{}", variant_name)
+  }
+}
+
+// Extracts the path of the message struct from the type of a field, removing the wrappers such as Option, Box, Vec or the maps
+pub fn message_type_path(ty: &Type) -> Option<Path> {
+  let Type::Path(type_path) = ty else {
+    return None;
+  };
+
+  let last_segment = type_path.path.segments.last()?;
+
+  let generic_arg_index = match last_segment.ident.to_string().as_str() {
+    "Option" | "Box" | "Vec" => 0,
+    "HashMap" | "BTreeMap" => 1,
+    _ => return Some(type_path.path.clone()),
+  };
+
+  let PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+    return None;
+  };
+
+  let inner_types: Vec<&Type> = args
+    .args
+    .iter()
+    .filter_map(|arg| match arg {
+      GenericArgument::Type(inner_type) => Some(inner_type),
+      _ => None,
+    })
+    .collect();
+
+  message_type_path(inner_types.get(generic_arg_index)?)
+}
@@ -11,17 +11,29 @@ use quote::quote;
 use syn::{parse_macro_input, DeriveInput, Error, Ident, LitStr};
 
 use crate::{
-  extract_validators::{extract_oneof_validators, OneofValidatorsOutput},
-  rules::extract_validators::{self, extract_message_validators},
+  attribute_extractors::ValidateAttrData,
+  example::get_example_tokens,
+  field_enum::get_field_enum_tokens,
+  extract_validators::{extract_oneof_validators, message_has_rules, OneofValidatorsOutput},
+  rules::extract_validators::{self, extract_message_validators, MessageValidatorsOutput},
+  schema::get_schema_tokens,
+  strategies::get_strategy_tokens,
 };
 
 mod attribute_extractors;
+#[cfg(feature = "cel")]
+mod cel_declarations;
 mod cel_rule_template;
 #[cfg(feature = "cel")]
 mod cel_try_into;
+mod example;
+mod field_enum;
+mod native_cel;
 mod pool_loader;
 mod rules;
+mod schema;
 mod special_field_names;
+mod strategies;
 mod validation_data;
 
 /// Adds conversion functions into [`cel::Value`] for oneofs.
@@ -39,9 +51,17 @@ pub fn try_into_cel_value_derive(input: TokenStream) -> TokenStream {
 }
 
 /// Adds the validation methods to the generated protobuf message structs.
+///
+/// Passing `table` after the message name (i.e. `#[protobuf_validate("myapp.v1.User", table)]`) enables the table-driven mode, where the rules for scalar fields are stored in a static table that is walked by a shared interpreter, rather than being expanded inline. The fields that cannot be described by the table fall back to inline validators.
+///
+/// Passing `example` generates a `valid_example()` constructor, which returns an instance of the message that satisfies all of its standard rules. The values come from the `const`, `in` and `example` rules, or they are derived from the other rules. If a rule cannot be satisfied automatically (such as a Cel rule or a complex pattern without an `example`), this results in a compile error.
 #[proc_macro_attribute]
 pub fn protobuf_validate(attrs: TokenStream, input: TokenStream) -> TokenStream {
-  let proto_message_name_tokens = parse_macro_input!(attrs as LitStr);
+  let ValidateAttrData {
+    message_name: proto_message_name_tokens,
+    table_mode,
+    example,
+  } = parse_macro_input!(attrs as ValidateAttrData);
   let proto_message_name = proto_message_name_tokens.value();
 
   let input_clone = input.clone();
@@ -68,48 +88,222 @@ pub fn protobuf_validate(attrs: TokenStream, input: TokenStream) -> TokenStream
     }
   };
 
-  let (validators, static_defs): (TokenStream2, TokenStream2) =
-    match extract_message_validators(&ast, &message_desc) {
-      Ok((validators_data, static_defs)) => (validators_data, static_defs),
+  let original_input_as_proc_macro2: proc_macro2::TokenStream = input.into();
+  let struct_ident = &ast.ident;
+
+  // If there are no rules in this message or in any of the messages reachable from it,
+  // validating it is a no-op, so nested_validate can be empty
+  let (nested_validate, static_defs): (TokenStream2, TokenStream2) =
+    if message_has_rules(&message_desc) {
+      let MessageValidatorsOutput {
+        validators,
+        static_defs,
+        field_validators,
+      } = match extract_message_validators(&ast, &message_desc, table_mode) {
+        Ok(output) => output,
+        Err(e) => return e.to_compile_error().into(),
+      };
+
+      // The vectors are not used when every rule is ignored
+      let nested_validate = quote! {
+        #[allow(clippy::ptr_arg)]
+        pub fn nested_validate<'a>(
+          &'a self,
+          validation_context: &::protocheck::context::ValidationContext,
+          parent_messages: &mut Vec<::protocheck::field_data::PathElement<'a>>,
+          violations: &mut Vec<::protocheck::violations::ViolationRecord>
+        ) {
+
+          #validators
+
+        }
+
+        #field_validators
+      };
+
+      (nested_validate, static_defs)
+    } else {
+      let validate_field = extract_validators::validate_field_tokens(struct_ident, &[]);
+
+      let nested_validate = quote! {
+        #[inline]
+        #[allow(clippy::ptr_arg)]
+        pub fn nested_validate<'a>(
+          &'a self,
+          _: &::protocheck::context::ValidationContext,
+          _: &mut Vec<::protocheck::field_data::PathElement<'a>>,
+          _: &mut Vec<::protocheck::violations::ViolationRecord>
+        ) {
+        }
+
+        #validate_field
+      };
+
+      (nested_validate, TokenStream2::new())
+    };
+
+  let field_enum = match get_field_enum_tokens(&ast, &message_desc) {
+    Ok(field_enum) => field_enum,
+    Err(e) => return e.to_compile_error().into(),
+  };
+
+  let schema = match get_schema_tokens(&ast, &message_desc) {
+    Ok(schema) => schema,
+    Err(e) => return e.to_compile_error().into(),
+  };
+
+  let valid_example = if example {
+    let example_tokens = match get_example_tokens(&ast, &message_desc) {
+      Ok(tokens) => tokens,
       Err(e) => return e.to_compile_error().into(),
     };
 
-  let original_input_as_proc_macro2: proc_macro2::TokenStream = input.into();
-  let struct_ident = &ast.ident;
+    quote! {
+      /// Returns an instance of this message that satisfies all of its standard rules.
+      pub fn valid_example() -> Self {
+        #example_tokens
+      }
+    }
+  } else {
+    TokenStream2::new()
+  };
+
+  let strategies = if cfg!(feature = "proptest") {
+    match get_strategy_tokens(&ast, &message_desc) {
+      Ok(tokens) => tokens,
+      Err(e) => return e.to_compile_error().into(),
+    }
+  } else {
+    TokenStream2::new()
+  };
+
+  #[cfg(feature = "cel")]
+  let env_tracking = cel_declarations::env_tracking_tokens();
+  #[cfg(not(feature = "cel"))]
+  let env_tracking = TokenStream2::new();
 
   let output = quote! {
     #static_defs
 
+    #env_tracking
+
     #original_input_as_proc_macro2
 
+    #field_enum
+
+    #strategies
+
     impl #struct_ident {
       pub fn validate(&self) -> Result<(), ::protocheck::types::protovalidate::Violations> {
-        let mut violations: Vec<::protocheck::types::protovalidate::Violation> = Vec::new();
-        let mut parent_messages: Vec<::protocheck::types::protovalidate::FieldPathElement> = Vec::new();
+        self.validate_with_context(&::protocheck::context::ValidationContext::default())
+      }
 
-        self.nested_validate(&mut parent_messages, &mut violations);
+      pub fn validate_with_context(
+        &self,
+        validation_context: &::protocheck::context::ValidationContext
+      ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        let mut violations: Vec<::protocheck::violations::ViolationRecord> = Vec::new();
+        let mut parent_messages: Vec<::protocheck::field_data::PathElement> = Vec::new();
+
+        self.nested_validate(validation_context, &mut parent_messages, &mut violations);
 
         if violations.len() > 0 {
-          return Err(::protocheck::types::protovalidate::Violations { violations });
+          return Err(::protocheck::violations::into_violations(violations));
         }
         Ok(())
       }
 
-      pub fn nested_validate(
+      pub fn validate_with(
         &self,
-        parent_messages: &mut Vec<::protocheck::types::protovalidate::FieldPathElement>,
-        violations: &mut Vec<::protocheck::types::protovalidate::Violation>
-      ) {
+        options: &::protocheck::context::ValidationOptions
+      ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        self.validate_with_context(&::protocheck::context::ValidationContext::default().with_options(options.clone()))
+      }
+
+      pub fn validate_masked(
+        &self,
+        mask: &::protocheck::types::FieldMask
+      ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        self.validate_with_context(&::protocheck::context::ValidationContext::default().with_field_mask(mask))
+      }
 
-        #validators
+      pub fn validate_first(&self) -> Result<(), ::protocheck::types::protovalidate::Violation> {
+        match self.first_violation_record() {
+          Some(violation) => Err(violation.into_violation()),
+          None => Ok(()),
+        }
+      }
 
+      pub fn is_valid(&self) -> bool {
+        self.first_violation_record().is_none()
       }
+
+      /// Validates the message and wraps it in [`Validated`](::protocheck::Validated), which proves that it passed validation.
+      pub fn into_validated(self) -> Result<::protocheck::Validated<Self>, ::protocheck::types::protovalidate::Violations> {
+        ::protocheck::Validated::new(self)
+      }
+
+      fn first_violation_record(&self) -> Option<::protocheck::violations::ViolationRecord> {
+        let validation_context = ::protocheck::context::ValidationContext::default().with_fail_fast(true);
+        let mut violations: Vec<::protocheck::violations::ViolationRecord> = Vec::new();
+        let mut parent_messages: Vec<::protocheck::field_data::PathElement> = Vec::new();
+
+        self.nested_validate(&validation_context, &mut parent_messages, &mut violations);
+
+        violations.pop()
+      }
+
+      /// Returns the static description of the rules defined for this message, its fields and its oneofs.
+      pub fn validation_schema() -> &'static ::protocheck::schema::MessageRulesInfo {
+        static SCHEMA: ::protocheck::schema::MessageRulesInfo = #schema;
+
+        &SCHEMA
+      }
+
+      #valid_example
+
+      #nested_validate
     }
 
     impl ::protocheck::ProtoValidator for #struct_ident {
       fn validate(&self) -> Result<(), ::protocheck::types::protovalidate::Violations> {
         self.validate()
       }
+
+      fn validate_with_context(
+        &self,
+        validation_context: &::protocheck::context::ValidationContext
+      ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        self.validate_with_context(validation_context)
+      }
+
+      fn validate_with(
+        &self,
+        options: &::protocheck::context::ValidationOptions
+      ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        self.validate_with(options)
+      }
+
+      fn validate_masked(
+        &self,
+        mask: &::protocheck::types::FieldMask
+      ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+        self.validate_masked(mask)
+      }
+
+      fn validate_first(&self) -> Result<(), ::protocheck::types::protovalidate::Violation> {
+        self.validate_first()
+      }
+
+      fn is_valid(&self) -> bool {
+        self.is_valid()
+      }
+    }
+
+    impl ::protocheck::ValidationSchema for #struct_ident {
+      fn validation_schema() -> &'static ::protocheck::schema::MessageRulesInfo {
+        Self::validation_schema()
+      }
     }
   };
 
@@ -204,10 +398,11 @@ pub fn protobuf_validate_oneof(attrs: TokenStream, input: TokenStream) -> TokenS
     #original_input_as_proc_macro2
 
     impl #oneof_rust_ident {
-      pub fn validate(
-        &self,
-        parent_messages: &mut Vec<::protocheck::types::protovalidate::FieldPathElement>,
-        violations: &mut Vec<::protocheck::types::protovalidate::Violation>,
+      pub fn validate<'a>(
+        &'a self,
+        validation_context: &::protocheck::context::ValidationContext,
+        parent_messages: &mut Vec<::protocheck::field_data::PathElement<'a>>,
+        violations: &mut Vec<::protocheck::violations::ViolationRecord>,
       ) {
         match self {
           #validators_tokens
@@ -0,0 +1,848 @@
+use proc_macro2::{Literal, TokenStream};
+use prost_reflect::{FieldDescriptor, Kind, MessageDescriptor};
+use proto_types::FieldType;
+use protocheck_core::field_data::FieldKind;
+use quote::quote;
+
+use crate::{
+  cel_rule_template::CelRuleTemplateTarget,
+  rules::core::get_field_type,
+  special_field_names::{proto_name_to_rust_ident, proto_name_to_rust_name},
+};
+
+/// Attempts to compile a Cel expression into native rust code that evaluates to an `Option<bool>` (where `None` stands for an execution error, such as an overflow).
+///
+/// Only a subset of Cel is supported (literals, field access on `this`, comparisons, arithmetic, logical operators, `size` and `has`). If the expression uses anything else, this returns `None` and the expression must be executed by the Cel interpreter.
+pub fn compile_native_cel_expression(
+  rule_target: &CelRuleTemplateTarget,
+  expression: &str,
+) -> Option<TokenStream> {
+  let tokens = tokenize(expression)?;
+  let ast = Parser { tokens, pos: 0 }.parse()?;
+
+  let mut compiler = NativeCompiler {
+    rule_target,
+    is_fallible: false,
+  };
+
+  let compiled = compiler.compile(&ast)?;
+
+  if compiled.ty != NativeType::Bool {
+    return None;
+  }
+
+  let result_tokens = compiled.tokens;
+
+  if compiler.is_fallible {
+    Some(quote! { (|| -> ::core::option::Option<bool> { Some(#result_tokens) })() })
+  } else {
+    Some(quote! { Some(#result_tokens) })
+  }
+}
+
+/// Returns the names of the fields that a Cel expression accesses with `this.<field>`, or `None` if the expression uses `this` as a whole or if it cannot be tokenized.
+pub fn referenced_fields(expression: &str) -> Option<Vec<String>> {
+  let tokens = tokenize(expression)?;
+  let mut fields: Vec<String> = Vec::new();
+
+  for (index, token) in tokens.iter().enumerate() {
+    if !matches!(token, Token::Ident(name) if name == "this") {
+      continue;
+    }
+
+    match (tokens.get(index + 1), tokens.get(index + 2)) {
+      (Some(Token::Dot), Some(Token::Ident(field_name))) => {
+        if !fields.contains(field_name) {
+          fields.push(field_name.clone());
+        }
+      }
+      _ => return None,
+    }
+  }
+
+  Some(fields)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Ident(String),
+  Int(i64),
+  UInt(u64),
+  Double(f64),
+  Str(String),
+  Op(&'static str),
+  LParen,
+  RParen,
+  LBracket,
+  RBracket,
+  Dot,
+  Comma,
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+  let chars: Vec<char> = input.chars().collect();
+  let mut tokens = Vec::new();
+  let mut pos = 0;
+
+  while let Some(&c) = chars.get(pos) {
+    match c {
+      c if c.is_whitespace() => pos += 1,
+      '(' => {
+        tokens.push(Token::LParen);
+        pos += 1;
+      }
+      ')' => {
+        tokens.push(Token::RParen);
+        pos += 1;
+      }
+      '[' => {
+        tokens.push(Token::LBracket);
+        pos += 1;
+      }
+      ']' => {
+        tokens.push(Token::RBracket);
+        pos += 1;
+      }
+      '.' => {
+        tokens.push(Token::Dot);
+        pos += 1;
+      }
+      ',' => {
+        tokens.push(Token::Comma);
+        pos += 1;
+      }
+      '\'' | '"' => {
+        let (string, new_pos) = read_string(&chars, pos)?;
+        tokens.push(Token::Str(string));
+        pos = new_pos;
+      }
+      '0'..='9' => {
+        let (number, new_pos) = read_number(&chars, pos)?;
+        tokens.push(number);
+        pos = new_pos;
+      }
+      c if c.is_ascii_alphabetic() || c == '_' => {
+        let start = pos;
+
+        while chars
+          .get(pos)
+          .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+        {
+          pos += 1;
+        }
+
+        tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+      }
+      _ => {
+        let next = chars.get(pos + 1).copied();
+
+        let (op, len) = match (c, next) {
+          ('&', Some('&')) => ("&&", 2),
+          ('|', Some('|')) => ("||", 2),
+          ('=', Some('=')) => ("==", 2),
+          ('!', Some('=')) => ("!=", 2),
+          ('<', Some('=')) => ("<=", 2),
+          ('>', Some('=')) => (">=", 2),
+          ('<', _) => ("<", 1),
+          ('>', _) => (">", 1),
+          ('!', _) => ("!", 1),
+          ('+', _) => ("+", 1),
+          ('-', _) => ("-", 1),
+          ('*', _) => ("*", 1),
+          ('/', _) => ("/", 1),
+          ('%', _) => ("%", 1),
+          _ => return None,
+        };
+
+        tokens.push(Token::Op(op));
+        pos += len;
+      }
+    }
+  }
+
+  Some(tokens)
+}
+
+fn read_string(chars: &[char], start: usize) -> Option<(String, usize)> {
+  let quote_char = chars[start];
+
+  // Triple-quoted strings are not supported
+  if chars.get(start + 1) == Some(&quote_char) && chars.get(start + 2) == Some(&quote_char) {
+    return None;
+  }
+
+  let mut output = String::new();
+  let mut pos = start + 1;
+
+  loop {
+    let c = *chars.get(pos)?;
+    pos += 1;
+
+    match c {
+      '\\' => {
+        let escaped = match chars.get(pos)? {
+          '\\' => '\\',
+          '\'' => '\'',
+          '"' => '"',
+          'n' => '\n',
+          't' => '\t',
+          'r' => '\r',
+          _ => return None,
+        };
+        output.push(escaped);
+        pos += 1;
+      }
+      c if c == quote_char => return Some((output, pos)),
+      c => output.push(c),
+    }
+  }
+}
+
+fn read_number(chars: &[char], start: usize) -> Option<(Token, usize)> {
+  let mut pos = start;
+
+  if chars[pos] == '0' && matches!(chars.get(pos + 1), Some('x') | Some('X')) {
+    pos += 2;
+    let digits_start = pos;
+
+    while chars
+      .get(pos)
+      .is_some_and(|c| c.is_ascii_hexdigit())
+    {
+      pos += 1;
+    }
+
+    let digits: String = chars[digits_start..pos].iter().collect();
+
+    return if matches!(chars.get(pos), Some('u') | Some('U')) {
+      Some((Token::UInt(u64::from_str_radix(&digits, 16).ok()?), pos + 1))
+    } else {
+      Some((Token::Int(i64::from_str_radix(&digits, 16).ok()?), pos))
+    };
+  }
+
+  let mut is_double = false;
+
+  while chars.get(pos).is_some_and(|c| c.is_ascii_digit()) {
+    pos += 1;
+  }
+
+  if chars.get(pos) == Some(&'.')
+    && chars
+      .get(pos + 1)
+      .is_some_and(|c| c.is_ascii_digit())
+  {
+    is_double = true;
+    pos += 1;
+
+    while chars.get(pos).is_some_and(|c| c.is_ascii_digit()) {
+      pos += 1;
+    }
+  }
+
+  if matches!(chars.get(pos), Some('e') | Some('E')) {
+    is_double = true;
+    pos += 1;
+
+    if matches!(chars.get(pos), Some('+') | Some('-')) {
+      pos += 1;
+    }
+
+    while chars.get(pos).is_some_and(|c| c.is_ascii_digit()) {
+      pos += 1;
+    }
+  }
+
+  let number: String = chars[start..pos].iter().collect();
+
+  if is_double {
+    let value: f64 = number.parse().ok()?;
+
+    value
+      .is_finite()
+      .then_some((Token::Double(value), pos))
+  } else if matches!(chars.get(pos), Some('u') | Some('U')) {
+    Some((Token::UInt(number.parse().ok()?), pos + 1))
+  } else {
+    Some((Token::Int(number.parse().ok()?), pos))
+  }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+  Int(i64),
+  UInt(u64),
+  Double(f64),
+  Str(String),
+  Bool(bool),
+  Ident(String),
+  Member(Box<Expr>, String),
+  Index(Box<Expr>, Box<Expr>),
+  Call(String, Vec<Expr>),
+  Unary(&'static str, Box<Expr>),
+  Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl Parser {
+  fn parse(mut self) -> Option<Expr> {
+    let expr = self.parse_or()?;
+
+    (self.pos == self.tokens.len()).then_some(expr)
+  }
+
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn advance(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    self.pos += 1;
+    token
+  }
+
+  fn expect(&mut self, expected: &Token) -> Option<()> {
+    (self.advance()? == *expected).then_some(())
+  }
+
+  fn peek_op(&self, ops: &[&'static str]) -> Option<&'static str> {
+    match self.peek() {
+      Some(Token::Op(op)) if ops.contains(op) => Some(*op),
+      _ => None,
+    }
+  }
+
+  fn parse_binary(
+    &mut self,
+    ops: &[&'static str],
+    next_level: fn(&mut Self) -> Option<Expr>,
+  ) -> Option<Expr> {
+    let mut lhs = next_level(self)?;
+
+    while let Some(op) = self.peek_op(ops) {
+      self.pos += 1;
+      let rhs = next_level(self)?;
+      lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+    }
+
+    Some(lhs)
+  }
+
+  fn parse_or(&mut self) -> Option<Expr> {
+    self.parse_binary(&["||"], Self::parse_and)
+  }
+
+  fn parse_and(&mut self) -> Option<Expr> {
+    self.parse_binary(&["&&"], Self::parse_relation)
+  }
+
+  fn parse_relation(&mut self) -> Option<Expr> {
+    self.parse_binary(&["<", "<=", ">", ">=", "==", "!="], Self::parse_addition)
+  }
+
+  fn parse_addition(&mut self) -> Option<Expr> {
+    self.parse_binary(&["+", "-"], Self::parse_multiplication)
+  }
+
+  fn parse_multiplication(&mut self) -> Option<Expr> {
+    self.parse_binary(&["*", "/", "%"], Self::parse_unary)
+  }
+
+  fn parse_unary(&mut self) -> Option<Expr> {
+    if let Some(op) = self.peek_op(&["!", "-"]) {
+      self.pos += 1;
+      let operand = self.parse_unary()?;
+
+      return Some(Expr::Unary(op, Box::new(operand)));
+    }
+
+    self.parse_member()
+  }
+
+  fn parse_member(&mut self) -> Option<Expr> {
+    let mut expr = self.parse_primary()?;
+
+    loop {
+      match self.peek() {
+        Some(Token::Dot) => {
+          self.pos += 1;
+          let name = match self.advance()? {
+            Token::Ident(name) => name,
+            _ => return None,
+          };
+
+          // Method calls are not supported
+          if self.peek() == Some(&Token::LParen) {
+            return None;
+          }
+
+          expr = Expr::Member(Box::new(expr), name);
+        }
+        Some(Token::LBracket) => {
+          self.pos += 1;
+          let index = self.parse_or()?;
+          self.expect(&Token::RBracket)?;
+
+          expr = Expr::Index(Box::new(expr), Box::new(index));
+        }
+        _ => return Some(expr),
+      }
+    }
+  }
+
+  fn parse_primary(&mut self) -> Option<Expr> {
+    match self.advance()? {
+      Token::Int(v) => Some(Expr::Int(v)),
+      Token::UInt(v) => Some(Expr::UInt(v)),
+      Token::Double(v) => Some(Expr::Double(v)),
+      Token::Str(v) => Some(Expr::Str(v)),
+      Token::LParen => {
+        let expr = self.parse_or()?;
+        self.expect(&Token::RParen)?;
+        Some(expr)
+      }
+      Token::Ident(name) => match name.as_str() {
+        "true" => Some(Expr::Bool(true)),
+        "false" => Some(Expr::Bool(false)),
+        "null" => None,
+        _ => {
+          if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let mut args = Vec::new();
+
+            if self.peek() != Some(&Token::RParen) {
+              loop {
+                args.push(self.parse_or()?);
+
+                if self.peek() == Some(&Token::Comma) {
+                  self.pos += 1;
+                } else {
+                  break;
+                }
+              }
+            }
+
+            self.expect(&Token::RParen)?;
+
+            Some(Expr::Call(name, args))
+          } else {
+            Some(Expr::Ident(name))
+          }
+        }
+      },
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NativeType {
+  Int,
+  UInt,
+  Double,
+  Bool,
+  String,
+  Bytes,
+  List,
+  Map,
+}
+
+struct Compiled {
+  tokens: TokenStream,
+  ty: NativeType,
+  int_literal: Option<i64>,
+}
+
+impl Compiled {
+  fn new(tokens: TokenStream, ty: NativeType) -> Self {
+    Self {
+      tokens,
+      ty,
+      int_literal: None,
+    }
+  }
+}
+
+enum This {
+  Message {
+    message_desc: MessageDescriptor,
+    base: TokenStream,
+  },
+  Value(Compiled),
+}
+
+struct NativeCompiler<'a> {
+  rule_target: &'a CelRuleTemplateTarget<'a>,
+  is_fallible: bool,
+}
+
+impl NativeCompiler<'_> {
+  fn compile(&mut self, expr: &Expr) -> Option<Compiled> {
+    match expr {
+      Expr::Int(v) => Some(Compiled {
+        tokens: int_literal_tokens(*v),
+        ty: NativeType::Int,
+        int_literal: Some(*v),
+      }),
+      Expr::UInt(v) => {
+        let literal = Literal::u64_suffixed(*v);
+        Some(Compiled::new(quote! { #literal }, NativeType::UInt))
+      }
+      Expr::Double(v) => {
+        let literal = Literal::f64_suffixed(*v);
+        Some(Compiled::new(quote! { (#literal) }, NativeType::Double))
+      }
+      Expr::Str(v) => {
+        let literal = Literal::string(v);
+        Some(Compiled::new(quote! { #literal }, NativeType::String))
+      }
+      Expr::Bool(v) => Some(Compiled::new(quote! { #v }, NativeType::Bool)),
+      Expr::Ident(name) => match self.resolve_ident(name)? {
+        This::Value(compiled) => Some(compiled),
+        This::Message { .. } => None,
+      },
+      Expr::Member(..) | Expr::Index(..) => {
+        let (message_desc, base, field_name) = self.resolve_field_access(expr)?;
+        let field = find_field(&message_desc, &field_name)?;
+
+        field_value(&field, &base)
+      }
+      Expr::Call(name, args) => self.compile_call(name, args),
+      Expr::Unary(op, operand) => self.compile_unary(op, operand),
+      Expr::Binary(op, lhs, rhs) => self.compile_binary(op, lhs, rhs),
+    }
+  }
+
+  fn resolve_ident(&self, name: &str) -> Option<This> {
+    if name != "this" {
+      return None;
+    }
+
+    match self.rule_target {
+      CelRuleTemplateTarget::Message { message_desc, .. } => Some(This::Message {
+        message_desc: (*message_desc).clone(),
+        base: quote! { self },
+      }),
+      CelRuleTemplateTarget::Field {
+        field_desc,
+        validation_data,
+      } => {
+        let value_ident = validation_data.value_ident();
+
+        match validation_data.field_kind {
+          FieldKind::Repeated(_) => Some(This::Value(Compiled::new(
+            quote! { #value_ident },
+            NativeType::List,
+          ))),
+          FieldKind::Map(_) => Some(This::Value(Compiled::new(
+            quote! { #value_ident },
+            NativeType::Map,
+          ))),
+          field_kind => match field_kind.inner_type() {
+            FieldType::Message => {
+              if let Kind::Message(message_desc) = field_desc.kind() {
+                Some(This::Message {
+                  message_desc,
+                  base: quote! { (#value_ident) },
+                })
+              } else {
+                None
+              }
+            }
+            field_type => scalar_value(field_type, &quote! { (#value_ident) }).map(This::Value),
+          },
+        }
+      }
+    }
+  }
+
+  fn resolve_field_access(&self, expr: &Expr) -> Option<(MessageDescriptor, TokenStream, String)> {
+    let (object, field_name) = match expr {
+      Expr::Member(object, name) => (object, name.clone()),
+      Expr::Index(object, index) => match index.as_ref() {
+        Expr::Str(name) => (object, name.clone()),
+        _ => return None,
+      },
+      _ => return None,
+    };
+
+    let name = match object.as_ref() {
+      Expr::Ident(name) => name,
+      _ => return None,
+    };
+
+    match self.resolve_ident(name)? {
+      This::Message { message_desc, base } => Some((message_desc, base, field_name)),
+      This::Value(_) => None,
+    }
+  }
+
+  fn compile_call(&mut self, name: &str, args: &[Expr]) -> Option<Compiled> {
+    if args.len() != 1 {
+      return None;
+    }
+
+    match name {
+      "size" => {
+        let arg = self.compile(&args[0])?;
+        let arg_tokens = &arg.tokens;
+
+        let tokens = match arg.ty {
+          NativeType::String => quote! { ((#arg_tokens).chars().count() as i64) },
+          NativeType::Bytes | NativeType::List | NativeType::Map => {
+            quote! { ((#arg_tokens).len() as i64) }
+          }
+          _ => return None,
+        };
+
+        Some(Compiled::new(tokens, NativeType::Int))
+      }
+      "has" => {
+        let (message_desc, base, field_name) = self.resolve_field_access(&args[0])?;
+        let field = find_field(&message_desc, &field_name)?;
+
+        if field
+          .containing_oneof()
+          .is_some_and(|oneof| !oneof.is_synthetic())
+        {
+          return None;
+        }
+
+        let field_ident = proto_name_to_rust_ident(field.name());
+        let access = quote! { #base.#field_ident };
+
+        let tokens = if field.is_list() || field.is_map() {
+          quote! { !#access.is_empty() }
+        } else if field.supports_presence() {
+          quote! { #access.is_some() }
+        } else {
+          match get_field_type(&field) {
+            FieldType::String | FieldType::Bytes => quote! { !#access.is_empty() },
+            FieldType::Bool => quote! { #access },
+            FieldType::Float | FieldType::Double => quote! { (#access != 0.0) },
+            _ => quote! { (#access != 0) },
+          }
+        };
+
+        Some(Compiled::new(tokens, NativeType::Bool))
+      }
+      _ => None,
+    }
+  }
+
+  fn compile_unary(&mut self, op: &str, operand: &Expr) -> Option<Compiled> {
+    let compiled = self.compile(operand)?;
+    let tokens = &compiled.tokens;
+
+    match (op, compiled.ty) {
+      ("!", NativeType::Bool) => Some(Compiled::new(quote! { !(#tokens) }, NativeType::Bool)),
+      ("-", NativeType::Int) => {
+        if let Some(literal) = compiled.int_literal {
+          let negated = literal.checked_neg()?;
+
+          Some(Compiled {
+            tokens: int_literal_tokens(negated),
+            ty: NativeType::Int,
+            int_literal: Some(negated),
+          })
+        } else {
+          self.is_fallible = true;
+          Some(Compiled::new(
+            quote! { (#tokens).checked_neg()? },
+            NativeType::Int,
+          ))
+        }
+      }
+      ("-", NativeType::Double) => Some(Compiled::new(quote! { (-(#tokens)) }, NativeType::Double)),
+      _ => None,
+    }
+  }
+
+  // In Cel, `&&` and `||` absorb the errors of their operands when the other operand alone determines the result (i.e. `false && <error>` is `false`, and `true || <error>` is `true`), so fallible operands are evaluated separately and only produce an error when the result depends on them.
+  fn compile_logical(&mut self, op: &str, lhs: &Expr, rhs: &Expr) -> Option<Compiled> {
+    let was_fallible = self.is_fallible;
+
+    self.is_fallible = false;
+    let lhs = self.compile(lhs)?;
+    let is_lhs_fallible = self.is_fallible;
+
+    self.is_fallible = false;
+    let rhs = self.compile(rhs)?;
+    let is_rhs_fallible = self.is_fallible;
+
+    if lhs.ty != NativeType::Bool || rhs.ty != NativeType::Bool {
+      return None;
+    }
+
+    let (lhs, rhs) = (&lhs.tokens, &rhs.tokens);
+
+    if !is_lhs_fallible && !is_rhs_fallible {
+      self.is_fallible = was_fallible;
+
+      let op_tokens: TokenStream = op.parse().ok()?;
+      return Some(Compiled::new(
+        quote! { (#lhs #op_tokens #rhs) },
+        NativeType::Bool,
+      ));
+    }
+
+    self.is_fallible = true;
+
+    // The value that decides the result regardless of the other operand
+    let absorbing = op == "||";
+
+    Some(Compiled::new(
+      quote! {
+        match (|| -> ::core::option::Option<bool> { Some(#lhs) })() {
+          Some(#absorbing) => #absorbing,
+          lhs => match ((|| -> ::core::option::Option<bool> { Some(#rhs) })(), lhs) {
+            (Some(#absorbing), _) => #absorbing,
+            (Some(_), Some(_)) => !#absorbing,
+            _ => return None,
+          },
+        }
+      },
+      NativeType::Bool,
+    ))
+  }
+
+  fn compile_binary(&mut self, op: &str, lhs: &Expr, rhs: &Expr) -> Option<Compiled> {
+    if matches!(op, "&&" | "||") {
+      return self.compile_logical(op, lhs, rhs);
+    }
+
+    let (lhs, rhs) = coerce_literals(self.compile(lhs)?, self.compile(rhs)?)?;
+    let ty = lhs.ty;
+    let (lhs, rhs) = (&lhs.tokens, &rhs.tokens);
+
+    match op {
+      "<" | "<=" | ">" | ">=" | "==" | "!=" => {
+        if matches!(ty, NativeType::List | NativeType::Map) {
+          return None;
+        }
+
+        let op_tokens: TokenStream = op.parse().ok()?;
+        Some(Compiled::new(
+          quote! { (#lhs #op_tokens #rhs) },
+          NativeType::Bool,
+        ))
+      }
+      "+" | "-" | "*" | "/" | "%" => match ty {
+        NativeType::Int | NativeType::UInt => {
+          let method = match op {
+            "+" => quote! { checked_add },
+            "-" => quote! { checked_sub },
+            "*" => quote! { checked_mul },
+            "/" => quote! { checked_div },
+            _ => quote! { checked_rem },
+          };
+
+          self.is_fallible = true;
+          Some(Compiled::new(quote! { (#lhs).#method(#rhs)? }, ty))
+        }
+        NativeType::Double if op != "%" => {
+          let op_tokens: TokenStream = op.parse().ok()?;
+          Some(Compiled::new(
+            quote! { (#lhs #op_tokens #rhs) },
+            NativeType::Double,
+          ))
+        }
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+}
+
+fn int_literal_tokens(value: i64) -> TokenStream {
+  let literal = Literal::i64_suffixed(value);
+  quote! { (#literal) }
+}
+
+/// Converts integer literals to the type of the other operand, where this can be done without changing their value.
+fn coerce_literals(lhs: Compiled, rhs: Compiled) -> Option<(Compiled, Compiled)> {
+  if lhs.ty == rhs.ty {
+    return Some((lhs, rhs));
+  }
+
+  let coerce = |literal: i64, target: NativeType| -> Option<Compiled> {
+    match target {
+      NativeType::UInt => {
+        let literal = Literal::u64_suffixed(u64::try_from(literal).ok()?);
+        Some(Compiled::new(quote! { #literal }, NativeType::UInt))
+      }
+      NativeType::Double => {
+        let literal = Literal::f64_suffixed(literal as f64);
+        Some(Compiled::new(quote! { (#literal) }, NativeType::Double))
+      }
+      _ => None,
+    }
+  };
+
+  if let Some(literal) = rhs.int_literal {
+    let rhs = coerce(literal, lhs.ty)?;
+    Some((lhs, rhs))
+  } else if let Some(literal) = lhs.int_literal {
+    let lhs = coerce(literal, rhs.ty)?;
+    Some((lhs, rhs))
+  } else {
+    None
+  }
+}
+
+fn find_field(message_desc: &MessageDescriptor, cel_name: &str) -> Option<FieldDescriptor> {
+  message_desc
+    .fields()
+    .find(|field| proto_name_to_rust_name(field.name()) == cel_name)
+}
+
+fn field_value(field: &FieldDescriptor, base: &TokenStream) -> Option<Compiled> {
+  if field
+    .containing_oneof()
+    .is_some_and(|oneof| !oneof.is_synthetic())
+  {
+    return None;
+  }
+
+  let field_ident = proto_name_to_rust_ident(field.name());
+  let access = quote! { #base.#field_ident };
+
+  if field.is_list() {
+    Some(Compiled::new(access, NativeType::List))
+  } else if field.is_map() {
+    Some(Compiled::new(access, NativeType::Map))
+  } else if field.supports_presence() {
+    // Fields with presence can only be used with `has`
+    None
+  } else {
+    scalar_value(get_field_type(field), &access)
+  }
+}
+
+fn scalar_value(field_type: FieldType, value_tokens: &TokenStream) -> Option<Compiled> {
+  let compiled = match field_type {
+    FieldType::Int32
+    | FieldType::Int64
+    | FieldType::Sint32
+    | FieldType::Sint64
+    | FieldType::Sfixed32
+    | FieldType::Sfixed64
+    | FieldType::Enum => Compiled::new(quote! { (#value_tokens as i64) }, NativeType::Int),
+    FieldType::Uint32 | FieldType::Uint64 | FieldType::Fixed32 | FieldType::Fixed64 => {
+      Compiled::new(quote! { (#value_tokens as u64) }, NativeType::UInt)
+    }
+    FieldType::Float | FieldType::Double => {
+      Compiled::new(quote! { (#value_tokens as f64) }, NativeType::Double)
+    }
+    FieldType::Bool => Compiled::new(quote! { #value_tokens }, NativeType::Bool),
+    FieldType::String => Compiled::new(quote! { #value_tokens.as_str() }, NativeType::String),
+    FieldType::Bytes => Compiled::new(quote! { &#value_tokens[..] }, NativeType::Bytes),
+    _ => return None,
+  };
+
+  Some(compiled)
+}
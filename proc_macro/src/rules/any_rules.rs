@@ -25,11 +25,17 @@ pub fn get_any_rules(
     .map_err(|invalid_items| invalid_lists_error(field_span, field_name, &invalid_items))?;
 
   if let Some(in_list) = in_list_rule {
-    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, static_defs);
+    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, None, static_defs);
   }
 
   if let Some(not_in_list) = not_in_list_rule {
-    validation_data.get_list_validator(ListRule::NotIn, &mut tokens, not_in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::NotIn,
+      &mut tokens,
+      not_in_list,
+      None,
+      static_defs,
+    );
   }
 
   Ok(tokens)
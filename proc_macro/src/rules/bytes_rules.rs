@@ -62,11 +62,17 @@ pub fn get_bytes_rules(
   }
 
   if let Some(in_list) = in_list_rule {
-    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, static_defs);
+    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, None, static_defs);
   }
 
   if let Some(not_in_list) = not_in_list_rule {
-    validation_data.get_list_validator(ListRule::NotIn, &mut tokens, not_in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::NotIn,
+      &mut tokens,
+      not_in_list,
+      None,
+      static_defs,
+    );
   }
 
   if let Some(well_known) = rules.well_known {
@@ -82,13 +88,19 @@ pub fn get_bytes_rules(
       }),
     };
 
-    let field_context_ident = validation_data.field_context_ident();
-    let value_ident = validation_data.value_ident();
-
-    let validator_expression_tokens = quote! {
-      ::protocheck::validators::bytes::#validator_path(&#field_context_ident, &#value_ident)
-    };
-    validation_data.get_validator_tokens(&mut tokens, &validator_expression_tokens);
+    if let Some(validator_path) = validator_path {
+      let field_context_ident = validation_data.field_context_ident();
+      let value_ident = validation_data.value_ident();
+
+      let validator_expression_tokens = quote! {
+        ::protocheck::validators::bytes::#validator_path(&#field_context_ident, &#value_ident)
+      };
+      validation_data.get_validator_tokens(
+        &mut tokens,
+        &format!("bytes.{}", validator_path),
+        &validator_expression_tokens,
+      );
+    }
   }
 
   Ok(tokens)
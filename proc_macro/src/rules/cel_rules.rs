@@ -3,13 +3,114 @@ pub fn get_cel_rules_checked(
   rules: &[Rule],
   static_defs: &mut TokenStream,
 ) -> Result<TokenStream, Error> {
-  if cfg!(feature = "cel") {
-    get_cel_rules(rule_target, rules, static_defs)
-  } else {
-    unimplemented!("Cannot use Cel validators without the 'cel' feature")
+  let mut tokens = TokenStream::new();
+  let mut interpreted_rules: Vec<Rule> = Vec::new();
+
+  for rule in rules {
+    match compile_native_cel_expression(rule_target, rule.expression()) {
+      Some(result_tokens) => tokens.extend(get_native_cel_rule_tokens(
+        rule_target,
+        rule,
+        &result_tokens,
+      )),
+      None => interpreted_rules.push(rule.clone()),
+    }
+  }
+
+  if interpreted_rules.is_empty() {
+    return Ok(tokens);
+  }
+
+  #[cfg(feature = "cel")]
+  {
+    tokens.extend(get_cel_rules(rule_target, &interpreted_rules, static_defs)?);
+
+    Ok(tokens)
+  }
+
+  #[cfg(not(feature = "cel"))]
+  {
+    let _ = static_defs;
+
+    Err(Error::new(
+      Span2::call_site(),
+      format!(
+        "The Cel expression `{}` for {} {} cannot be compiled to native rust code, so it requires the 'cel' feature",
+        interpreted_rules[0].expression(),
+        rule_target.get_validation_type(),
+        rule_target.get_full_name()
+      ),
+    ))
+  }
+}
+
+fn get_native_cel_rule_tokens(
+  rule_target: &CelRuleTemplateTarget,
+  rule: &Rule,
+  result_tokens: &TokenStream,
+) -> TokenStream {
+  let rule_id = rule.id();
+  let error_message = rule.message();
+  let (validation_context_ident, parent_messages_ident, violations_ident) =
+    rule_target.get_idents();
+  let push_violation =
+    push_violation_tokens(validation_context_ident, violations_ident, quote! { v });
+
+  let validation_expression = match rule_target {
+    CelRuleTemplateTarget::Field {
+      validation_data, ..
+    } => {
+      let field_context_ident = validation_data.field_context_ident();
+
+      quote! { validate_native_cel_field(&#field_context_ident, #rule_id, #error_message, #result_tokens) }
+    }
+    CelRuleTemplateTarget::Message { .. } => {
+      let message_name = rule_target.get_full_name();
+
+      quote! { validate_native_cel_message(#parent_messages_ident, #rule_id, #error_message, #message_name, #result_tokens) }
+    }
+  };
+
+  let mask_check = message_rule_mask_check(rule_target, rule.expression());
+
+  quote! {
+    if #validation_context_ident.is_rule_enabled(::protocheck::context::RuleCategory::Cel, #rule_id) #mask_check {
+      match ::protocheck::validators::native_cel::#validation_expression {
+        Ok(_) => {}
+        Err(v) => #push_violation
+      };
+    }
   }
 }
 
+// Message-level rules only run when the fields that they reference are included in the field mask, if one is being used
+fn message_rule_mask_check(
+  rule_target: &CelRuleTemplateTarget,
+  expression: &str,
+) -> Option<TokenStream> {
+  let CelRuleTemplateTarget::Message {
+    message_desc,
+    validation_context_ident,
+    parent_messages_ident,
+    ..
+  } = rule_target
+  else {
+    return None;
+  };
+
+  // The rules that use `this` as a whole reference all of the fields of the message
+  let referenced_fields = referenced_fields(expression).unwrap_or_else(|| {
+    message_desc
+      .fields()
+      .map(|field| field.name().to_string())
+      .collect()
+  });
+
+  Some(quote! {
+    && #validation_context_ident.is_message_rule_in_mask(#parent_messages_ident.as_slice(), &[#(#referenced_fields),*])
+  })
+}
+
 #[cfg(feature = "cel")]
 mod cel {
   use std::{collections::HashMap, sync::Arc};
@@ -24,8 +125,11 @@ mod cel {
 
   use super::super::Rule;
   use crate::{
-    cel_rule_template::CelRuleTemplateTarget, special_field_names::proto_name_to_rust_name,
-    validation_data::ValidationData, Ident2, Span2,
+    cel_declarations::add_cel_declarations,
+    cel_rule_template::CelRuleTemplateTarget,
+    special_field_names::proto_name_to_rust_name,
+    validation_data::{push_violation_tokens, ValidationData},
+    Ident2, Span2,
   };
 
   pub fn get_cel_rules(
@@ -52,7 +156,10 @@ mod cel {
 
     let error_prefix = format!("Cel program error for {} {}:", validation_type, target_name);
 
-    let (parent_messages_ident, violations_ident) = rule_target.get_idents();
+    let (validation_context_ident, parent_messages_ident, violations_ident) =
+      rule_target.get_idents();
+    let push_violation =
+      push_violation_tokens(validation_context_ident, violations_ident, quote! { v });
 
     for (index, rule) in rules.iter().enumerate() {
       let program = match Program::compile(rule.expression()) {
@@ -67,6 +174,9 @@ mod cel {
 
       let mut context = Context::default();
 
+      if let Err(e) = add_cel_declarations(&mut context) {
+        return Err(syn::Error::new(Span2::call_site(), e));
+      }
       context.add_variable_from_value("this", &cel_value);
 
       match program.execute(&context) {
@@ -114,44 +224,50 @@ mod cel {
 
                 let validation_expression = match validation_data.field_kind.inner_type() {
                   FieldType::Message | FieldType::Timestamp | FieldType::Duration => {
-                    quote! { validate_cel_field_try_into(&#field_context_ident, rule, #value_ident.clone()) }
+                    quote! { validate_cel_field_try_into(#validation_context_ident, &#field_context_ident, rule, #value_ident.clone()) }
                   }
                   FieldType::Bytes => {
-                    quote! { validate_cel_field_with_val(&#field_context_ident, rule, #value_ident.to_vec().into()) }
+                    quote! { validate_cel_field_with_val(#validation_context_ident, &#field_context_ident, rule, #value_ident.to_vec().into()) }
                   }
                   FieldType::Float => {
-                    quote! { validate_cel_field_with_val(&#field_context_ident, rule, (#value_ident as f64).into()) }
+                    quote! { validate_cel_field_with_val(#validation_context_ident, &#field_context_ident, rule, (#value_ident as f64).into()) }
                   }
                   FieldType::Int32 | FieldType::Sint32 | FieldType::Sfixed32 => {
-                    quote! { validate_cel_field_with_val(&#field_context_ident, rule, (#value_ident as i64).into()) }
+                    quote! { validate_cel_field_with_val(#validation_context_ident, &#field_context_ident, rule, (#value_ident as i64).into()) }
                   }
                   FieldType::Uint32 | FieldType::Fixed32 => {
-                    quote! { validate_cel_field_with_val(&#field_context_ident, rule, (#value_ident as u64).into()) }
+                    quote! { validate_cel_field_with_val(#validation_context_ident, &#field_context_ident, rule, (#value_ident as u64).into()) }
                   }
                   _ => {
-                    quote! { validate_cel_field_with_val(&#field_context_ident, rule, (#value_ident).clone().into()) }
+                    quote! { validate_cel_field_with_val(#validation_context_ident, &#field_context_ident, rule, (#value_ident).clone().into()) }
                   }
                 };
 
                 let validator_tokens = quote! {
-                  let rule = #rule_tokens;
+                  if #validation_context_ident.is_rule_enabled(::protocheck::context::RuleCategory::Cel, #rule_id) {
+                    let rule = #rule_tokens;
 
-                  match ::protocheck::validators::cel::#validation_expression {
-                    Ok(_) => {}
-                    Err(v) => #violations_ident.push(v)
-                  };
+                    match ::protocheck::validators::cel::#validation_expression {
+                      Ok(_) => {}
+                      Err(v) => #push_violation
+                    };
+                  }
                 };
 
                 tokens.extend(validator_tokens);
               }
               CelRuleTemplateTarget::Message { .. } => {
+                let mask_check = super::message_rule_mask_check(rule_target, &expression);
+
                 let validator_tokens = quote! {
-                  let rule = #rule_tokens;
+                  if #validation_context_ident.is_rule_enabled(::protocheck::context::RuleCategory::Cel, #rule_id) #mask_check {
+                    let rule = #rule_tokens;
 
-                  match ::protocheck::validators::cel::validate_cel_message(#parent_messages_ident, rule, self.clone()) {
-                    Ok(_) => {}
-                    Err(v) => #violations_ident.push(v)
-                  };
+                    match ::protocheck::validators::cel::validate_cel_message(#validation_context_ident, #parent_messages_ident, rule, self.clone()) {
+                      Ok(_) => {}
+                      Err(v) => #push_violation
+                    };
+                  }
                 };
 
                 tokens.extend(validator_tokens);
@@ -282,6 +398,13 @@ mod cel {
 pub use cel::*;
 use proc_macro2::TokenStream;
 use proto_types::protovalidate::Rule;
+use quote::quote;
 use syn::Error;
 
-use crate::cel_rule_template::CelRuleTemplateTarget;
+#[cfg(not(feature = "cel"))]
+use crate::Span2;
+use crate::{
+  cel_rule_template::CelRuleTemplateTarget,
+  native_cel::{compile_native_cel_expression, referenced_fields},
+  validation_data::push_violation_tokens,
+};
@@ -4,7 +4,7 @@ use syn::Error;
 
 use crate::{
   rules::core::{get_field_error, invalid_lists_error},
-  validation_data::{ListRule, ValidationData},
+  validation_data::{ListRule, SortedListValues, ValidationData},
 };
 
 pub fn get_duration_rules(
@@ -39,11 +39,23 @@ pub fn get_duration_rules(
     .map_err(|invalid_items| invalid_lists_error(field_span, field_name, &invalid_items))?;
 
   if let Some(in_list) = in_list_rule {
-    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::In,
+      &mut tokens,
+      in_list,
+      rules.sorted_in(),
+      static_defs,
+    );
   }
 
   if let Some(not_in_list) = not_in_list_rule {
-    validation_data.get_list_validator(ListRule::NotIn, &mut tokens, not_in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::NotIn,
+      &mut tokens,
+      not_in_list,
+      rules.sorted_not_in(),
+      static_defs,
+    );
   }
 
   Ok(tokens)
@@ -9,7 +9,7 @@ use syn::Error;
 use super::protovalidate::EnumRules;
 use crate::{
   rules::core::{get_field_error, invalid_lists_error},
-  validation_data::{ListRule, ValidationData},
+  validation_data::{ListRule, SortedListValues, ValidationData},
 };
 
 pub fn get_enum_rules(
@@ -35,19 +35,24 @@ pub fn get_enum_rules(
   if rules.defined_only() {
     let enum_ident_tokens: TokenStream = enum_ident_str.parse().unwrap_or(quote! { compile_error!(format!("Failed to parse enum ident {} into tokens for enum {} in field {}", field_type_ident, enum_name, field_name)) });
 
-    let violations_ident = &validation_data.violations_ident;
     let field_context_ident = &validation_data.field_context_ident();
     let value_ident = validation_data.value_ident();
 
     let error_message = format!("must be a defined value of '{}'", enum_name);
 
-    let validator_tokens = quote! {
-      if !#enum_ident_tokens::try_from(#value_ident).is_ok() {
-        #violations_ident.push(::protocheck::validators::enums::defined_only(&#field_context_ident, #error_message));
+    let validator_expression_tokens = quote! {
+      if #enum_ident_tokens::try_from(#value_ident).is_ok() {
+        Ok(())
+      } else {
+        Err(::protocheck::validators::enums::defined_only(&#field_context_ident, #error_message))
       }
     };
 
-    tokens.extend(validator_tokens);
+    validation_data.get_validator_tokens(
+      &mut tokens,
+      "enum.defined_only",
+      &validator_expression_tokens,
+    );
   }
 
   let ContainingRules {
@@ -78,11 +83,23 @@ pub fn get_enum_rules(
       ));
     }
 
-    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::In,
+      &mut tokens,
+      in_list,
+      rules.sorted_in(),
+      static_defs,
+    );
   }
 
   if let Some(not_in_list) = not_in_list_rule {
-    validation_data.get_list_validator(ListRule::NotIn, &mut tokens, not_in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::NotIn,
+      &mut tokens,
+      not_in_list,
+      rules.sorted_not_in(),
+      static_defs,
+    );
   }
 
   Ok(tokens)
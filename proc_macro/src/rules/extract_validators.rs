@@ -1,8 +1,14 @@
-use std::{cell::OnceCell, collections::HashMap};
+use std::{
+  cell::OnceCell,
+  collections::{HashMap, HashSet},
+  sync::{LazyLock, Mutex},
+};
 
+use convert_case::{Case, Casing};
 use proc_macro2::{Ident as Ident2, TokenStream};
 use prost_reflect::{
-  prost::Message, FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor, Value as ProstValue,
+  prost::Message, DescriptorPool, FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor,
+  Value as ProstValue,
 };
 use proto_types::FieldType;
 use protocheck_core::field_data::FieldKind;
@@ -17,6 +23,7 @@ use super::{
 use crate::{
   attribute_extractors::{extract_proto_name_attribute, ProstAttrData},
   cel_rule_template::CelRuleTemplateTarget,
+  field_enum::{field_enum_ident, field_variant_ident},
   pool_loader::{
     FIELD_RULES_EXT_DESCRIPTOR, MESSAGE_RULES_EXT_DESCRIPTOR, ONEOF_RULES_EXT_DESCRIPTOR,
   },
@@ -25,8 +32,9 @@ use crate::{
     core::{get_field_kind, get_field_rules, get_field_type},
     map_rules::get_map_rules,
     repeated_rules::get_repeated_rules,
+    table_rules::{get_table_field, table_field_validator_tokens},
   },
-  validation_data::ValidationData,
+  validation_data::{push_unfiltered_violation_tokens, ValidationData},
   Span2,
 };
 
@@ -148,6 +156,7 @@ pub fn extract_oneof_validators(
       let map_value_ident = format_ident!("val");
       let violations_ident = format_ident!("violations");
       let parent_messages_ident = format_ident!("parent_messages");
+      let validation_context_ident = format_ident!("validation_context");
       let map_key_context_ident = format_ident!("key_context");
       let map_value_context_ident = format_ident!("value_context");
       let vec_item_context_ident = format_ident!("item_context");
@@ -168,6 +177,7 @@ pub fn extract_oneof_validators(
         field_context_ident: &field_context_ident,
         item_ident: &item_ident,
         parent_messages_ident: &parent_messages_ident,
+        validation_context_ident: &validation_context_ident,
         map_key_ident: &key_ident,
         map_value_ident: &map_value_ident,
         index_ident: &index_ident,
@@ -177,6 +187,7 @@ pub fn extract_oneof_validators(
         map_value_context_ident: &map_value_context_ident,
         vec_item_context_ident: &vec_item_context_ident,
         value_ident: OnceCell::new(),
+        table_rules: None,
       };
 
       if !field_rules.cel.is_empty() {
@@ -205,13 +216,17 @@ pub fn extract_oneof_validators(
         field_validators = validation_data.get_aggregated_validator_tokens(field_validators);
       }
 
-      if field_is_message(&field.kind()) {
+      if field_needs_validation(&field.kind()) {
         validation_data.get_message_field_validator_tokens(
           &mut field_validators,
           FieldKind::Single(FieldType::Message),
         );
       }
 
+      if !field_validators.is_empty() {
+        field_validators = field_mask_guard(&validation_data, field_validators);
+      }
+
       validators.insert(field_ident, field_validators);
     }
   }
@@ -222,14 +237,40 @@ pub fn extract_oneof_validators(
   })
 }
 
+#[derive(Debug)]
+pub struct MessageValidatorsOutput {
+  pub validators: TokenStream,
+  pub static_defs: TokenStream,
+  /// The functions that validate each field on its own, along with the `validate_field` function that dispatches to them.
+  pub field_validators: TokenStream,
+}
+
 pub fn extract_message_validators(
   input_tokens: &DeriveInput,
   message_desc: &MessageDescriptor,
-) -> Result<(TokenStream, TokenStream), Error> {
+  table_mode: bool,
+) -> Result<MessageValidatorsOutput, Error> {
   let mut validators: TokenStream = TokenStream::new();
   let mut static_defs: TokenStream = TokenStream::new();
+  let mut field_validators_methods: TokenStream = TokenStream::new();
+  let mut table_fields: Vec<TokenStream> = Vec::new();
+  // The arms of the match in validate_field, which call the function that validates each field
+  let mut field_validators_arms: Vec<TokenStream> = Vec::new();
+  // The index of the first table field that has not been validated yet
+  let mut table_run_start: usize = 0;
+
+  let struct_ident = &input_tokens.ident;
+  let table_ident = format_ident!(
+    "__{}_VALIDATION_TABLE",
+    message_desc
+      .full_name()
+      .to_case(Case::Snake)
+      .replace(".", "_")
+      .to_uppercase()
+  );
 
   let mut rust_field_spans: HashMap<String, Span2> = HashMap::new();
+  let mut rust_field_types: HashMap<String, syn::Type> = HashMap::new();
   let mut rust_enum_paths: HashMap<String, String> = HashMap::new();
 
   if let syn::Data::Struct(syn::DataStruct { fields, .. }) = &input_tokens.data {
@@ -256,6 +297,7 @@ pub fn extract_message_validators(
           }
         }
         rust_field_spans.insert(ident.to_string(), ident.span());
+        rust_field_types.insert(ident.to_string(), field.ty.clone());
       }
     }
   }
@@ -265,6 +307,7 @@ pub fn extract_message_validators(
 
   let violations_ident = format_ident!("violations");
   let parent_messages_ident = format_ident!("parent_messages");
+  let validation_context_ident = format_ident!("validation_context");
 
   // Message Rules
   if let ProstValue::Message(message_rules_msg) = message_rules_descriptor.as_ref() {
@@ -280,6 +323,7 @@ pub fn extract_message_validators(
       validators.extend(get_cel_rules_checked(
         &CelRuleTemplateTarget::Message {
           message_desc,
+          validation_context_ident: validation_context_ident.clone(),
           parent_messages_ident: parent_messages_ident.clone(),
           violations_ident: violations_ident.clone(),
         },
@@ -308,13 +352,37 @@ pub fn extract_message_validators(
       let oneof_proto_name = oneof.name();
       let item_rust_ident = proto_name_to_rust_ident(oneof_proto_name);
 
-      let required_check = oneof_rules.required().then_some(quote! {
-        #violations_ident.push(::protocheck::validators::oneofs::required(#oneof_proto_name, #parent_messages_ident.as_slice()));
+      // The fields of the oneof have their own mask guard, so this one only covers the required check,
+      // which applies when any of the fields of the oneof is in the mask
+      let required_check = oneof_rules.required().then(|| {
+        let push_violation = push_unfiltered_violation_tokens(
+          &validation_context_ident,
+          &violations_ident,
+          quote! { ::protocheck::validators::oneofs::required(#oneof_proto_name, #parent_messages_ident.as_slice()) },
+        );
+
+        let oneof_fields_names = oneof
+          .fields()
+          .map(|field| field.name().to_string());
+
+        quote! {
+          if #validation_context_ident.is_rule_enabled(::protocheck::context::RuleCategory::Standard, "oneof.required")
+            && (#(#validation_context_ident.is_field_in_mask(#parent_messages_ident.as_slice(), #oneof_fields_names))||*)
+          {
+            #push_violation
+          }
+        }
       });
 
       validators.extend(quote! {
         match &self.#item_rust_ident {
-          Some(oneof) => { oneof.validate(#parent_messages_ident, #violations_ident); },
+          Some(oneof) => {
+            oneof.validate(#validation_context_ident, #parent_messages_ident, #violations_ident);
+
+            if #validation_context_ident.should_stop(#violations_ident) {
+              return;
+            }
+          },
           None => { #required_check }
         };
       });
@@ -371,6 +439,20 @@ pub fn extract_message_validators(
         continue;
       }
 
+      let field_rust_type = rust_field_types
+        .get(field_rust_name.as_ref())
+        .ok_or_else(|| {
+          Error::new_spanned(
+            struct_ident,
+            format!(
+              "Could not find the field {} in struct {}",
+              field_rust_name, struct_ident
+            ),
+          )
+        })?;
+      let method_ident =
+        format_ident!("__validate_field_{}", field_proto_name.to_case(Case::Snake));
+
       let mut validation_data = ValidationData {
         proto_name: field_proto_name,
         tag: field_tag,
@@ -387,6 +469,7 @@ pub fn extract_message_validators(
         field_context_ident: &field_context_ident,
         item_ident: &item_ident,
         parent_messages_ident: &parent_messages_ident,
+        validation_context_ident: &validation_context_ident,
         map_key_ident: &key_ident,
         map_value_ident: &map_value_ident,
         index_ident: &index_ident,
@@ -396,8 +479,39 @@ pub fn extract_message_validators(
         map_value_context_ident: &map_value_context_ident,
         vec_item_context_ident: &vec_item_context_ident,
         value_ident: OnceCell::new(),
+        table_rules: None,
       };
 
+      if table_mode
+        && let Some(table_field) = get_table_field(
+          &mut static_defs,
+          &validation_data,
+          &field,
+          &field_rules,
+          field_rust_enum.clone(),
+          struct_ident,
+        )?
+      {
+        if !table_field.is_empty() {
+          let field_validators =
+            table_field_validator_tokens(&validation_data, &table_ident, table_fields.len());
+
+          field_validators_methods.extend(single_field_validator_tokens(
+            &method_ident,
+            field_rust_type,
+            &field_validators,
+          ));
+          field_validators_arms.push(single_field_dispatch_tokens(
+            struct_ident,
+            field_proto_name,
+            &method_ident,
+            field_rust_type,
+          ));
+          table_fields.push(table_field);
+        }
+        continue;
+      }
+
       let field_rules_type = field_rules.r#type.as_ref();
       if is_repeated {
         get_repeated_rules(
@@ -441,7 +555,7 @@ pub fn extract_message_validators(
           )?);
         }
 
-        if field_is_message(&field.kind()) {
+        if field_needs_validation(&field.kind()) {
           validation_data.get_message_field_validator_tokens(
             &mut field_validators,
             FieldKind::Single(FieldType::Message),
@@ -455,11 +569,200 @@ pub fn extract_message_validators(
         }
       }
 
-      validators.extend(field_validators);
+      if !field_validators.is_empty() {
+        let field_validators = field_mask_guard(&validation_data, field_validators);
+
+        // The table fields that come before this one are validated first, so that the violations are in the same order as in the inline mode
+        validators.extend(table_run_tokens(
+          &table_ident,
+          &mut table_run_start,
+          table_fields.len(),
+        ));
+
+        validators.extend(quote! {
+          Self::#method_ident(&self.#item_rust_ident, #validation_context_ident, #parent_messages_ident, #violations_ident);
+
+          if #validation_context_ident.should_stop(#violations_ident) {
+            return;
+          }
+        });
+
+        field_validators_methods.extend(single_field_validator_tokens(
+          &method_ident,
+          field_rust_type,
+          &field_validators,
+        ));
+        field_validators_arms.push(single_field_dispatch_tokens(
+          struct_ident,
+          field_proto_name,
+          &method_ident,
+          field_rust_type,
+        ));
+      }
+    }
+  }
+
+  if !table_fields.is_empty() {
+    validators.extend(table_run_tokens(
+      &table_ident,
+      &mut table_run_start,
+      table_fields.len(),
+    ));
+
+    static_defs.extend(quote! {
+      static #table_ident: &[::protocheck::table::TableField<#struct_ident>] = &[#(#table_fields),*];
+    });
+  }
+
+  field_validators_methods.extend(validate_field_tokens(struct_ident, &field_validators_arms));
+
+  Ok(MessageValidatorsOutput {
+    validators,
+    static_defs,
+    field_validators: field_validators_methods,
+  })
+}
+
+// Validates the table fields that were collected since the last run, if there are any
+fn table_run_tokens(table_ident: &Ident2, run_start: &mut usize, run_end: usize) -> TokenStream {
+  if *run_start == run_end {
+    return TokenStream::new();
+  }
+
+  let start = *run_start;
+  *run_start = run_end;
+
+  quote! {
+    ::protocheck::table::validate_table(
+      self,
+      &#table_ident[#start..#run_end],
+      validation_context,
+      parent_messages.as_slice(),
+      violations
+    );
+
+    if validation_context.should_stop(violations) {
+      return;
     }
   }
+}
 
-  Ok((validators, static_defs))
+// Generates the function that contains the validators for a single field, which receives the value of the field.
+// It is called by nested_validate with the value in the message, and by validate_field with a value on its own
+fn single_field_validator_tokens(
+  method_ident: &Ident2,
+  field_rust_type: &syn::Type,
+  field_validators: &TokenStream,
+) -> TokenStream {
+  quote! {
+    #[doc(hidden)]
+    #[inline]
+    fn #method_ident<'a>(
+      field_value: &'a #field_rust_type,
+      validation_context: &::protocheck::context::ValidationContext,
+      parent_messages: &mut Vec<::protocheck::field_data::PathElement<'a>>,
+      violations: &mut Vec<::protocheck::violations::ViolationRecord>
+    ) {
+      #field_validators
+    }
+  }
+}
+
+// The arm of the match in validate_field for a single field, which checks that the value has the type of the field
+fn single_field_dispatch_tokens(
+  struct_ident: &Ident,
+  field_proto_name: &str,
+  method_ident: &Ident2,
+  field_rust_type: &syn::Type,
+) -> TokenStream {
+  let enum_ident = field_enum_ident(struct_ident);
+  let variant_ident = field_variant_ident(field_proto_name);
+
+  quote! {
+    #enum_ident::#variant_ident => {
+      let Some(field_value) = value.downcast_ref::<#field_rust_type>() else {
+        panic!(
+          "Expected a value of type {} for the `{}` field, found {}",
+          ::core::any::type_name::<#field_rust_type>(),
+          #field_proto_name,
+          ::core::any::type_name::<V>()
+        );
+      };
+
+      Self::#method_ident(field_value, &validation_context, &mut parent_messages, &mut violations);
+    }
+  }
+}
+
+/// Generates the `validate_field` function of a message, which validates a value for one of its fields on its own.
+pub fn validate_field_tokens(
+  struct_ident: &Ident,
+  field_validators_arms: &[TokenStream],
+) -> TokenStream {
+  let enum_ident = field_enum_ident(struct_ident);
+
+  let body = if field_validators_arms.is_empty() {
+    quote! {
+      let _ = (field, value);
+
+      Ok(())
+    }
+  } else {
+    quote! {
+      let value: &dyn ::core::any::Any = value;
+      let validation_context = ::protocheck::context::ValidationContext::default();
+      let mut violations: Vec<::protocheck::violations::ViolationRecord> = Vec::new();
+      let mut parent_messages: Vec<::protocheck::field_data::PathElement> = Vec::new();
+
+      #[allow(unreachable_patterns)]
+      match field {
+        #(#field_validators_arms)*
+        _ => {}
+      }
+
+      if violations.is_empty() {
+        Ok(())
+      } else {
+        Err(::protocheck::violations::into_violations(violations))
+      }
+    }
+  };
+
+  quote! {
+    /// Validates a value for a single field on its own, running its standard and field-level Cel rules.
+    /// The paths of the violations are the same as if the field had been validated as part of the message.
+    ///
+    /// The fields that are part of a oneof and the fields without rules are not validated, so they always return `Ok(())`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field has rules and the value does not have its Rust type (i.e. `Option<String>` for an `optional string` field).
+    pub fn validate_field<V: ::core::any::Any>(
+      field: #enum_ident,
+      value: &V
+    ) -> Result<(), ::protocheck::types::protovalidate::Violations> {
+      #body
+    }
+  }
+}
+
+// Only validates the field if it is included in the field mask (when there is one)
+fn field_mask_guard(
+  validation_data: &ValidationData,
+  field_validators: TokenStream,
+) -> TokenStream {
+  let ValidationData {
+    validation_context_ident,
+    parent_messages_ident,
+    proto_name,
+    ..
+  } = validation_data;
+
+  quote! {
+    if #validation_context_ident.is_field_in_mask(#parent_messages_ident.as_slice(), #proto_name) {
+      #field_validators
+    }
+  }
 }
 
 pub fn field_is_boxed(field_desc: &FieldDescriptor, message_desc: &MessageDescriptor) -> bool {
@@ -469,6 +772,101 @@ pub fn field_is_boxed(field_desc: &FieldDescriptor, message_desc: &MessageDescri
   false
 }
 
+/// Returns `true` if the field is a message that has some rules defined in itself or in any of the messages that can be reached from it. Message fields that do not satisfy this condition can be skipped entirely during validation.
+pub fn field_needs_validation(field_kind: &Kind) -> bool {
+  if let Kind::Message(field_message_desc) = field_kind
+    && field_is_message(field_kind)
+  {
+    return message_has_rules(field_message_desc);
+  }
+  false
+}
+
+// The results are cached separately for each descriptor pool, since the same compiler process
+// can expand the macro with different pools that contain messages with the same names.
+// Holding a clone of each pool also keeps its identity from being reused by a different one.
+type MessagesWithRules = Vec<(DescriptorPool, HashMap<String, bool>)>;
+
+static MESSAGES_WITH_RULES: LazyLock<Mutex<MessagesWithRules>> =
+  LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Checks if a message, or any of the messages reachable from its fields (including lists, maps and oneofs), has any validation rules.
+pub fn message_has_rules(message_desc: &MessageDescriptor) -> bool {
+  let pool = message_desc.parent_pool();
+
+  if let Some(has_rules) = MESSAGES_WITH_RULES.lock().ok().and_then(|cache| {
+    cache
+      .iter()
+      .find(|(cached_pool, _)| cached_pool == pool)
+      .and_then(|(_, messages)| messages.get(message_desc.full_name()).copied())
+  }) {
+    return has_rules;
+  }
+
+  let mut visited: HashSet<String> = HashSet::new();
+  let mut to_visit: Vec<MessageDescriptor> = vec![message_desc.clone()];
+  let mut has_rules = false;
+
+  while let Some(current) = to_visit.pop() {
+    if !visited.insert(current.full_name().to_string()) {
+      continue;
+    }
+
+    if message_has_own_rules(&current) {
+      has_rules = true;
+      break;
+    }
+
+    for field in current.fields() {
+      let field_kind = if field.is_map() {
+        match field.kind() {
+          Kind::Message(map_entry_desc) => map_entry_desc.map_entry_value_field().kind(),
+          other => other,
+        }
+      } else {
+        field.kind()
+      };
+
+      if let Kind::Message(field_message_desc) = &field_kind
+        && field_is_message(&field_kind)
+      {
+        to_visit.push(field_message_desc.clone());
+      }
+    }
+  }
+
+  if let Ok(mut cache) = MESSAGES_WITH_RULES.lock() {
+    let full_name = message_desc.full_name().to_string();
+
+    if let Some((_, messages)) = cache
+      .iter_mut()
+      .find(|(cached_pool, _)| cached_pool == pool)
+    {
+      messages.insert(full_name, has_rules);
+    } else {
+      cache.push((pool.clone(), HashMap::from([(full_name, has_rules)])));
+    }
+  }
+
+  has_rules
+}
+
+fn message_has_own_rules(message_desc: &MessageDescriptor) -> bool {
+  message_desc
+    .options()
+    .has_extension(&MESSAGE_RULES_EXT_DESCRIPTOR)
+    || message_desc.oneofs().any(|oneof| {
+      oneof
+        .options()
+        .has_extension(&ONEOF_RULES_EXT_DESCRIPTOR)
+    })
+    || message_desc.fields().any(|field| {
+      field
+        .options()
+        .has_extension(&FIELD_RULES_EXT_DESCRIPTOR)
+    })
+}
+
 pub fn field_is_message(field_kind: &Kind) -> bool {
   if let Kind::Message(field_message_desc) = field_kind
     && !field_message_desc
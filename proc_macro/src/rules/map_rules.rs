@@ -7,7 +7,7 @@ use syn::Error;
 use super::{field_rules::Type as RulesType, Ignore};
 use crate::{
   cel_rule_template::CelRuleTemplateTarget,
-  extract_validators::field_is_message,
+  extract_validators::{field_is_message, field_needs_validation},
   rules::{
     cel_rules::get_cel_rules_checked,
     core::{convert_kind_to_proto_type, get_field_error, get_field_rules, get_field_type},
@@ -153,7 +153,7 @@ pub fn get_map_rules(
     }
   }
 
-  if value_is_message && !ignore_values_validators {
+  if field_needs_validation(&value_desc.kind()) && !ignore_values_validators {
     map_validation_data.get_message_field_validator_tokens(
       &mut values_rules,
       FieldKind::MapValue(FieldType::Message),
@@ -5,7 +5,6 @@ use protovalidate::{MessageRules, OneofRules, Rule};
 pub mod any_rules;
 pub mod bool_rules;
 pub mod bytes_rules;
-#[cfg(feature = "cel")]
 pub mod cel_rules;
 pub mod core;
 pub mod duration_rules;
@@ -15,4 +14,5 @@ pub mod map_rules;
 pub mod numeric_rules;
 pub mod repeated_rules;
 pub mod string_rules;
+pub mod table_rules;
 pub mod timestamp_rules;
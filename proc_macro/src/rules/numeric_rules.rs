@@ -7,10 +7,10 @@ use syn::Error;
 
 use crate::{
   rules::core::{get_field_error, invalid_lists_error},
-  validation_data::{ListRule, ValidationData},
+  validation_data::{ListRule, SortedListValues, ValidationData},
 };
 
-pub fn get_numeric_rules<HashableType, T: NumericRules<HashableType>>(
+pub fn get_numeric_rules<HashableType, T: NumericRules<HashableType> + SortedListValues>(
   validation_data: &ValidationData,
   rules: &T,
   static_defs: &mut TokenStream,
@@ -45,11 +45,23 @@ where
     .map_err(|invalid_items| invalid_lists_error(field_span, field_name, &invalid_items))?;
 
   if let Some(in_list) = in_list_rule {
-    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::In,
+      &mut tokens,
+      in_list,
+      rules.sorted_in(),
+      static_defs,
+    );
   };
 
   if let Some(not_in_list) = not_in_list_rule {
-    validation_data.get_list_validator(ListRule::NotIn, &mut tokens, not_in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::NotIn,
+      &mut tokens,
+      not_in_list,
+      rules.sorted_not_in(),
+      static_defs,
+    );
   }
 
   if rules.finite() {
@@ -60,10 +72,12 @@ where
       validation_data.field_kind.inner_type().name()
     );
 
+    let rule_id = format!("{}.finite", validation_data.field_kind.inner_type().name());
+
     let validator_expression_tokens = quote! {
       ::protocheck::validators::floats::#func_ident(&#field_context_ident, #value_ident)
     };
-    validation_data.get_validator_tokens(&mut tokens, &validator_expression_tokens);
+    validation_data.get_validator_tokens(&mut tokens, &rule_id, &validator_expression_tokens);
   }
 
   Ok(tokens)
@@ -8,7 +8,7 @@ use syn::Error;
 use super::{field_rules::Type as RulesType, protovalidate::Ignore};
 use crate::{
   cel_rule_template::CelRuleTemplateTarget,
-  extract_validators::field_is_message,
+  extract_validators::{field_is_message, field_needs_validation},
   rules::{
     cel_rules::get_cel_rules_checked,
     core::{get_field_error, get_field_rules},
@@ -34,6 +34,7 @@ pub fn get_repeated_rules(
   let item_is_message = field_is_message(&field_desc.kind());
 
   let mut ignore_items_validators = false;
+  let mut has_unique_rule = false;
 
   if !field_rules.cel.is_empty() {
     vec_level_rules.extend(get_cel_rules_checked(
@@ -56,12 +57,15 @@ pub fn get_repeated_rules(
         ));
       }
 
+      has_unique_rule = true;
+
       let items_validation_data =
         items_validation_data.get_or_insert_with(|| validation_data.to_repeated_item(field_desc));
 
       let field_context_ident = items_validation_data.field_context_ident();
       let value_ident = items_validation_data.value_ident();
-      let violations_ident = items_validation_data.violations_ident;
+      let rule_check = items_validation_data.rule_check_tokens("repeated.unique");
+      let push_violation = items_validation_data.push_checked_violation_tokens(quote! { v });
 
       vec_level_rules.extend(quote! {
         let mut processed_values = ::std::collections::HashSet::new();
@@ -75,12 +79,12 @@ pub fn get_repeated_rules(
       };
 
       items_rules.extend(quote! {
-        if !not_unique {
+        if !not_unique && #rule_check {
           match ::protocheck::validators::repeated::#func_name(&#field_context_ident, #value_ident, &mut processed_values) {
             Ok(_) => {},
             Err(v) => {
               not_unique = true;
-              #violations_ident.push(v);
+              #push_violation
             }
           };
         }
@@ -132,7 +136,7 @@ pub fn get_repeated_rules(
     }
   }
 
-  if item_is_message && !ignore_items_validators {
+  if field_needs_validation(&field_desc.kind()) && !ignore_items_validators {
     validation_data.get_message_field_validator_tokens(
       &mut items_rules,
       FieldKind::RepeatedItem(FieldType::Message),
@@ -144,6 +148,7 @@ pub fn get_repeated_rules(
     &RepeatedValidator {
       vec_level_rules,
       items_rules,
+      has_unique_rule,
     },
   );
 
@@ -7,7 +7,7 @@ use syn::Error;
 use super::protovalidate::StringRules;
 use crate::{
   rules::core::{get_field_error, invalid_lists_error},
-  validation_data::{ListRule, ValidationData},
+  validation_data::{ListRule, SortedListValues, ValidationData},
 };
 
 pub fn get_string_rules(
@@ -68,61 +68,85 @@ pub fn get_string_rules(
   }
 
   if let Some(in_list) = in_list_rule {
-    validation_data.get_list_validator(ListRule::In, &mut tokens, in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::In,
+      &mut tokens,
+      in_list,
+      rules.sorted_in(),
+      static_defs,
+    );
   }
 
   if let Some(not_in_list) = not_in_list_rule {
-    validation_data.get_list_validator(ListRule::NotIn, &mut tokens, not_in_list, static_defs);
+    validation_data.get_list_validator(
+      ListRule::NotIn,
+      &mut tokens,
+      not_in_list,
+      rules.sorted_not_in(),
+      static_defs,
+    );
   }
 
-  if let Some(well_known_kind) = rules.well_known {
-    let mut is_strict: Option<bool> = None;
-    let validator_path = match well_known_kind {
-      WellKnown::Email(enabled) => enabled.then_some(quote! { email }),
-      WellKnown::Hostname(enabled) => enabled.then_some(quote! { hostname }),
-      WellKnown::Ip(enabled) => enabled.then_some(quote! { ip }),
-      WellKnown::Ipv4(enabled) => enabled.then_some(quote! { ipv4 }),
-      WellKnown::Ipv6(enabled) => enabled.then_some(quote! { ipv6 }),
-      WellKnown::Uri(enabled) => enabled.then_some(quote! { uri }),
-      WellKnown::UriRef(enabled) => enabled.then_some(quote! { uri_ref }),
-      WellKnown::Address(enabled) => enabled.then_some(quote! { address }),
-      WellKnown::Uuid(enabled) => enabled.then_some(quote! { uuid }),
-      WellKnown::Tuuid(enabled) => enabled.then_some(quote! { tuuid }),
-      WellKnown::IpWithPrefixlen(enabled) => enabled.then_some(quote! { ip_with_prefixlen }),
-      WellKnown::Ipv4WithPrefixlen(enabled) => enabled.then_some(quote! { ipv4_with_prefixlen }),
-      WellKnown::Ipv6WithPrefixlen(enabled) => enabled.then_some(quote! { ipv6_with_prefixlen }),
-      WellKnown::IpPrefix(enabled) => enabled.then_some(quote! { ip_prefix }),
-      WellKnown::Ipv4Prefix(enabled) => enabled.then_some(quote! { ipv4_prefix }),
-      WellKnown::Ipv6Prefix(enabled) => enabled.then_some(quote! { ipv6_prefix }),
-      WellKnown::HostAndPort(enabled) => enabled.then_some(quote! { host_and_port }),
-      WellKnown::WellKnownRegex(well_known_regex) => {
-        if let Some(val) = rules.strict {
-          is_strict = Some(val)
-        } else {
-          is_strict = Some(true)
-        };
-
-        match well_known_regex {
-          1 => Some(quote! { header_name }),
-          2 => Some(quote! { header_value }),
-          _ => None,
-        }
-      }
+  if let Some((validator_func, is_strict)) = get_well_known_validator(rules) {
+    let field_context_ident = validation_data.field_context_ident();
+    let value_ident = validation_data.value_ident();
+
+    let strict_arg = is_strict.map(|bool| quote! { , #bool });
+
+    // The well known regexes (the only ones with the strict argument) have their own rule ids
+    let rule_id = if is_strict.is_some() {
+      format!("string.well_known_regex.{}", validator_func)
+    } else {
+      format!("string.{}", validator_func)
     };
 
-    if let Some(validator_func) = validator_path {
-      let field_context_ident = validation_data.field_context_ident();
-      let value_ident = validation_data.value_ident();
+    let validator_expression_tokens = quote! {
+      ::protocheck::validators::string::#validator_func(&#field_context_ident, &#value_ident #strict_arg)
+    };
 
-      let strict_arg = is_strict.map(|bool| quote! { , #bool });
+    validation_data.get_validator_tokens(&mut tokens, &rule_id, &validator_expression_tokens);
+  }
 
-      let validator_expression_tokens = quote! {
-        ::protocheck::validators::string::#validator_func(&#field_context_ident, &#value_ident #strict_arg)
+  Ok(tokens)
+}
+
+/// Returns the name of the validator function for the well known format that is set, if there is one, and the `strict` argument for the validators that accept it.
+fn get_well_known_validator(rules: &StringRules) -> Option<(TokenStream, Option<bool>)> {
+  let well_known_kind = rules.well_known?;
+  let mut is_strict: Option<bool> = None;
+
+  let validator_path = match well_known_kind {
+    WellKnown::Email(enabled) => enabled.then_some(quote! { email }),
+    WellKnown::Hostname(enabled) => enabled.then_some(quote! { hostname }),
+    WellKnown::Ip(enabled) => enabled.then_some(quote! { ip }),
+    WellKnown::Ipv4(enabled) => enabled.then_some(quote! { ipv4 }),
+    WellKnown::Ipv6(enabled) => enabled.then_some(quote! { ipv6 }),
+    WellKnown::Uri(enabled) => enabled.then_some(quote! { uri }),
+    WellKnown::UriRef(enabled) => enabled.then_some(quote! { uri_ref }),
+    WellKnown::Address(enabled) => enabled.then_some(quote! { address }),
+    WellKnown::Uuid(enabled) => enabled.then_some(quote! { uuid }),
+    WellKnown::Tuuid(enabled) => enabled.then_some(quote! { tuuid }),
+    WellKnown::IpWithPrefixlen(enabled) => enabled.then_some(quote! { ip_with_prefixlen }),
+    WellKnown::Ipv4WithPrefixlen(enabled) => enabled.then_some(quote! { ipv4_with_prefixlen }),
+    WellKnown::Ipv6WithPrefixlen(enabled) => enabled.then_some(quote! { ipv6_with_prefixlen }),
+    WellKnown::IpPrefix(enabled) => enabled.then_some(quote! { ip_prefix }),
+    WellKnown::Ipv4Prefix(enabled) => enabled.then_some(quote! { ipv4_prefix }),
+    WellKnown::Ipv6Prefix(enabled) => enabled.then_some(quote! { ipv6_prefix }),
+    WellKnown::HostAndPort(enabled) => enabled.then_some(quote! { host_and_port }),
+    WellKnown::WellKnownRegex(well_known_regex) => {
+      if let Some(val) = rules.strict {
+        is_strict = Some(val)
+      } else {
+        is_strict = Some(true)
       };
 
-      validation_data.get_validator_tokens(&mut tokens, &validator_expression_tokens);
+      match well_known_regex {
+        1 => Some(quote! { header_name }),
+        2 => Some(quote! { header_value }),
+        _ => None,
+      }
     }
-  }
+  };
 
-  Ok(tokens)
+  validator_path.map(|path| (path, is_strict))
 }
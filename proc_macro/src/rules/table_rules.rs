@@ -0,0 +1,189 @@
+use std::cell::{OnceCell, RefCell};
+
+use proc_macro2::TokenStream;
+use prost_reflect::{FieldDescriptor, Kind as ProstReflectKind};
+use proto_types::FieldType;
+use quote::{format_ident, quote};
+use syn::{Error, Ident};
+
+use super::{protovalidate::FieldRules, Ignore};
+use crate::{rules::core::get_field_rules, validation_data::ValidationData, Ident2};
+
+/// Collects the standard rules of a field as entries of a validation table.
+///
+/// When it is set in the [`ValidationData`], the validator expressions that would be expanded inline are wrapped in a function that receives the value from the table instead.
+#[derive(Debug)]
+pub struct TableRules {
+  value_variant: Ident2,
+  rules: RefCell<Vec<TokenStream>>,
+}
+
+impl TableRules {
+  pub fn push(
+    &self,
+    field_context_ident: &Ident2,
+    rule_id: &str,
+    validator_expression_tokens: &TokenStream,
+  ) {
+    let value_variant = &self.value_variant;
+
+    self.rules.borrow_mut().push(quote! {
+      ::protocheck::table::TableRule {
+        rule_id: #rule_id,
+        check: |#field_context_ident: &::protocheck::field_data::FieldContext, value: ::protocheck::table::TableValue| {
+          match value {
+            ::protocheck::table::TableValue::#value_variant(val) => #validator_expression_tokens,
+            _ => Ok(()),
+          }
+        },
+      }
+    });
+  }
+}
+
+/// Builds the entry of the validation table for a field, when using the table-driven mode.
+/// The rules are extracted with the same functions used for the inline validators. Returns `None` if the field or any of its rules cannot be described by a table entry, in which case the inline validators should be used.
+pub fn get_table_field(
+  static_defs: &mut TokenStream,
+  validation_data: &ValidationData,
+  field_desc: &FieldDescriptor,
+  field_rules: &FieldRules,
+  field_rust_enum: Option<String>,
+  struct_ident: &Ident,
+) -> Result<Option<TokenStream>, Error> {
+  if field_desc.is_list()
+    || field_desc.is_map()
+    || validation_data.is_boxed
+    || !field_rules.cel.is_empty()
+    || matches!(field_desc.kind(), ProstReflectKind::Message(_))
+  {
+    return Ok(None);
+  }
+
+  let field_type = validation_data.field_kind.inner_type();
+
+  let Some(value_variant) = table_variant(field_type) else {
+    return Ok(None);
+  };
+
+  let table_rules = TableRules {
+    value_variant: value_variant.clone(),
+    rules: RefCell::new(Vec::new()),
+  };
+
+  // The statics are only kept if the field ends up in the table, since the inline validators would define them again
+  let mut field_static_defs = TokenStream::new();
+
+  if let Some(rules_type) = field_rules.r#type.as_ref() {
+    let table_validation_data = ValidationData {
+      value_ident: OnceCell::from(quote! { val }),
+      table_rules: Some(&table_rules),
+      ..validation_data.clone()
+    };
+
+    let inline_tokens = get_field_rules(
+      &mut field_static_defs,
+      field_rust_enum,
+      field_desc,
+      &table_validation_data,
+      rules_type,
+    )?;
+
+    // Some rules can only be expanded inline
+    if !inline_tokens.is_empty() {
+      return Ok(None);
+    }
+  }
+
+  let rules = table_rules.rules.into_inner();
+
+  if rules.is_empty() && !validation_data.is_required {
+    return Ok(Some(TokenStream::new()));
+  }
+
+  static_defs.extend(field_static_defs);
+
+  let field_ident = validation_data.item_rust_ident;
+  let message_value = table_value_tokens(validation_data, &quote! { message.#field_ident });
+  let accessor = quote! { |message: &#struct_ident| #message_value };
+
+  let proto_name = validation_data.proto_name;
+  let tag = validation_data.tag;
+  let field_kind = validation_data.field_kind;
+  let is_required = validation_data.is_required;
+  let ignore_if_zero =
+    matches!(validation_data.ignore, Ignore::IfZeroValue) && !validation_data.is_option();
+
+  Ok(Some(quote! {
+    ::protocheck::table::TableField {
+      proto_name: #proto_name,
+      tag: #tag,
+      field_kind: #field_kind,
+      is_required: #is_required,
+      ignore_if_zero: #ignore_if_zero,
+      accessor: #accessor,
+      rules: &[#(#rules),*],
+    }
+  }))
+}
+
+/// Validates a value for a single field that is in the validation table, by running its entry with the `field_value` argument of the function that contains the validators of the field.
+pub fn table_field_validator_tokens(
+  validation_data: &ValidationData,
+  table_ident: &Ident2,
+  index: usize,
+) -> TokenStream {
+  let ValidationData {
+    validation_context_ident,
+    parent_messages_ident,
+    violations_ident,
+    ..
+  } = validation_data;
+  let value = table_value_tokens(validation_data, &validation_data.field_value_tokens());
+
+  quote! {
+    ::protocheck::table::validate_table_field(
+      &#table_ident[#index],
+      #value,
+      #validation_context_ident,
+      #parent_messages_ident.as_slice(),
+      #violations_ident
+    );
+  }
+}
+
+// Wraps the value of a field in a TableValue, which is `None` if an optional field is not set
+fn table_value_tokens(validation_data: &ValidationData, field_value: &TokenStream) -> TokenStream {
+  let field_type = validation_data.field_kind.inner_type();
+  let value_path = quote! { ::protocheck::table::TableValue };
+
+  let Some(value_variant) = table_variant(field_type) else {
+    return quote! {
+      compile_error!("Found a field without a table value type during macro expansion.")
+    };
+  };
+
+  match (validation_data.is_option(), field_type) {
+    (true, FieldType::String) => quote! { #field_value.as_deref().map(#value_path::String) },
+    (true, _) => quote! { #field_value.map(#value_path::#value_variant) },
+    (false, FieldType::String) => quote! { Some(#value_path::String(#field_value.as_str())) },
+    (false, _) => quote! { Some(#value_path::#value_variant(#field_value)) },
+  }
+}
+
+// The name of the variant of TableValue for the given type
+fn table_variant(field_type: FieldType) -> Option<Ident2> {
+  let name = match field_type {
+    FieldType::String => "String",
+    FieldType::Bool => "Bool",
+    FieldType::Float => "Float",
+    FieldType::Double => "Double",
+    FieldType::Int32 | FieldType::Sint32 | FieldType::Sfixed32 | FieldType::Enum => "Int32",
+    FieldType::Int64 | FieldType::Sint64 | FieldType::Sfixed64 => "Int64",
+    FieldType::Uint32 | FieldType::Fixed32 => "Uint32",
+    FieldType::Uint64 | FieldType::Fixed64 => "Uint64",
+    _ => return None,
+  };
+
+  Some(format_ident!("{}", name))
+}
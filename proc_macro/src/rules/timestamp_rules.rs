@@ -29,7 +29,11 @@ pub fn get_timestamp_rules(
     let validator_expression_tokens = quote! {
       ::protocheck::validators::timestamps::within(&#field_context_ident, #value_ident, #within_val, #error_message)
     };
-    validation_data.get_validator_tokens(&mut tokens, &validator_expression_tokens);
+    validation_data.get_validator_tokens(
+      &mut tokens,
+      "timestamp.within",
+      &validator_expression_tokens,
+    );
   }
 
   let TimestampComparableRules {
@@ -48,14 +52,22 @@ pub fn get_timestamp_rules(
     let validator_expression_tokens = quote! {
       ::protocheck::validators::timestamps::lt_now(&#field_context_ident, #value_ident)
     };
-    validation_data.get_validator_tokens(&mut tokens, &validator_expression_tokens);
+    validation_data.get_validator_tokens(
+      &mut tokens,
+      "timestamp.lt_now",
+      &validator_expression_tokens,
+    );
   }
 
   if gt_now {
     let validator_expression_tokens = quote! {
       ::protocheck::validators::timestamps::gt_now(&#field_context_ident, #value_ident)
     };
-    validation_data.get_validator_tokens(&mut tokens, &validator_expression_tokens);
+    validation_data.get_validator_tokens(
+      &mut tokens,
+      "timestamp.gt_now",
+      &validator_expression_tokens,
+    );
   }
 
   Ok(tokens)
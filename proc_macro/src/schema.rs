@@ -0,0 +1,449 @@
+use std::borrow::Cow;
+
+use proc_macro2::Literal;
+use prost_reflect::{
+  prost::Message, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, ReflectMessage,
+  Value as ProstValue,
+};
+use quote::quote;
+use syn::{DeriveInput, Error, GenericArgument, PathArguments, Type};
+
+use crate::{
+  pool_loader::{
+    FIELD_RULES_EXT_DESCRIPTOR, MESSAGE_RULES_EXT_DESCRIPTOR, ONEOF_RULES_EXT_DESCRIPTOR,
+  },
+  rules::{
+    extract_validators::message_has_rules,
+    protovalidate::{FieldRules, MessageRules, OneofRules, Rule},
+  },
+  special_field_names::proto_name_to_rust_name,
+  Span2, TokenStream2,
+};
+
+/// Generates the static [`MessageRulesInfo`](protocheck_core::schema::MessageRulesInfo) that describes the rules of a message, its fields and its oneofs.
+pub fn get_schema_tokens(
+  input_tokens: &DeriveInput,
+  message_desc: &MessageDescriptor,
+) -> Result<TokenStream2, Error> {
+  let full_name = message_desc.full_name();
+
+  let mut fields_tokens = Vec::new();
+
+  for field in message_desc.fields() {
+    let rules_msg = extension_message(
+      field
+        .options()
+        .get_extension(&FIELD_RULES_EXT_DESCRIPTOR),
+    );
+
+    let (item_kind, is_repeated) = if field.is_map() {
+      (map_entry_field(&field, false).map(|f| f.kind()), false)
+    } else {
+      (Some(field.kind()), field.is_list())
+    };
+
+    fields_tokens.push(field_info_tokens(
+      field.name(),
+      field.json_name(),
+      field.number(),
+      &item_kind.unwrap_or_else(|| field.kind()),
+      is_repeated,
+      field.is_map(),
+      rules_msg.as_ref(),
+      Some(&field),
+      message_schema_tokens(input_tokens, &field),
+    )?);
+  }
+
+  let mut oneofs_tokens = Vec::new();
+
+  for oneof in message_desc.oneofs() {
+    if oneof.is_synthetic() {
+      continue;
+    }
+
+    let required = match extension_message(
+      oneof
+        .options()
+        .get_extension(&ONEOF_RULES_EXT_DESCRIPTOR),
+    ) {
+      Some(rules_msg) => decode::<OneofRules>(&rules_msg, "oneof")?.required(),
+      None => false,
+    };
+
+    let oneof_name = oneof.name();
+    let fields_names = oneof
+      .fields()
+      .map(|field| field.name().to_string());
+
+    oneofs_tokens.push(quote! {
+      ::protocheck::schema::OneofRulesInfo {
+        name: #oneof_name,
+        required: #required,
+        fields: &[#(#fields_names),*],
+      }
+    });
+  }
+
+  let message_cel = match extension_message(
+    message_desc
+      .options()
+      .get_extension(&MESSAGE_RULES_EXT_DESCRIPTOR),
+  ) {
+    Some(rules_msg) => decode::<MessageRules>(&rules_msg, "message")?.cel,
+    None => Vec::new(),
+  };
+
+  let cel_tokens = cel_rules_tokens(&message_cel);
+
+  Ok(quote! {
+    ::protocheck::schema::MessageRulesInfo {
+      full_name: #full_name,
+      fields: &[#(#fields_tokens),*],
+      oneofs: &[#(#oneofs_tokens),*],
+      cel: #cel_tokens,
+    }
+  })
+}
+
+// Builds the info for a field, or for the items, keys or values of a repeated field or a map
+#[allow(clippy::too_many_arguments)]
+fn field_info_tokens(
+  name: &str,
+  json_name: &str,
+  tag: u32,
+  kind: &Kind,
+  is_repeated: bool,
+  is_map: bool,
+  rules_msg: Option<&DynamicMessage>,
+  field: Option<&FieldDescriptor>,
+  message_schema: TokenStream2,
+) -> Result<TokenStream2, Error> {
+  let field_type = kind_name(kind);
+
+  let mut required = false;
+  let mut cel_tokens = quote! { &[] };
+  let mut rules_tokens = Vec::new();
+  let mut items = None;
+  let mut keys = None;
+  let mut values = None;
+
+  if let Some(rules_msg) = rules_msg {
+    let field_rules = decode::<FieldRules>(rules_msg, "field")?;
+
+    required = field_rules.required();
+    cel_tokens = cel_rules_tokens(&field_rules.cel);
+
+    for (rules_field, value) in rules_msg.fields() {
+      // Only the fields in the `type` oneof (i.e. `string` or `int32`) contain the standard rules
+      if rules_field
+        .containing_oneof()
+        .is_none_or(|oneof| oneof.name() != "type")
+      {
+        continue;
+      }
+
+      let ProstValue::Message(type_rules) = value else {
+        continue;
+      };
+
+      let rules_type = rules_field.name();
+
+      for (rule_field, rule_value) in type_rules.fields() {
+        let rule_name = rule_field.name();
+
+        match (rules_type, rule_name, rule_value) {
+          ("repeated", "items", ProstValue::Message(items_rules)) => {
+            items = Some(field_info_tokens(
+              name,
+              json_name,
+              tag,
+              kind,
+              false,
+              false,
+              Some(items_rules),
+              None,
+              quote! { None },
+            )?);
+          }
+          ("map", "keys" | "values", ProstValue::Message(entry_rules)) => {
+            let is_key = rule_name == "keys";
+            let entry_kind = field
+              .and_then(|field| map_entry_field(field, is_key))
+              .map_or_else(|| kind.clone(), |entry_field| entry_field.kind());
+
+            let tokens = Some(field_info_tokens(
+              name,
+              json_name,
+              tag,
+              &entry_kind,
+              false,
+              false,
+              Some(entry_rules),
+              None,
+              quote! { None },
+            )?);
+
+            if is_key {
+              keys = tokens;
+            } else {
+              values = tokens;
+            }
+          }
+          _ => {
+            if let Some(value_tokens) = rule_value_tokens(rule_value) {
+              let rule_id = format!("{}.{}", rules_type, rule_name);
+
+              rules_tokens.push(quote! {
+                ::protocheck::schema::RuleInfo {
+                  id: #rule_id,
+                  value: #value_tokens,
+                }
+              });
+            }
+          }
+        }
+      }
+    }
+  }
+
+  let items = option_ref_tokens(items);
+  let keys = option_ref_tokens(keys);
+  let values = option_ref_tokens(values);
+
+  Ok(quote! {
+    ::protocheck::schema::FieldRulesInfo {
+      name: #name,
+      json_name: #json_name,
+      tag: #tag,
+      field_type: #field_type,
+      is_repeated: #is_repeated,
+      is_map: #is_map,
+      required: #required,
+      rules: &[#(#rules_tokens),*],
+      cel: #cel_tokens,
+      items: #items,
+      keys: #keys,
+      values: #values,
+      message: #message_schema,
+    }
+  })
+}
+
+// Links the schema of the message type of a field (or of its items or values) when that message has rules, since it then has the generated `ValidationSchema` impl as well. The fields in a oneof are not part of the struct, so they are not linked.
+fn message_schema_tokens(input_tokens: &DeriveInput, field: &FieldDescriptor) -> TokenStream2 {
+  let kind = if field.is_map() {
+    map_entry_field(field, false).map(|value_field| value_field.kind())
+  } else {
+    Some(field.kind())
+  };
+
+  let Some(Kind::Message(field_message_desc)) = kind else {
+    return quote! { None };
+  };
+
+  if !message_has_rules(&field_message_desc) {
+    return quote! { None };
+  }
+
+  let rust_name = proto_name_to_rust_name(field.name());
+
+  let syn::Data::Struct(data) = &input_tokens.data else {
+    return quote! { None };
+  };
+
+  let Some(rust_type) = data
+    .fields
+    .iter()
+    .find(|rust_field| {
+      rust_field
+        .ident
+        .as_ref()
+        .is_some_and(|ident| *ident == rust_name.as_ref())
+    })
+    .map(|rust_field| message_type(&rust_field.ty))
+  else {
+    return quote! { None };
+  };
+
+  quote! { Some(<#rust_type as ::protocheck::ValidationSchema>::validation_schema) }
+}
+
+// Unwraps the containers used by prost for message fields, such as `Option<Box<T>>`, `Vec<T>` or `HashMap<K, T>`
+fn message_type(rust_type: &Type) -> &Type {
+  let Type::Path(type_path) = rust_type else {
+    return rust_type;
+  };
+
+  let Some(segment) = type_path.path.segments.last() else {
+    return rust_type;
+  };
+
+  if !matches!(
+    segment.ident.to_string().as_str(),
+    "Option" | "Box" | "Vec" | "HashMap" | "BTreeMap"
+  ) {
+    return rust_type;
+  }
+
+  let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+    return rust_type;
+  };
+
+  // The message is the last type argument, which is the value for maps
+  let inner = arguments
+    .args
+    .iter()
+    .rev()
+    .find_map(|argument| match argument {
+      GenericArgument::Type(inner) => Some(inner),
+      _ => None,
+    });
+
+  inner.map_or(rust_type, message_type)
+}
+
+// Converts the value of a standard rule. Returns None for values that cannot be represented, such as nested messages
+fn rule_value_tokens(value: &ProstValue) -> Option<TokenStream2> {
+  let tokens = match value {
+    ProstValue::Bool(v) => quote! { ::protocheck::schema::RuleValue::Bool(#v) },
+    ProstValue::I32(v) => {
+      let v = i64::from(*v);
+      quote! { ::protocheck::schema::RuleValue::Int(#v) }
+    }
+    ProstValue::I64(v) => quote! { ::protocheck::schema::RuleValue::Int(#v) },
+    ProstValue::EnumNumber(v) => {
+      let v = i64::from(*v);
+      quote! { ::protocheck::schema::RuleValue::Int(#v) }
+    }
+    ProstValue::U32(v) => {
+      let v = u64::from(*v);
+      quote! { ::protocheck::schema::RuleValue::Uint(#v) }
+    }
+    ProstValue::U64(v) => quote! { ::protocheck::schema::RuleValue::Uint(#v) },
+    ProstValue::F32(v) => {
+      let v = f64_tokens(f64::from(*v));
+      quote! { ::protocheck::schema::RuleValue::Double(#v) }
+    }
+    ProstValue::F64(v) => {
+      let v = f64_tokens(*v);
+      quote! { ::protocheck::schema::RuleValue::Double(#v) }
+    }
+    ProstValue::String(v) => quote! { ::protocheck::schema::RuleValue::String(#v) },
+    ProstValue::Bytes(v) => {
+      let v = Literal::byte_string(v);
+      quote! { ::protocheck::schema::RuleValue::Bytes(#v) }
+    }
+    ProstValue::List(list) => {
+      let items = list.iter().filter_map(rule_value_tokens);
+      quote! { ::protocheck::schema::RuleValue::List(&[#(#items),*]) }
+    }
+    ProstValue::Message(msg) => {
+      let variant = match msg.descriptor().full_name() {
+        "google.protobuf.Duration" => quote! { Duration },
+        "google.protobuf.Timestamp" => quote! { Timestamp },
+        _ => return None,
+      };
+
+      let seconds = match msg.get_field_by_name("seconds").as_deref() {
+        Some(ProstValue::I64(seconds)) => *seconds,
+        _ => 0,
+      };
+      let nanos = match msg.get_field_by_name("nanos").as_deref() {
+        Some(ProstValue::I32(nanos)) => *nanos,
+        _ => 0,
+      };
+
+      quote! { ::protocheck::schema::RuleValue::#variant { seconds: #seconds, nanos: #nanos } }
+    }
+    ProstValue::Map(_) => return None,
+  };
+
+  Some(tokens)
+}
+
+fn cel_rules_tokens(rules: &[Rule]) -> TokenStream2 {
+  let rules = rules.iter().map(|rule| {
+    let id = rule.id();
+    let message = rule.message();
+    let expression = rule.expression();
+
+    quote! {
+      ::protocheck::schema::CelRuleInfo {
+        id: #id,
+        message: #message,
+        expression: #expression,
+      }
+    }
+  });
+
+  quote! { &[#(#rules),*] }
+}
+
+fn option_ref_tokens(tokens: Option<TokenStream2>) -> TokenStream2 {
+  match tokens {
+    Some(tokens) => quote! { Some(&#tokens) },
+    None => quote! { None },
+  }
+}
+
+// Non-finite values cannot be turned into literals, so they must use the constants
+fn f64_tokens(value: f64) -> TokenStream2 {
+  if value.is_nan() {
+    quote! { f64::NAN }
+  } else if value == f64::INFINITY {
+    quote! { f64::INFINITY }
+  } else if value == f64::NEG_INFINITY {
+    quote! { f64::NEG_INFINITY }
+  } else {
+    quote! { #value }
+  }
+}
+
+fn map_entry_field(field: &FieldDescriptor, key: bool) -> Option<FieldDescriptor> {
+  match field.kind() {
+    Kind::Message(entry_desc) if key => Some(entry_desc.map_entry_key_field()),
+    Kind::Message(entry_desc) => Some(entry_desc.map_entry_value_field()),
+    _ => None,
+  }
+}
+
+fn kind_name(kind: &Kind) -> &'static str {
+  match kind {
+    Kind::Double => "double",
+    Kind::Float => "float",
+    Kind::Int32 => "int32",
+    Kind::Int64 => "int64",
+    Kind::Uint32 => "uint32",
+    Kind::Uint64 => "uint64",
+    Kind::Sint32 => "sint32",
+    Kind::Sint64 => "sint64",
+    Kind::Fixed32 => "fixed32",
+    Kind::Fixed64 => "fixed64",
+    Kind::Sfixed32 => "sfixed32",
+    Kind::Sfixed64 => "sfixed64",
+    Kind::Bool => "bool",
+    Kind::String => "string",
+    Kind::Bytes => "bytes",
+    Kind::Message(_) => "message",
+    Kind::Enum(_) => "enum",
+  }
+}
+
+/// Returns the message of a rules extension (such as `(buf.validate.field)`), if it is set.
+pub fn extension_message(value: Cow<'_, ProstValue>) -> Option<DynamicMessage> {
+  match value.as_ref() {
+    ProstValue::Message(msg) => Some(msg.clone()),
+    _ => None,
+  }
+}
+
+/// Decodes the message of a rules extension into its generated type.
+pub fn decode<T: Message + Default>(msg: &DynamicMessage, rules_kind: &str) -> Result<T, Error> {
+  T::decode(msg.encode_to_vec().as_slice()).map_err(|e| {
+    Error::new(
+      Span2::call_site(),
+      format!("Could not decode {} rules: {}", rules_kind, e),
+    )
+  })
+}
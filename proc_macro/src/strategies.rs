@@ -0,0 +1,1147 @@
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::Literal;
+use prost_reflect::{
+  prost::Message, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, Value as ProstValue,
+};
+use quote::quote;
+use regex_syntax::hir::{Hir, HirKind, Look};
+use syn::{DeriveInput, Error, GenericArgument, PathArguments, Type};
+
+use crate::{
+  example::{
+    duration_nanos, field_rules, float_value, int_value, nested_field_rules, set_rule, type_rules,
+    NumericRules, NANOS_PER_SECOND, WELL_KNOWN_STRINGS,
+  },
+  field_enum::{field_enum_ident, field_variant_ident, message_type_path},
+  pool_loader::ONEOF_RULES_EXT_DESCRIPTOR,
+  rules::protovalidate::{FieldRules, Ignore, OneofRules},
+  schema::{decode, extension_message},
+  special_field_names::proto_name_to_rust_ident,
+  TokenStream2,
+};
+
+// The default size limits for lists, maps, strings and bytes without rules
+const DEFAULT_MAX_ITEMS: usize = 4;
+const DEFAULT_MAX_LEN: usize = 16;
+
+// The depth up to which recursive fields are generated. Required recursive fields can go twice as deep, so that the recursion stops at an optional field whenever there is one
+const MAX_RECURSION_DEPTH: u32 = 3;
+
+// The default ranges for durations and timestamps without rules, in seconds (around 10 thousand years, and the years 1970 to 9999)
+const DURATION_SECONDS_RANGE: (i64, i64) = (-315_576_000_000, 315_576_000_000);
+const TIMESTAMP_SECONDS_RANGE: (i64, i64) = (0, 253_402_300_799);
+
+/// Generates the [`Arbitrary`](proptest::arbitrary::Arbitrary) implementation of a message, which produces instances that satisfy its rules, along with the `violate_rule` and `violating_strategy` helpers.
+///
+/// The strategies for each field are derived from its rules (ranges, lengths, `in` lists, patterns and so on), and the resulting messages are filtered with `is_valid`, so that the rules which cannot be expressed as a strategy (such as Cel rules) are still satisfied.
+pub fn get_strategy_tokens(
+  input_tokens: &DeriveInput,
+  message_desc: &MessageDescriptor,
+) -> Result<TokenStream2, Error> {
+  let struct_ident = &input_tokens.ident;
+  let field_enum_ident = field_enum_ident(struct_ident);
+  let proptest = quote! { ::protocheck::proptest };
+
+  let error = |message: String| {
+    Error::new_spanned(
+      struct_ident,
+      format!(
+        "Could not generate the proptest strategy for {}: {}",
+        message_desc.full_name(),
+        message
+      ),
+    )
+  };
+
+  let mut rust_field_types: HashMap<String, &Type> = HashMap::new();
+
+  if let syn::Data::Struct(syn::DataStruct { fields, .. }) = &input_tokens.data {
+    for field in fields {
+      if let Some(ident) = &field.ident {
+        rust_field_types.insert(ident.to_string(), &field.ty);
+      }
+    }
+  }
+
+  let rust_type = |proto_name: &str| {
+    rust_field_types
+      .get(
+        proto_name_to_rust_ident(proto_name)
+          .to_string()
+          .as_str(),
+      )
+      .copied()
+      .ok_or_else(|| error(format!("could not find the rust type of `{}`", proto_name)))
+  };
+
+  let mut strategies = Vec::new();
+  let mut idents = Vec::new();
+  let mut violation_arms = TokenStream2::new();
+  let mut has_recursive_fields = false;
+
+  for field in message_desc.fields() {
+    if field
+      .containing_oneof()
+      .is_some_and(|oneof| !oneof.is_synthetic())
+    {
+      continue;
+    }
+
+    let field_type = rust_type(field.name())?;
+    let field_ident = proto_name_to_rust_ident(field.name());
+    has_recursive_fields |= is_recursive(&field, message_desc) || is_boxed_type(field_type);
+
+    strategies.push(
+      field_strategy(&field, message_desc, field_type)
+        .map_err(|e| error(format!("field `{}` {}", field.name(), e)))?,
+    );
+
+    let variant_ident = field_variant_ident(field.name());
+
+    for (rule_id, value) in field_violations(&field) {
+      violation_arms.extend(quote! {
+        (#field_enum_ident::#variant_ident, #rule_id) => {
+          self.#field_ident = #value;
+          true
+        }
+      });
+    }
+
+    idents.push(field_ident);
+  }
+
+  for oneof in message_desc.oneofs() {
+    if oneof.is_synthetic() {
+      continue;
+    }
+
+    let is_required = extension_message(
+      oneof
+        .options()
+        .get_extension(&ONEOF_RULES_EXT_DESCRIPTOR),
+    )
+    .map(|rules| decode::<OneofRules>(&rules, "oneof"))
+    .transpose()?
+    .is_some_and(|rules| rules.required());
+
+    let oneof_type = rust_type(oneof.name())?;
+    let oneof_path = message_type_path(oneof_type).ok_or_else(|| {
+      error(format!(
+        "could not find the rust type of `{}`",
+        oneof.name()
+      ))
+    })?;
+
+    let mut variants = Vec::new();
+
+    // The rust types of the variants are not known here, so the message variants are skipped, which also avoids infinitely deep strategies for the recursive ones
+    for field in oneof.fields() {
+      let Ok(strategy) = single_strategy(&field, field_rules(&field).as_ref(), None, false) else {
+        continue;
+      };
+
+      let variant_ident = field_variant_ident(field.name());
+
+      variants.push(quote! { #strategy.prop_map(#oneof_path::#variant_ident).boxed() });
+    }
+
+    let strategy = if variants.is_empty() {
+      quote! { #proptest::strategy::Just(<#oneof_type as ::core::default::Default>::default()) }
+    } else if is_required {
+      quote! { #proptest::strategy::Union::new(::std::vec![#(#variants),*]).prop_map(Some) }
+    } else {
+      quote! { #proptest::option::of(#proptest::strategy::Union::new(::std::vec![#(#variants),*])) }
+    };
+
+    strategies.push(strategy);
+    idents.push(proto_name_to_rust_ident(oneof.name()));
+  }
+
+  // Proptest only implements Strategy for tuples of up to 12 elements, so the strategies are nested in pairs
+  let mut tuple_strategy = quote! { #proptest::strategy::Just(()) };
+  let mut tuple_pattern = quote! { () };
+
+  for (strategy, ident) in strategies.iter().zip(&idents).rev() {
+    tuple_strategy = quote! { (#strategy, #tuple_strategy) };
+    tuple_pattern = quote! { (#ident, #tuple_pattern) };
+  }
+
+  // The parameter is the depth of the message, which limits the recursive fields
+  let depth_param = if has_recursive_fields {
+    quote! { depth }
+  } else {
+    quote! { _ }
+  };
+
+  Ok(quote! {
+    impl #proptest::arbitrary::Arbitrary for #struct_ident {
+      type Parameters = u32;
+      type Strategy = #proptest::strategy::BoxedStrategy<Self>;
+
+      fn arbitrary_with(#depth_param: Self::Parameters) -> Self::Strategy {
+        use #proptest::strategy::Strategy;
+
+        #tuple_strategy
+          .prop_map(|#tuple_pattern| Self { #(#idents),* })
+          .prop_filter("must satisfy the validation rules", |message| message.is_valid())
+          .boxed()
+      }
+    }
+
+    impl #struct_ident {
+      /// Changes the value of a field so that it violates the given rule (i.e. `string.min_len`), based on the value of the rule itself. Returns `false` if the rule is not defined for this field, or if it cannot be violated automatically (as with patterns or Cel rules).
+      pub fn violate_rule(&mut self, field: #field_enum_ident, rule_id: &str) -> bool {
+        match (field, rule_id) {
+          #violation_arms
+          _ => false,
+        }
+      }
+
+      /// Returns a strategy for instances of this message that only violate the given rule, using [`violate_rule`](Self::violate_rule) on valid instances.
+      ///
+      /// # Panics
+      ///
+      /// Panics if the rule cannot be violated automatically, which is when [`violate_rule`](Self::violate_rule) returns `false`.
+      pub fn violating_strategy(
+        field: #field_enum_ident,
+        rule_id: &'static str
+      ) -> #proptest::strategy::BoxedStrategy<Self> {
+        use #proptest::strategy::Strategy;
+
+        assert!(
+          <Self as ::core::default::Default>::default().violate_rule(field, rule_id),
+          "The rule {} cannot be violated automatically for the {:?} field",
+          rule_id,
+          field
+        );
+
+        #proptest::arbitrary::any::<Self>()
+          .prop_filter_map("must violate the rule", move |mut message| {
+            message.violate_rule(field, rule_id).then_some(message)
+          })
+          .boxed()
+      }
+    }
+  })
+}
+
+fn field_strategy(
+  field: &FieldDescriptor,
+  message_desc: &MessageDescriptor,
+  rust_type: &Type,
+) -> Result<TokenStream2, String> {
+  let proptest = quote! { ::protocheck::proptest };
+
+  let mut rules = field_rules(field);
+  let mut is_required = false;
+
+  if let Some(field_rules) = &rules {
+    let decoded_rules = FieldRules::decode(field_rules.encode_to_vec().as_slice())
+      .map_err(|e| format!("has rules that could not be decoded: {}", e))?;
+
+    if matches!(decoded_rules.ignore(), Ignore::Always) {
+      rules = None;
+    } else {
+      is_required = decoded_rules.required();
+    }
+  }
+
+  let is_boxed = is_boxed_type(rust_type);
+  let recursive = is_boxed || is_recursive(field, message_desc);
+
+  let strategy = field_value_strategy(
+    field,
+    rules.as_ref(),
+    rust_type,
+    is_required,
+    recursive,
+    is_boxed,
+  )?;
+
+  if !recursive {
+    return Ok(strategy);
+  }
+
+  // Recursive fields are left empty past the maximum depth, as their strategies would be infinitely deep
+  let max_depth = if is_required {
+    MAX_RECURSION_DEPTH * 2
+  } else {
+    MAX_RECURSION_DEPTH
+  };
+
+  Ok(quote! {
+    if depth < #max_depth {
+      #proptest::strategy::Strategy::boxed(#strategy)
+    } else {
+      #proptest::strategy::Strategy::boxed(
+        #proptest::strategy::Just(<#rust_type as ::core::default::Default>::default())
+      )
+    }
+  })
+}
+
+// The strategy for the value of a field. The messages of recursive fields are generated one level deeper than the current one
+fn field_value_strategy(
+  field: &FieldDescriptor,
+  rules: Option<&DynamicMessage>,
+  rust_type: &Type,
+  is_required: bool,
+  recursive: bool,
+  is_boxed: bool,
+) -> Result<TokenStream2, String> {
+  let proptest = quote! { ::protocheck::proptest };
+
+  let type_rules = rules.and_then(type_rules);
+  let list_rules = type_rules
+    .as_ref()
+    .filter(|(rules_type, _)| rules_type == "repeated" || rules_type == "map")
+    .map(|(_, type_rules)| type_rules);
+
+  if field.is_map() {
+    let Kind::Message(entry_desc) = field.kind() else {
+      return Err("is a map without an entry message".to_string());
+    };
+
+    let (min_pairs, max_pairs) = size_range(
+      list_rules,
+      "min_pairs",
+      "max_pairs",
+      None,
+      DEFAULT_MAX_ITEMS,
+    );
+    let key = single_strategy(
+      &entry_desc.map_entry_key_field(),
+      list_rules
+        .and_then(|rules| nested_field_rules(rules, "keys"))
+        .as_ref(),
+      Some(rust_type),
+      false,
+    )?;
+    let value = single_strategy(
+      &entry_desc.map_entry_value_field(),
+      list_rules
+        .and_then(|rules| nested_field_rules(rules, "values"))
+        .as_ref(),
+      Some(rust_type),
+      recursive,
+    )?;
+
+    return Ok(quote! {
+      #proptest::collection::vec((#key, #value), #min_pairs..=#max_pairs)
+        .prop_map(|entries| entries.into_iter().collect::<#rust_type>())
+    });
+  }
+
+  if field.is_list() {
+    let (min_items, max_items) = size_range(
+      list_rules,
+      "min_items",
+      "max_items",
+      None,
+      DEFAULT_MAX_ITEMS,
+    );
+    let item = single_strategy(
+      field,
+      list_rules
+        .and_then(|rules| nested_field_rules(rules, "items"))
+        .as_ref(),
+      Some(rust_type),
+      recursive,
+    )?;
+
+    return Ok(quote! { #proptest::collection::vec(#item, #min_items..=#max_items) });
+  }
+
+  let mut value = single_strategy(field, rules, Some(rust_type), recursive)?;
+
+  if is_boxed {
+    value = quote! { #value.prop_map(::std::boxed::Box::new) };
+  }
+
+  if !field.supports_presence() {
+    Ok(value)
+  } else if is_required {
+    Ok(quote! { #value.prop_map(Some) })
+  } else {
+    Ok(quote! { #proptest::option::of(#value) })
+  }
+}
+
+// Returns the strategy for a single value, list item, map key or map value. The rust type of the field is used to find the path of the nested messages
+fn single_strategy(
+  field: &FieldDescriptor,
+  rules: Option<&DynamicMessage>,
+  rust_type: Option<&Type>,
+  recursive: bool,
+) -> Result<TokenStream2, String> {
+  let proptest = quote! { ::protocheck::proptest };
+
+  let type_rules = rules
+    .and_then(type_rules)
+    .map(|(_, type_rules)| type_rules);
+  let type_rules = type_rules.as_ref();
+
+  let kind = field.kind();
+
+  let tokens = match &kind {
+    Kind::Message(message_desc) => match message_desc.full_name() {
+      "google.protobuf.Duration" | "google.protobuf.Timestamp" => {
+        let is_timestamp = message_desc.full_name() == "google.protobuf.Timestamp";
+        let type_path = if is_timestamp {
+          quote! { ::protocheck::types::Timestamp }
+        } else {
+          quote! { ::protocheck::types::Duration }
+        };
+
+        let rules = NumericRules::new(type_rules, duration_nanos);
+        let (default_min, default_max) = if is_timestamp {
+          TIMESTAMP_SECONDS_RANGE
+        } else {
+          DURATION_SECONDS_RANGE
+        };
+
+        let to_tokens = |nanos: i128| {
+          let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).unwrap_or_default();
+          let nanos = i32::try_from(nanos.rem_euclid(NANOS_PER_SECOND)).unwrap_or_default();
+
+          quote! { #type_path { seconds: #seconds, nanos: #nanos } }
+        };
+
+        if let Some(values) = explicit_values(&rules) {
+          let values = values.into_iter().map(to_tokens);
+
+          sample_tokens(values.collect())
+        } else {
+          // Only whole seconds are generated, so the bounds are rounded towards the inside of the range
+          let min = rules
+            .gt
+            .map(|gt| gt + 1)
+            .or(rules.gte)
+            .map_or(default_min, |min| {
+              i64::try_from((min + NANOS_PER_SECOND - 1).div_euclid(NANOS_PER_SECOND))
+                .unwrap_or(default_min)
+            });
+          let max = rules
+            .lt
+            .map(|lt| lt - 1)
+            .or(rules.lte)
+            .map_or(default_max, |max| {
+              i64::try_from(max.div_euclid(NANOS_PER_SECOND)).unwrap_or(default_max)
+            });
+
+          let (min, max) = if min <= max {
+            (min, max)
+          } else {
+            (default_min, default_max)
+          };
+
+          quote! {
+            (#min..=#max).prop_map(|seconds: i64| #type_path { seconds, nanos: 0 })
+          }
+        }
+      }
+      _ => {
+        let message_path = rust_type
+          .and_then(message_type_path)
+          .ok_or_else(|| "is a message whose rust type could not be found".to_string())?;
+
+        // The well known types and the common types from proto_types do not implement Arbitrary, so they are left empty
+        if message_desc.full_name().starts_with("google.") {
+          quote! { #proptest::strategy::Just(<#message_path as ::core::default::Default>::default()) }
+        } else if recursive {
+          quote! { #proptest::arbitrary::any_with::<#message_path>(depth + 1) }
+        } else {
+          quote! { #proptest::arbitrary::any::<#message_path>() }
+        }
+      }
+    },
+    Kind::Bool => match type_rules.and_then(|rules| set_rule(rules, "const")) {
+      Some(ProstValue::Bool(value)) => quote! { #proptest::strategy::Just(#value) },
+      _ => quote! { #proptest::arbitrary::any::<bool>() },
+    },
+    Kind::String => string_strategy(type_rules)?,
+    Kind::Bytes => {
+      let strategy = bytes_strategy(type_rules);
+
+      quote! {
+        #strategy.prop_map(|bytes: ::std::vec::Vec<u8>| ::core::convert::From::from(bytes))
+      }
+    }
+    Kind::Enum(enum_desc) => {
+      let rules = NumericRules::new(type_rules, int_value);
+      let defined_only = type_rules
+        .and_then(|rules| set_rule(rules, "defined_only"))
+        .is_some_and(|defined_only| defined_only == ProstValue::Bool(true));
+
+      if let Some(values) = explicit_values(&rules) {
+        sample_tokens(
+          values
+            .into_iter()
+            .map(|v| int_literal(&kind, v))
+            .collect(),
+        )
+      } else if defined_only {
+        let defined_values: HashSet<i32> = enum_desc
+          .values()
+          .map(|value| value.number())
+          .collect();
+        let mut defined_values: Vec<i32> = defined_values.into_iter().collect();
+        defined_values.sort_unstable();
+
+        sample_tokens(
+          defined_values
+            .into_iter()
+            .map(|value| quote! { #value })
+            .collect(),
+        )
+      } else {
+        quote! { #proptest::arbitrary::any::<i32>() }
+      }
+    }
+    Kind::Float | Kind::Double => {
+      let rules = NumericRules::new(type_rules, float_value);
+      let is_float = matches!(kind, Kind::Float);
+      let literal = |value: f64| float_literal(value, is_float);
+
+      if let Some(values) = explicit_values(&rules) {
+        sample_tokens(
+          values
+            .into_iter()
+            .map(|value| {
+              let value = literal(value);
+              quote! { #value }
+            })
+            .collect(),
+        )
+      } else {
+        let min = rules.gt.or(rules.gte);
+        let max = rules.lt.or(rules.lte);
+
+        // The ranges are inclusive, and the exclusive bounds are enforced by the final filter
+        let range = match (min, max) {
+          (Some(min), Some(max)) if min <= max => Some((min, max)),
+          (Some(min), None) => Some((min, min + 1_000_000.0)),
+          (None, Some(max)) => Some((max - 1_000_000.0, max)),
+          _ => None,
+        };
+
+        match range {
+          Some((min, max)) => {
+            let min = literal(min);
+            let max = literal(max);
+
+            quote! { (#min..=#max) }
+          }
+          None if is_float => quote! { #proptest::num::f32::NORMAL | #proptest::num::f32::ZERO },
+          None => quote! { #proptest::num::f64::NORMAL | #proptest::num::f64::ZERO },
+        }
+      }
+    }
+    _ => {
+      let rules = NumericRules::new(type_rules, int_value);
+      let (type_min, type_max) = int_bounds(&kind);
+
+      if let Some(values) = explicit_values(&rules) {
+        sample_tokens(
+          values
+            .into_iter()
+            .map(|v| int_literal(&kind, v))
+            .collect(),
+        )
+      } else {
+        let min = rules
+          .gt
+          .map(|gt| gt + 1)
+          .or(rules.gte)
+          .unwrap_or(type_min);
+        let max = rules
+          .lt
+          .map(|lt| lt - 1)
+          .or(rules.lte)
+          .unwrap_or(type_max);
+
+        let (min, max) = if min <= max {
+          (min, max)
+        } else {
+          (type_min, type_max)
+        };
+
+        let min = int_literal(&kind, min.max(type_min));
+        let max = int_literal(&kind, max.min(type_max));
+
+        quote! { (#min..=#max) }
+      }
+    }
+  };
+
+  Ok(tokens)
+}
+
+fn string_strategy(rules: Option<&DynamicMessage>) -> Result<TokenStream2, String> {
+  let proptest = quote! { ::protocheck::proptest };
+
+  let string_rule = |name: &str| match rules.and_then(|rules| set_rule(rules, name)) {
+    Some(ProstValue::String(value)) => Some(value),
+    _ => None,
+  };
+  let string_list = |name: &str| match rules.and_then(|rules| set_rule(rules, name)) {
+    Some(ProstValue::List(values)) => values
+      .iter()
+      .filter_map(|value| value.as_str().map(str::to_string))
+      .collect(),
+    _ => Vec::new(),
+  };
+  let to_tokens = |value: String| quote! { ::std::string::String::from(#value) };
+
+  if let Some(const_value) = string_rule("const") {
+    return Ok(sample_tokens(vec![to_tokens(const_value)]));
+  }
+
+  let in_list: Vec<String> = string_list("in");
+
+  if !in_list.is_empty() {
+    return Ok(sample_tokens(in_list.into_iter().map(to_tokens).collect()));
+  }
+
+  let pattern = string_rule("pattern").or_else(|| {
+    if let Some(rules) = rules
+      && let Some((_, value)) = WELL_KNOWN_STRINGS
+        .iter()
+        .find(|(name, _)| set_rule(rules, name) == Some(ProstValue::Bool(true)))
+    {
+      return Some(regex::escape(value));
+    }
+
+    None
+  });
+
+  let pattern = pattern.unwrap_or_else(|| {
+    let prefix = string_rule("prefix").unwrap_or_default();
+    let contains = string_rule("contains").unwrap_or_default();
+    let suffix = string_rule("suffix").unwrap_or_default();
+
+    let (min_len, max_len) = size_range(rules, "min_len", "max_len", Some("len"), DEFAULT_MAX_LEN);
+    let (min_bytes, max_bytes) = size_range(
+      rules,
+      "min_bytes",
+      "max_bytes",
+      Some("len_bytes"),
+      DEFAULT_MAX_LEN,
+    );
+
+    let fixed_len = prefix.chars().count() + contains.chars().count() + suffix.chars().count();
+    let min_len = min_len.max(min_bytes).saturating_sub(fixed_len);
+    let max_len = max_len
+      .min(max_bytes)
+      .saturating_sub(fixed_len)
+      .max(min_len);
+
+    format!(
+      "{}{}[a-z0-9]{{{},{}}}{}",
+      regex::escape(&prefix),
+      regex::escape(&contains),
+      min_len,
+      max_len,
+      regex::escape(&suffix)
+    )
+  });
+
+  let pattern = strategy_pattern(&pattern)?;
+  let error_message = format!("Could not build a strategy from the pattern {}", pattern);
+
+  Ok(quote! {
+    #proptest::string::string_regex(#pattern).expect(#error_message)
+  })
+}
+
+// Checks that a pattern can be used by proptest to generate strings, which does not support assertions such as anchors or word boundaries.
+// Since the generated strings always match the whole pattern, the anchors at its start and end are removed
+fn strategy_pattern(pattern: &str) -> Result<String, String> {
+  let hir = regex_syntax::Parser::new()
+    .parse(pattern)
+    .map_err(|e| format!("has an invalid pattern: {}", e))?;
+
+  let trimmed = match hir.kind() {
+    HirKind::Concat(subs) => {
+      let mut subs = subs.as_slice();
+
+      if let [first, rest @ ..] = subs
+        && matches!(first.kind(), HirKind::Look(Look::Start))
+      {
+        subs = rest;
+      }
+
+      if let [rest @ .., last] = subs
+        && matches!(last.kind(), HirKind::Look(Look::End))
+      {
+        subs = rest;
+      }
+
+      Some(Hir::concat(subs.to_vec()))
+    }
+    HirKind::Look(Look::Start | Look::End) => Some(Hir::empty()),
+    _ => None,
+  };
+  let hir = trimmed.unwrap_or(hir);
+
+  if has_assertions(&hir) {
+    return Err(format!(
+      "has the pattern {}, which contains assertions (such as word boundaries or anchors in the middle of the pattern) that cannot be used to generate strings",
+      pattern
+    ));
+  }
+
+  Ok(hir.to_string())
+}
+
+fn has_assertions(hir: &Hir) -> bool {
+  match hir.kind() {
+    HirKind::Look(_) => true,
+    HirKind::Repetition(repetition) => has_assertions(&repetition.sub),
+    HirKind::Capture(capture) => has_assertions(&capture.sub),
+    HirKind::Concat(subs) | HirKind::Alternation(subs) => subs.iter().any(has_assertions),
+    _ => false,
+  }
+}
+
+fn bytes_strategy(rules: Option<&DynamicMessage>) -> TokenStream2 {
+  let proptest = quote! { ::protocheck::proptest };
+
+  let to_tokens = |value: &[u8]| {
+    let value = Literal::byte_string(value);
+    quote! { #value.to_vec() }
+  };
+  let is_set =
+    |name: &str| rules.and_then(|rules| set_rule(rules, name)) == Some(ProstValue::Bool(true));
+
+  if let Some(ProstValue::Bytes(const_value)) = rules.and_then(|rules| set_rule(rules, "const")) {
+    return sample_tokens(vec![to_tokens(&const_value)]);
+  }
+
+  if let Some(ProstValue::List(in_list)) = rules.and_then(|rules| set_rule(rules, "in")) {
+    return sample_tokens(
+      in_list
+        .iter()
+        .filter_map(|value| value.as_bytes().map(|value| to_tokens(value)))
+        .collect(),
+    );
+  }
+
+  if is_set("ipv6") {
+    return quote! { #proptest::collection::vec(#proptest::arbitrary::any::<u8>(), 16) };
+  }
+
+  if is_set("ip") || is_set("ipv4") {
+    return quote! { #proptest::collection::vec(#proptest::arbitrary::any::<u8>(), 4) };
+  }
+
+  let (min_len, max_len) = size_range(rules, "min_len", "max_len", Some("len"), DEFAULT_MAX_LEN);
+
+  quote! {
+    #proptest::collection::vec(#proptest::arbitrary::any::<u8>(), #min_len..=#max_len)
+  }
+}
+
+// Returns the violating value for each rule of a field that can be violated automatically
+fn field_violations(field: &FieldDescriptor) -> Vec<(String, TokenStream2)> {
+  let mut violations = Vec::new();
+
+  if field.is_list() || field.is_map() {
+    return violations;
+  }
+
+  let Some(rules) = field_rules(field) else {
+    return violations;
+  };
+
+  let Ok(decoded_rules) = FieldRules::decode(rules.encode_to_vec().as_slice()) else {
+    return violations;
+  };
+
+  let has_presence = field.supports_presence();
+  let wrap = |value: TokenStream2| {
+    if has_presence {
+      quote! { Some(#value) }
+    } else {
+      value
+    }
+  };
+
+  if decoded_rules.required() && has_presence {
+    violations.push(("required".to_string(), quote! { None }));
+  }
+
+  let Some((rules_type, type_rules)) = type_rules(&rules) else {
+    return violations;
+  };
+
+  let kind = field.kind();
+  let rule_id = |rule: &str| format!("{}.{}", rules_type, rule);
+
+  match &kind {
+    Kind::Message(message_desc)
+      if matches!(
+        message_desc.full_name(),
+        "google.protobuf.Duration" | "google.protobuf.Timestamp"
+      ) =>
+    {
+      let type_path = if message_desc.full_name() == "google.protobuf.Timestamp" {
+        quote! { ::protocheck::types::Timestamp }
+      } else {
+        quote! { ::protocheck::types::Duration }
+      };
+      let to_tokens = |nanos: i128| {
+        let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).unwrap_or_default();
+        let nanos = i32::try_from(nanos.rem_euclid(NANOS_PER_SECOND)).unwrap_or_default();
+
+        wrap(quote! { #type_path { seconds: #seconds, nanos: #nanos } })
+      };
+
+      let rules = NumericRules::new(Some(&type_rules), duration_nanos);
+
+      for (rule, value) in numeric_violations(&rules, NANOS_PER_SECOND, 1) {
+        violations.push((rule_id(rule), to_tokens(value)));
+      }
+    }
+    Kind::Message(_) => {}
+    Kind::Bool => {
+      if let Some(ProstValue::Bool(value)) = set_rule(&type_rules, "const") {
+        let value = !value;
+        violations.push((rule_id("const"), wrap(quote! { #value })));
+      }
+    }
+    Kind::String => {
+      for (rule, value) in string_violations(field, &type_rules) {
+        violations.push((rule_id(rule), wrap(value)));
+      }
+    }
+    Kind::Bytes => {}
+    Kind::Enum(enum_desc) => {
+      let rules = NumericRules::new(Some(&type_rules), int_value);
+
+      for (rule, value) in numeric_violations(&rules, 1, 1) {
+        if let Ok(value) = i32::try_from(value) {
+          violations.push((rule_id(rule), wrap(quote! { #value })));
+        }
+      }
+
+      if set_rule(&type_rules, "defined_only") == Some(ProstValue::Bool(true))
+        && let Some(max_defined) = enum_desc
+          .values()
+          .map(|value| value.number())
+          .max()
+        && let Some(undefined) = max_defined.checked_add(1)
+      {
+        violations.push((rule_id("defined_only"), wrap(quote! { #undefined })));
+      }
+    }
+    Kind::Float | Kind::Double => {
+      let rules = NumericRules::new(Some(&type_rules), float_value);
+      let is_float = matches!(kind, Kind::Float);
+
+      for (rule, value) in numeric_violations(&rules, 1.0, 1.0) {
+        let value = float_literal(value, is_float);
+
+        violations.push((rule_id(rule), wrap(quote! { #value })));
+      }
+
+      if set_rule(&type_rules, "finite") == Some(ProstValue::Bool(true)) {
+        let nan = if is_float {
+          quote! { f32::NAN }
+        } else {
+          quote! { f64::NAN }
+        };
+
+        violations.push((rule_id("finite"), wrap(nan)));
+      }
+    }
+    _ => {
+      let rules = NumericRules::new(Some(&type_rules), int_value);
+      let (type_min, type_max) = int_bounds(&kind);
+
+      for (rule, value) in numeric_violations(&rules, 1, 1) {
+        if (type_min..=type_max).contains(&value) {
+          violations.push((rule_id(rule), wrap(int_literal(&kind, value))));
+        }
+      }
+    }
+  }
+
+  violations
+}
+
+// Returns the values that violate the numeric rules, using `step` to move past the const and `in` values and `epsilon` to move past the inclusive bounds
+fn numeric_violations<T>(rules: &NumericRules<T>, step: T, epsilon: T) -> Vec<(&'static str, T)>
+where
+  T: Copy + PartialOrd + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+{
+  let mut violations = Vec::new();
+
+  if let Some(const_value) = rules.const_value {
+    violations.push(("const", const_value + step));
+  }
+
+  if let Some(max_in) = rules
+    .in_list
+    .iter()
+    .copied()
+    .reduce(|max, value| if value > max { value } else { max })
+  {
+    violations.push(("in", max_in + step));
+  }
+
+  if let Some(not_in) = rules.not_in_list.first() {
+    violations.push(("not_in", *not_in));
+  }
+
+  if let Some(gt) = rules.gt {
+    violations.push(("gt", gt));
+  }
+
+  if let Some(gte) = rules.gte {
+    violations.push(("gte", gte - epsilon));
+  }
+
+  if let Some(lt) = rules.lt {
+    violations.push(("lt", lt));
+  }
+
+  if let Some(lte) = rules.lte {
+    violations.push(("lte", lte + epsilon));
+  }
+
+  violations
+}
+
+fn string_violations(
+  field: &FieldDescriptor,
+  rules: &DynamicMessage,
+) -> Vec<(&'static str, TokenStream2)> {
+  let field_ident = proto_name_to_rust_ident(field.name());
+  let current = if field.supports_presence() {
+    quote! { self.#field_ident.clone().unwrap_or_default() }
+  } else {
+    quote! { self.#field_ident.clone() }
+  };
+
+  let string_rule = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::String(value)) => Some(value),
+    _ => None,
+  };
+  let length_rule = |name: &str| match set_rule(rules, name) {
+    Some(ProstValue::U64(value)) => usize::try_from(value).ok(),
+    _ => None,
+  };
+  let repeated = |len: usize| {
+    let value = "a".repeat(len);
+    quote! { ::std::string::String::from(#value) }
+  };
+
+  let mut violations = Vec::new();
+
+  if let Some(const_value) = string_rule("const") {
+    let value = format!("{}_", const_value);
+    violations.push(("const", quote! { ::std::string::String::from(#value) }));
+  }
+
+  if let Some(ProstValue::List(in_list)) = set_rule(rules, "in")
+    && let Some(longest) = in_list
+      .iter()
+      .filter_map(|value| value.as_str())
+      .max_by_key(|value| value.len())
+  {
+    let value = format!("{}_", longest);
+    violations.push(("in", quote! { ::std::string::String::from(#value) }));
+  }
+
+  if let Some(ProstValue::List(not_in_list)) = set_rule(rules, "not_in")
+    && let Some(first) = not_in_list
+      .first()
+      .and_then(|value| value.as_str())
+  {
+    violations.push(("not_in", quote! { ::std::string::String::from(#first) }));
+  }
+
+  for rule in ["min_len", "min_bytes"] {
+    if let Some(min_len) = length_rule(rule)
+      && min_len > 0
+    {
+      violations.push((rule, repeated(min_len - 1)));
+    }
+  }
+
+  for rule in ["max_len", "max_bytes", "len", "len_bytes"] {
+    if let Some(max_len) = length_rule(rule) {
+      violations.push((rule, repeated(max_len + 1)));
+    }
+  }
+
+  if string_rule("prefix").is_some_and(|prefix| !prefix.is_empty()) {
+    violations.push(("prefix", quote! { ::std::format!("!{}", #current) }));
+  }
+
+  if string_rule("suffix").is_some_and(|suffix| !suffix.is_empty()) {
+    violations.push(("suffix", quote! { ::std::format!("{}!", #current) }));
+  }
+
+  if let Some(contains) = string_rule("contains").filter(|contains| !contains.is_empty()) {
+    violations.push(("contains", quote! { #current.replace(#contains, "") }));
+  }
+
+  if let Some(not_contains) = string_rule("not_contains") {
+    violations.push((
+      "not_contains",
+      quote! { ::std::format!("{}{}", #current, #not_contains) },
+    ));
+  }
+
+  // A null character is not valid for any of the well known formats
+  for (rule, _) in WELL_KNOWN_STRINGS {
+    if set_rule(rules, rule) == Some(ProstValue::Bool(true)) {
+      violations.push((rule, quote! { ::std::string::String::from("\0") }));
+    }
+  }
+
+  if set_rule(rules, "well_known_regex").is_some() {
+    violations.push((
+      "well_known_regex",
+      quote! { ::std::string::String::from("\0") },
+    ));
+  }
+
+  violations
+}
+
+// Returns the const or `in` values, which are the only ones allowed when they are defined
+fn explicit_values<T: Copy>(rules: &NumericRules<T>) -> Option<Vec<T>> {
+  if let Some(const_value) = rules.const_value {
+    Some(vec![const_value])
+  } else if !rules.in_list.is_empty() {
+    Some(rules.in_list.clone())
+  } else {
+    None
+  }
+}
+
+fn sample_tokens(values: Vec<TokenStream2>) -> TokenStream2 {
+  quote! { ::protocheck::proptest::sample::select(::std::vec![#(#values),*]) }
+}
+
+// Returns the size range for lists, maps, strings and bytes, using `default_span` when there is no maximum size
+fn size_range(
+  rules: Option<&DynamicMessage>,
+  min_name: &str,
+  max_name: &str,
+  exact_name: Option<&str>,
+  default_span: usize,
+) -> (usize, usize) {
+  // The sizes are usize, since that is what proptest uses for the size ranges of collections
+  let size_rule = |name: &str| match rules.and_then(|rules| set_rule(rules, name)) {
+    Some(ProstValue::U64(value)) => Some(usize::try_from(value).unwrap_or(usize::MAX)),
+    _ => None,
+  };
+
+  if let Some(exact) = exact_name.and_then(size_rule) {
+    return (exact, exact);
+  }
+
+  let min = size_rule(min_name).unwrap_or(0);
+  let max = size_rule(max_name).unwrap_or(min.saturating_add(default_span));
+
+  (min, max.max(min))
+}
+
+fn int_bounds(kind: &Kind) -> (i128, i128) {
+  match kind {
+    Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 | Kind::Enum(_) => {
+      (i128::from(i32::MIN), i128::from(i32::MAX))
+    }
+    Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => (i128::from(i64::MIN), i128::from(i64::MAX)),
+    Kind::Uint32 | Kind::Fixed32 => (0, i128::from(u32::MAX)),
+    _ => (0, i128::from(u64::MAX)),
+  }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn float_literal(value: f64, is_float: bool) -> Literal {
+  if is_float {
+    Literal::f32_suffixed(value as f32)
+  } else {
+    Literal::f64_suffixed(value)
+  }
+}
+
+// Integer literals are suffixed, so that the strategies have a concrete type
+fn int_literal(kind: &Kind, value: i128) -> TokenStream2 {
+  let literal = match kind {
+    Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 | Kind::Enum(_) => {
+      Literal::i32_suffixed(i32::try_from(value).unwrap_or_default())
+    }
+    Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+      Literal::i64_suffixed(i64::try_from(value).unwrap_or_default())
+    }
+    Kind::Uint32 | Kind::Fixed32 => Literal::u32_suffixed(u32::try_from(value).unwrap_or_default()),
+    _ => Literal::u64_suffixed(u64::try_from(value).unwrap_or_default()),
+  };
+
+  quote! { #literal }
+}
+
+// Checks if the rust type of a field is boxed, which prost does for the fields that lead back to their own message, either directly or through other messages
+fn is_boxed_type(rust_type: &Type) -> bool {
+  let Type::Path(type_path) = rust_type else {
+    return false;
+  };
+
+  let Some(segment) = type_path.path.segments.last() else {
+    return false;
+  };
+
+  match segment.ident.to_string().as_str() {
+    "Box" => true,
+    "Option" => match &segment.arguments {
+      PathArguments::AngleBracketed(arguments) => arguments
+        .args
+        .iter()
+        .any(|argument| matches!(argument, GenericArgument::Type(inner) if is_boxed_type(inner))),
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+// Checks if a message field can lead back to the message that contains it, in which case its strategy would be infinitely deep
+fn is_recursive(field: &FieldDescriptor, message_desc: &MessageDescriptor) -> bool {
+  let start = match field.kind() {
+    Kind::Message(entry_desc) if field.is_map() => {
+      match entry_desc.map_entry_value_field().kind() {
+        Kind::Message(value_desc) => value_desc,
+        _ => return false,
+      }
+    }
+    Kind::Message(field_message_desc) => field_message_desc,
+    _ => return false,
+  };
+
+  let mut visited: HashSet<String> = HashSet::new();
+  let mut to_visit = vec![start];
+
+  while let Some(current) = to_visit.pop() {
+    if current.full_name() == message_desc.full_name() {
+      return true;
+    }
+
+    if !visited.insert(current.full_name().to_string()) {
+      continue;
+    }
+
+    for nested_field in current.fields() {
+      if let Kind::Message(nested_desc) = nested_field.kind() {
+        to_visit.push(nested_desc);
+      }
+    }
+  }
+
+  false
+}
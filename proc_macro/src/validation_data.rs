@@ -8,16 +8,21 @@ use proc_macro2::TokenStream;
 use prost_reflect::FieldDescriptor;
 use proto_types::{
   protovalidate::{
-    ComparableGreaterThan, ComparableLessThan, ComparableRules, ConstRule, Ignore, ItemList,
-    LengthRules, SubstringRule, SubstringRules,
+    ComparableGreaterThan, ComparableLessThan, ComparableRules, ConstRule, DoubleRules,
+    DurationRules, EnumRules, Fixed32Rules, Fixed64Rules, FloatRules, Ignore, Int32Rules,
+    Int64Rules, ItemList, LengthRules, SFixed32Rules, SFixed64Rules, SInt32Rules, SInt64Rules,
+    StringRules, SubstringRule, SubstringRules, UInt32Rules, UInt64Rules,
   },
-  FieldType,
+  Duration, FieldType,
 };
 use protocheck_core::field_data::FieldKind;
 use quote::{format_ident, quote, ToTokens};
 
 use crate::{
-  rules::core::{get_field_type, get_plural_suffix},
+  rules::{
+    core::{get_field_type, get_plural_suffix},
+    table_rules::TableRules,
+  },
   Ident2, ProtoType, Span2,
 };
 
@@ -41,17 +46,22 @@ pub(crate) struct ValidationData<'a> {
   pub item_rust_ident: &'a Ident2,
   pub violations_ident: &'a Ident2,
   pub parent_messages_ident: &'a Ident2,
+  pub validation_context_ident: &'a Ident2,
   pub field_context_ident: &'a Ident2,
   pub map_key_context_ident: &'a Ident2,
   pub map_value_context_ident: &'a Ident2,
   pub vec_item_context_ident: &'a Ident2,
   pub field_kind: FieldKind,
   pub value_ident: OnceCell<TokenStream>,
+  /// When set, the standard rules are collected as entries of the validation table instead of being expanded inline.
+  pub table_rules: Option<&'a TableRules>,
 }
 
 pub struct RepeatedValidator {
   pub vec_level_rules: TokenStream,
   pub items_rules: TokenStream,
+  // The unique rule keeps track of the values seen so far, so the items cannot be validated in parallel
+  pub has_unique_rule: bool,
 }
 
 pub struct MapValidator {
@@ -74,6 +84,118 @@ impl ListRule {
   }
 }
 
+/// Gives access to the values of the `in` and `not_in` rules, sorted and deduplicated, so that large lists can be stored in static slices and checked with a binary search.
+/// Returns `None` for the types that cannot be sorted reliably, such as floats.
+pub trait SortedListValues {
+  fn sorted_in(&self) -> Option<Vec<TokenStream>>;
+  fn sorted_not_in(&self) -> Option<Vec<TokenStream>>;
+}
+
+fn sorted_tokens<T: Ord + Clone + ToTokens>(values: &[T]) -> Vec<TokenStream> {
+  let mut values = values.to_vec();
+  values.sort();
+  values.dedup();
+
+  values
+    .iter()
+    .map(|value| value.to_token_stream())
+    .collect()
+}
+
+macro_rules! sorted_list_values {
+  ($($rules_type:ty),*) => {
+    $(
+      impl SortedListValues for $rules_type {
+        fn sorted_in(&self) -> Option<Vec<TokenStream>> {
+          Some(sorted_tokens(&self.r#in))
+        }
+
+        fn sorted_not_in(&self) -> Option<Vec<TokenStream>> {
+          Some(sorted_tokens(&self.not_in))
+        }
+      }
+    )*
+  };
+}
+
+sorted_list_values!(
+  Int32Rules,
+  Int64Rules,
+  UInt32Rules,
+  UInt64Rules,
+  SInt32Rules,
+  SInt64Rules,
+  Fixed32Rules,
+  Fixed64Rules,
+  SFixed32Rules,
+  SFixed64Rules,
+  StringRules,
+  EnumRules
+);
+
+impl SortedListValues for FloatRules {
+  fn sorted_in(&self) -> Option<Vec<TokenStream>> {
+    None
+  }
+
+  fn sorted_not_in(&self) -> Option<Vec<TokenStream>> {
+    None
+  }
+}
+
+impl SortedListValues for DoubleRules {
+  fn sorted_in(&self) -> Option<Vec<TokenStream>> {
+    None
+  }
+
+  fn sorted_not_in(&self) -> Option<Vec<TokenStream>> {
+    None
+  }
+}
+
+fn sorted_durations(values: &[Duration]) -> Vec<TokenStream> {
+  let mut values = values.to_vec();
+  values.sort_by_key(|d| (d.seconds, d.nanos));
+  values.dedup_by_key(|d| (d.seconds, d.nanos));
+
+  values
+    .iter()
+    .map(|duration| {
+      let seconds = duration.seconds;
+      let nanos = duration.nanos;
+
+      quote! { ::protocheck::types::Duration { seconds: #seconds, nanos: #nanos } }
+    })
+    .collect()
+}
+
+impl SortedListValues for DurationRules {
+  fn sorted_in(&self) -> Option<Vec<TokenStream>> {
+    Some(sorted_durations(&self.r#in))
+  }
+
+  fn sorted_not_in(&self) -> Option<Vec<TokenStream>> {
+    Some(sorted_durations(&self.not_in))
+  }
+}
+
+// The type of the items in a sorted static list
+fn sorted_list_item_type(field_type: FieldType) -> Option<TokenStream> {
+  let item_type = match field_type {
+    FieldType::String => quote! { &'static str },
+    FieldType::Int32 | FieldType::Sint32 | FieldType::Sfixed32 | FieldType::Enum => {
+      quote! { i32 }
+    }
+    FieldType::Int64 | FieldType::Sint64 | FieldType::Sfixed64 => quote! { i64 },
+    FieldType::Uint32 | FieldType::Fixed32 => quote! { u32 },
+    FieldType::Uint64 | FieldType::Fixed64 => quote! { u64 },
+    FieldType::Duration => quote! { ::protocheck::types::Duration },
+    _ => return None,
+  };
+
+  Some(item_type)
+}
+
 impl ValidationData<'_> {
   pub fn static_full_name(&self) -> String {
     self
@@ -97,7 +219,7 @@ impl ValidationData<'_> {
         ::protocheck::validators::#validator_type_ident::contains(&#field_context_ident, &#value_ident, #val_tokens, #error_message)
       };
 
-      self.get_validator_tokens(tokens, &expr);
+      self.get_validator_tokens(tokens, &format!("{}.contains", validator_type_ident), &expr);
     }
 
     if let Some(not_contains) = rules.not_contains {
@@ -109,7 +231,7 @@ impl ValidationData<'_> {
         ::protocheck::validators::#validator_type_ident::not_contains(&#field_context_ident, &#value_ident, #val_tokens, #error_message)
       };
 
-      self.get_validator_tokens(tokens, &expr);
+      self.get_validator_tokens(tokens, &format!("{}.not_contains", validator_type_ident), &expr);
     }
 
     if let Some(prefix) = rules.prefix {
@@ -121,7 +243,7 @@ impl ValidationData<'_> {
         ::protocheck::validators::#validator_type_ident::prefix(&#field_context_ident, &#value_ident, #val_tokens, #error_message)
       };
 
-      self.get_validator_tokens(tokens, &expr);
+      self.get_validator_tokens(tokens, &format!("{}.prefix", validator_type_ident), &expr);
     }
 
     if let Some(suffix) = rules.suffix {
@@ -133,7 +255,7 @@ impl ValidationData<'_> {
         ::protocheck::validators::#validator_type_ident::suffix(&#field_context_ident, &#value_ident, #val_tokens, #error_message)
       };
 
-      self.get_validator_tokens(tokens, &expr);
+      self.get_validator_tokens(tokens, &format!("{}.suffix", validator_type_ident), &expr);
     }
   }
 
@@ -141,54 +263,18 @@ impl ValidationData<'_> {
     let value_ident = self.value_ident();
     let field_context_ident = self.field_context_ident();
     let validator_type_ident = format_ident!("{}", rules.name());
-    let unit = rules.unit();
-
-    if let Some(len) = rules.len {
-      let error_message = format!(
-        "must be exactly {} {}{} long",
-        len,
-        unit,
-        get_plural_suffix(len)
-      );
-      let func_name = format_ident!("{}", rules.len_name());
-
-      let expr = quote! {
-        ::protocheck::validators::#validator_type_ident::#func_name(&#field_context_ident, &#value_ident, #len, #error_message)
-      };
-
-      self.get_validator_tokens(tokens, &expr);
-    }
-
-    if let Some(min_len) = rules.min_len {
-      let error_message = format!(
-        "must contain at least {} {}{}",
-        min_len,
-        unit,
-        get_plural_suffix(min_len)
-      );
-      let func_name = format_ident!("{}", rules.min_len_name());
-
-      let expr = quote! {
-        ::protocheck::validators::#validator_type_ident::#func_name(&#field_context_ident, &#value_ident, #min_len, #error_message)
-      };
-
-      self.get_validator_tokens(tokens, &expr);
-    }
-
-    if let Some(max_len) = rules.max_len {
-      let error_message = format!(
-        "cannot contain more than {} {}{}",
-        max_len,
-        unit,
-        get_plural_suffix(max_len)
-      );
-      let func_name = format_ident!("{}", rules.max_len_name());
+    let rule_type = match self.field_kind {
+      FieldKind::Map(_) => "map".to_string(),
+      FieldKind::Repeated(_) => "repeated".to_string(),
+      field_kind => field_kind.inner_type().name().to_string(),
+    };
 
+    for (func_name, target, error_message) in length_checks(&rules) {
       let expr = quote! {
-        ::protocheck::validators::#validator_type_ident::#func_name(&#field_context_ident, &#value_ident, #max_len, #error_message)
+        ::protocheck::validators::#validator_type_ident::#func_name(&#field_context_ident, &#value_ident, #target, #error_message)
       };
 
-      self.get_validator_tokens(tokens, &expr);
+      self.get_validator_tokens(tokens, &format!("{}.{}", rule_type, func_name), &expr);
     }
   }
 
@@ -214,7 +300,11 @@ impl ValidationData<'_> {
     let validator_expression_tokens = quote! {
       ::protocheck::validators::#validator_type_ident::pattern(&#field_context_ident, &#value_ident, &#static_regex_ident, #error_message)
     };
-    self.get_validator_tokens(tokens, &validator_expression_tokens);
+    self.get_validator_tokens(
+      tokens,
+      &format!("{}.pattern", validator_type_ident),
+      &validator_expression_tokens,
+    );
   }
 
   pub fn get_comparable_validator<T>(
@@ -238,7 +328,7 @@ impl ValidationData<'_> {
           let func_ident = format_ident!("{}_lt", proto_type_name);
 
           let expr = quote! { #module_path::#func_ident(&#field_context_ident, #value_ident, #lt, #error_message) };
-          self.get_validator_tokens(tokens, &expr);
+          self.get_validator_tokens(tokens, &format!("{}.lt", proto_type_name), &expr);
         }
         ComparableLessThan::Lte {
           val: lte,
@@ -247,7 +337,7 @@ impl ValidationData<'_> {
           let func_ident = format_ident!("{}_lte", proto_type_name);
 
           let expr = quote! { #module_path::#func_ident(&#field_context_ident, #value_ident, #lte, #error_message) };
-          self.get_validator_tokens(tokens, &expr);
+          self.get_validator_tokens(tokens, &format!("{}.lte", proto_type_name), &expr);
         }
       };
     }
@@ -261,7 +351,7 @@ impl ValidationData<'_> {
           let func_ident = format_ident!("{}_gt", proto_type_name);
 
           let expr = quote! { #module_path::#func_ident(&#field_context_ident, #value_ident, #gt, #error_message) };
-          self.get_validator_tokens(tokens, &expr);
+          self.get_validator_tokens(tokens, &format!("{}.gt", proto_type_name), &expr);
         }
         ComparableGreaterThan::Gte {
           val: gte,
@@ -270,7 +360,7 @@ impl ValidationData<'_> {
           let func_ident = format_ident!("{}_gte", proto_type_name);
 
           let expr = quote! { #module_path::#func_ident(&#field_context_ident, #value_ident, #gte, #error_message) };
-          self.get_validator_tokens(tokens, &expr);
+          self.get_validator_tokens(tokens, &format!("{}.gte", proto_type_name), &expr);
         }
       };
     }
@@ -281,14 +371,17 @@ impl ValidationData<'_> {
     rule_type: ListRule,
     tokens: &mut TokenStream,
     list: ItemList,
+    sorted_values: Option<Vec<TokenStream>>,
     static_defs: &mut TokenStream,
   ) {
     let field_context_ident = self.field_context_ident();
     let value_ident = self.value_ident();
-    let proto_type_name = self.field_kind.inner_type().name();
+    let field_type = self.field_kind.inner_type();
+    let proto_type_name = field_type.name();
 
     let module_path = quote! { ::protocheck::validators::containing };
     let rule_name = rule_type.name();
+    let rule_id = format!("{}.{}", proto_type_name, rule_name);
 
     match list {
       ItemList::Slice {
@@ -300,22 +393,40 @@ impl ValidationData<'_> {
           #module_path::#func_path(&#field_context_ident, #value_ident, &#list_tokens, #error_message)
         };
 
-        self.get_validator_tokens(tokens, &expr);
+        self.get_validator_tokens(tokens, &rule_id, &expr);
       }
       ItemList::HashSet {
         error_message,
         tokens: hashset_tokens,
         static_ident,
       } => {
-        let func_path = format_ident!("{}_{}_hashset_list", proto_type_name, rule_name);
+        // Large lists are stored in a sorted static slice whenever possible,
+        // so that no hashing or allocations are needed at runtime
+        if let Some(values) = sorted_values
+          && let Some(item_type) = sorted_list_item_type(field_type)
+        {
+          let func_path = format_ident!("{}_{}_sorted_list", proto_type_name, rule_name);
+
+          static_defs.extend(quote! {
+            static #static_ident: &[#item_type] = &[#(#values),*];
+          });
+
+          let expr = quote! {
+            #module_path::#func_path(&#field_context_ident, #value_ident, #static_ident, #error_message)
+          };
 
-        static_defs.extend(hashset_tokens);
+          self.get_validator_tokens(tokens, &rule_id, &expr);
+        } else {
+          let func_path = format_ident!("{}_{}_hashset_list", proto_type_name, rule_name);
 
-        let expr = quote! {
-          #module_path::#func_path(&#field_context_ident, #value_ident, &#static_ident, #error_message)
-        };
+          static_defs.extend(hashset_tokens);
+
+          let expr = quote! {
+            #module_path::#func_path(&#field_context_ident, #value_ident, &#static_ident, #error_message)
+          };
 
-        self.get_validator_tokens(tokens, &expr);
+          self.get_validator_tokens(tokens, &rule_id, &expr);
+        }
       }
     };
   }
@@ -398,12 +509,19 @@ impl ValidationData<'_> {
     });
 
     let loop_tokens = has_loop.then(|| {
-      let field_ident = &self.item_rust_ident;
+      let field_value = self.field_value_tokens();
       let key_ident = &self.map_key_ident;
       let map_value_ident = &self.map_value_ident;
 
-      quote! {
-        for (#key_ident, #map_value_ident) in self.#field_ident.iter() {
+      // Entries are sorted by key so that the order of the violations is deterministic,
+      // and the same whether the entries are validated sequentially or in parallel
+      let sorted_entries = quote! {
+        let mut map_entries: Vec<_> = #field_value.iter().collect();
+        map_entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+      };
+
+      let sequential_loop = quote! {
+        for &(#key_ident, #map_value_ident) in map_entries.iter() {
           #keys_context_tokens
           #values_context_tokens
 
@@ -411,6 +529,33 @@ impl ValidationData<'_> {
 
           #values_rules
         }
+      };
+
+      if cfg!(feature = "rayon") {
+        let parallel_validation = self.parallel_validation_tokens(
+          quote! { map_entries.as_slice() },
+          quote! { _, &(#key_ident, #map_value_ident) },
+          quote! {
+            #keys_context_tokens
+            #values_context_tokens
+
+            #keys_rules
+
+            #values_rules
+          },
+        );
+
+        let parallel_loop = self.parallel_loop_tokens(&parallel_validation, &sequential_loop);
+
+        quote! {
+          #sorted_entries
+          #parallel_loop
+        }
+      } else {
+        quote! {
+          #sorted_entries
+          #sequential_loop
+        }
       }
     });
 
@@ -461,6 +606,7 @@ impl ValidationData<'_> {
     let RepeatedValidator {
       vec_level_rules,
       items_rules,
+      has_unique_rule,
     } = rules_data;
 
     let has_loop = !items_rules.is_empty();
@@ -470,7 +616,7 @@ impl ValidationData<'_> {
       .then(|| self.field_context_tokens(self.field_kind, self.field_context_ident));
 
     let loop_tokens = has_loop.then(|| {
-      let field_ident = &self.item_rust_ident;
+      let field_value = self.field_value_tokens();
       let index_ident = &self.index_ident;
       let item_ident = &self.item_ident;
 
@@ -479,11 +625,26 @@ impl ValidationData<'_> {
         self.vec_item_context_ident,
       );
 
-      quote! {
-        for (#index_ident, #item_ident) in self.#field_ident.iter().enumerate() {
+      let sequential_loop = quote! {
+        for (#index_ident, #item_ident) in #field_value.iter().enumerate() {
           #items_context_tokens
           #items_rules
         }
+      };
+
+      if cfg!(feature = "rayon") && !has_unique_rule {
+        let parallel_validation = self.parallel_validation_tokens(
+          quote! { #field_value.as_slice() },
+          quote! { #index_ident, #item_ident },
+          quote! {
+            #items_context_tokens
+            #items_rules
+          },
+        );
+
+        self.parallel_loop_tokens(&parallel_validation, &sequential_loop)
+      } else {
+        sequential_loop
       }
     });
 
@@ -495,6 +656,55 @@ impl ValidationData<'_> {
     });
   }
 
+  // Calls the parallel validator with a closure that receives the same idents
+  // used by the sequential loop, so that the same rules can be used in both
+  fn parallel_validation_tokens(
+    &self,
+    items: TokenStream,
+    item_pattern: TokenStream,
+    item_rules: TokenStream,
+  ) -> TokenStream {
+    let Self {
+      parent_messages_ident,
+      violations_ident,
+      validation_context_ident,
+      ..
+    } = self;
+
+    quote! {
+      ::protocheck::parallel::validate_items(
+        #items,
+        #validation_context_ident,
+        #parent_messages_ident.as_slice(),
+        #violations_ident,
+        |#item_pattern, #parent_messages_ident, #violations_ident| {
+          #item_rules
+        },
+      );
+
+      if #validation_context_ident.should_stop(#violations_ident) {
+        return;
+      }
+    }
+  }
+
+  fn parallel_loop_tokens(
+    &self,
+    parallel_validation: &TokenStream,
+    sequential_loop: &TokenStream,
+  ) -> TokenStream {
+    let field_value = self.field_value_tokens();
+    let validation_context_ident = self.validation_context_ident;
+
+    quote! {
+      if #validation_context_ident.should_validate_in_parallel(#field_value.len()) {
+        #parallel_validation
+      } else {
+        #sequential_loop
+      }
+    }
+  }
+
   pub fn get_const_validator<T>(&self, tokens: &mut TokenStream, rule: ConstRule<T>)
   where
     T: ToTokens,
@@ -508,7 +718,7 @@ impl ValidationData<'_> {
 
     let expr = quote! { ::protocheck::validators::constants::#func_ident(&#field_context_ident, #value_ident, #val, #error_message) };
 
-    self.get_validator_tokens(tokens, &expr);
+    self.get_validator_tokens(tokens, &format!("{}.const", proto_type_name), &expr);
   }
 
   pub fn get_message_field_validator_tokens(
@@ -519,6 +729,7 @@ impl ValidationData<'_> {
     let Self {
       parent_messages_ident,
       violations_ident,
+      validation_context_ident,
       ..
     } = self;
 
@@ -544,9 +755,9 @@ impl ValidationData<'_> {
     let subscript_tokens = self.subscript_tokens(field_kind);
 
     let field_path_element_tokens = quote! {
-      ::protocheck::types::protovalidate::FieldPathElement {
-        field_name: Some(#field_proto_name.to_string()),
-        field_number: Some(#field_tag as i32),
+      ::protocheck::field_data::PathElement {
+        name: #field_proto_name,
+        tag: Some(#field_tag),
         field_type: Some(#field_proto_type as i32),
         key_type: #key_type,
         value_type: #value_type,
@@ -554,12 +765,34 @@ impl ValidationData<'_> {
       };
     };
 
+    // The depth violation bypasses the rule filters, since the nested message would otherwise be considered valid without being validated
+    let push_depth_violation = push_unfiltered_violation_tokens(
+      validation_context_ident,
+      violations_ident,
+      quote! { depth_violation },
+    );
+
     tokens.extend(quote! {
       let current_nested_field_element = #field_path_element_tokens;
 
       #parent_messages_ident.push(current_nested_field_element);
-      #value_ident.nested_validate(#parent_messages_ident, #violations_ident);
-      #parent_messages_ident.pop();
+
+      if #validation_context_ident.is_max_depth_exceeded(#parent_messages_ident.len()) {
+        let depth_violation = ::protocheck::validators::depth::max_depth(
+          #parent_messages_ident.as_slice(),
+          #validation_context_ident.options().max_depth().unwrap_or_default()
+        );
+        #parent_messages_ident.pop();
+
+        #push_depth_violation
+      } else {
+        #value_ident.nested_validate(#validation_context_ident, #parent_messages_ident, #violations_ident);
+        #parent_messages_ident.pop();
+
+        if #validation_context_ident.should_stop(#violations_ident) {
+          return;
+        }
+      }
     });
   }
 
@@ -567,21 +800,25 @@ impl ValidationData<'_> {
     self.is_required.then(|| {
       let field_context_tokens = self.field_context_tokens(self.field_kind, self.field_context_ident);
       let field_context_ident = self.field_context_ident();
-      let violations_ident = &self.violations_ident;
+      let rule_check = self.rule_check_tokens("field.required");
+      let push_violation = self.push_checked_violation_tokens(quote! { required_violation });
 
       quote! {
-      #field_context_tokens
-      let required_violation = ::protocheck::validators::required::required(&#field_context_ident);
-      #violations_ident.push(required_violation);
-    }})
+        if #rule_check {
+          #field_context_tokens
+          let required_violation = ::protocheck::validators::required::required(&#field_context_ident);
+          #push_violation
+        }
+      }
+    })
   }
 
   pub fn get_required_only_validator(&self, tokens: &mut TokenStream) {
-    let item_rust_ident = &self.item_rust_ident;
+    let field_value = self.field_value_tokens();
     let required_validation_tokens = self.get_required_validation_tokens();
 
     tokens.extend(quote! {
-      if self.#item_rust_ident.is_none() {
+      if #field_value.is_none() {
         #required_validation_tokens
       }
     });
@@ -590,13 +827,13 @@ impl ValidationData<'_> {
   pub fn get_aggregated_validator_tokens(&self, validators: TokenStream) -> TokenStream {
     let field_context_tokens = self.field_context_tokens(self.field_kind, self.field_context_ident);
     let required_check = self.get_required_validation_tokens();
-    let field_ident = self.item_rust_ident;
+    let field_value = self.field_value_tokens();
 
     if self.is_option() {
       let match_kind = if self.field_kind.is_copy() {
-        quote! { self.#field_ident }
+        quote! { #field_value }
       } else {
-        quote! { self.#field_ident.as_ref() }
+        quote! { #field_value.as_ref() }
       };
 
       quote! {
@@ -639,21 +876,55 @@ impl ValidationData<'_> {
     }
   }
 
+  /// Adds a validator for a standard rule, which is only evaluated if the rule passes the filters of the validation context.
   pub fn get_validator_tokens(
     &self,
     tokens: &mut TokenStream,
+    rule_id: &str,
     validator_expression_tokens: &TokenStream,
   ) {
-    let violations_ident = &self.violations_ident;
+    if let Some(table_rules) = self.table_rules {
+      table_rules.push(self.field_context_ident(), rule_id, validator_expression_tokens);
+      return;
+    }
+
+    let rule_check = self.rule_check_tokens(rule_id);
+    let push_violation = self.push_checked_violation_tokens(quote! { v });
 
     tokens.extend(quote! {
-      match #validator_expression_tokens {
-        Ok(_) => {}
-        Err(v) => #violations_ident.push(v)
-      };
+      if #rule_check {
+        match #validator_expression_tokens {
+          Ok(_) => {}
+          Err(v) => #push_violation
+        };
+      }
     });
   }
 
+  /// Checks whether a standard rule passes the filters of the validation context.
+  pub fn rule_check_tokens(&self, rule_id: &str) -> TokenStream {
+    let validation_context_ident = self.validation_context_ident;
+
+    quote! {
+      #validation_context_ident.is_rule_enabled(::protocheck::context::RuleCategory::Standard, #rule_id)
+    }
+  }
+
+  /// Pushes the violation of a rule that was already checked with [`rule_check_tokens`](Self::rule_check_tokens).
+  pub fn push_checked_violation_tokens(&self, violation: TokenStream) -> TokenStream {
+    push_unfiltered_violation_tokens(
+      self.validation_context_ident,
+      self.violations_ident,
+      violation,
+    )
+  }
+
+  // The validators of each field are generated in a function that receives a reference to its value as `field_value`,
+  // so that the field can also be validated on its own
+  pub fn field_value_tokens(&self) -> TokenStream {
+    quote! { (*field_value) }
+  }
+
   pub fn is_option(&self) -> bool {
     self.is_optional && !self.is_in_oneof
   }
@@ -662,7 +933,7 @@ impl ValidationData<'_> {
     match field_kind {
       FieldKind::RepeatedItem(_) => {
         let index_ident = self.index_ident;
-        quote! { Some(::protocheck::types::protovalidate::field_path_element::Subscript::Index(#index_ident as u64)) }
+        quote! { Some(::protocheck::field_data::PathSubscript::Index(#index_ident as u64)) }
       }
       FieldKind::MapKey(_) | FieldKind::MapValue(_) => {
         if let Some(key_type_enum) = self.map_keys_type {
@@ -678,7 +949,6 @@ impl ValidationData<'_> {
 
   pub fn value_ident(&self) -> &TokenStream {
     let Self {
-      item_rust_ident,
       map_key_ident: key_ident,
       map_value_ident,
       item_ident,
@@ -712,13 +982,12 @@ impl ValidationData<'_> {
             quote! { #map_value_ident }
           }
         }
-        FieldKind::Repeated(_) => quote! { self.#item_rust_ident },
-        FieldKind::Map(_) => quote! { self.#item_rust_ident },
+        FieldKind::Repeated(_) | FieldKind::Map(_) => self.field_value_tokens(),
         FieldKind::Single(_) => {
           let base_ident = if self.is_optional || self.is_in_oneof {
             quote! { val }
           } else {
-            quote! { self.#item_rust_ident }
+            self.field_value_tokens()
           };
 
           if !is_copy && !self.is_option() {
@@ -733,10 +1002,12 @@ impl ValidationData<'_> {
 }
 
 fn generate_key_subscript(key_proto_type: &ProtoType, key_ident: &Ident2) -> TokenStream {
-  let subscript_path = quote! { ::protocheck::types::protovalidate::field_path_element::Subscript };
+  let subscript_path = quote! { ::protocheck::field_data::PathSubscript };
 
   match key_proto_type {
-    ProtoType::String => quote! { #subscript_path::StringKey(#key_ident.clone().into()) },
+    ProtoType::String => {
+      quote! { #subscript_path::StringKey(::std::borrow::Cow::Borrowed(#key_ident.as_str())) }
+    }
     ProtoType::Uint64 => quote! { #subscript_path::UintKey(#key_ident.clone().into()) },
     ProtoType::Uint32 => quote! { #subscript_path::UintKey(#key_ident.clone().into()) },
     ProtoType::Int64 => quote! { #subscript_path::IntKey(#key_ident.clone().into()) },
@@ -756,3 +1027,86 @@ fn generate_key_subscript(key_proto_type: &ProtoType, key_ident: &Ident2) -> Tok
     }
   }
 }
+
+/// Returns the name of the validator function, the target and the error message for each length rule that is set.
+fn length_checks(rules: &LengthRules) -> Vec<(Ident2, u64, String)> {
+  let unit = rules.unit();
+  let mut checks = Vec::new();
+
+  if let Some(len) = rules.len {
+    let error_message = format!(
+      "must be exactly {} {}{} long",
+      len,
+      unit,
+      get_plural_suffix(len)
+    );
+    checks.push((format_ident!("{}", rules.len_name()), len, error_message));
+  }
+
+  if let Some(min_len) = rules.min_len {
+    let error_message = format!(
+      "must contain at least {} {}{}",
+      min_len,
+      unit,
+      get_plural_suffix(min_len)
+    );
+    checks.push((
+      format_ident!("{}", rules.min_len_name()),
+      min_len,
+      error_message,
+    ));
+  }
+
+  if let Some(max_len) = rules.max_len {
+    let error_message = format!(
+      "cannot contain more than {} {}{}",
+      max_len,
+      unit,
+      get_plural_suffix(max_len)
+    );
+    checks.push((
+      format_ident!("{}", rules.max_len_name()),
+      max_len,
+      error_message,
+    ));
+  }
+
+  checks
+}
+
+/// Pushes a violation to the list if it passes the rule filters, and makes the validator return early if no more violations should be collected.
+pub fn push_violation_tokens(
+  validation_context_ident: &Ident2,
+  violations_ident: &Ident2,
+  violation: TokenStream,
+) -> TokenStream {
+  let push_violation =
+    push_unfiltered_violation_tokens(validation_context_ident, violations_ident, quote! { violation });
+
+  quote! {
+    {
+      let violation = #violation;
+
+      if #validation_context_ident.should_report(&violation) {
+        #push_violation
+      }
+    }
+  }
+}
+
+/// Pushes a violation to the list without checking the rule filters, and makes the validator return early if no more violations should be collected.
+pub fn push_unfiltered_violation_tokens(
+  validation_context_ident: &Ident2,
+  violations_ident: &Ident2,
+  violation: TokenStream,
+) -> TokenStream {
+  quote! {
+    {
+      #violations_ident.push(#violation);
+
+      if #validation_context_ident.should_stop(#violations_ident) {
+        return;
+      }
+    }
+  }
+}
@@ -9,7 +9,15 @@ use std::{
 };
 
 use prost_build::Config;
-use prost_reflect::{prost::Message, prost_types::FileDescriptorSet};
+use prost_reflect::DescriptorPool;
+#[cfg(feature = "tonic")]
+pub use services::{generate_validated_services, SKIP_VALIDATION_OPTION};
+
+#[cfg(feature = "tonic")]
+mod services;
+
+/// The path to the directory with the proto files of protocheck, such as `protocheck/options.proto`, which defines the `(protocheck.skip_validation)` method option. It must be added to the include paths in order to import them.
+pub const PROTO_INCLUDE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/proto");
 
 /// This function compiles the proto_files in the list, it creates an intermediary file descriptor and it uses it to extract information about the messages, enums and oneofs which can later be used to generate the validation logic with protocheck.
 pub fn compile_protos_with_validators(
@@ -18,30 +26,115 @@ pub fn compile_protos_with_validators(
   proto_include_paths: &[impl AsRef<Path>],
   packages: &[&str],
 ) -> Result<(), Box<dyn Error>> {
-  let out_dir = env::var("OUT_DIR")
-    .map(PathBuf::from)
-    .unwrap_or_else(|_| env::temp_dir());
+  compile_protos_with_options(
+    config,
+    proto_files,
+    proto_include_paths,
+    packages,
+    &ValidatorsOptions::default(),
+  )
+}
 
-  let temp_descriptor_path = out_dir.join("temp_file_descriptor_set_for_protocheck.bin");
-  {
-    let mut temp_config = prost_build::Config::new();
-    temp_config.file_descriptor_set_path(&temp_descriptor_path);
-    temp_config.out_dir(&out_dir);
-    temp_config.compile_protos(proto_files, proto_include_paths)?;
-  }
+/// Works like [`compile_protos_with_validators`], but the messages use the table-driven validation mode, where the rules for scalar fields are stored in static tables that are walked by a shared interpreter, rather than being expanded inline in each message. This reduces the amount of generated code (and compile times) for large schemas.
+/// The messages listed in `inline_messages` (by their full name, such as `myapp.v1.User`) will keep using the inline validators, which can be useful for messages that are validated in hot paths.
+pub fn compile_protos_with_table_validators(
+  config: &mut Config,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  packages: &[&str],
+  inline_messages: &[&str],
+) -> Result<(), Box<dyn Error>> {
+  compile_protos_with_options(
+    config,
+    proto_files,
+    proto_include_paths,
+    packages,
+    &ValidatorsOptions {
+      table_mode: true,
+      inline_messages,
+      ..Default::default()
+    },
+  )
+}
 
-  let mut fds_file = std::fs::File::open(&temp_descriptor_path)?;
-  let mut fds_bytes = Vec::new();
-  fds_file.read_to_end(&mut fds_bytes)?;
-  let fds = FileDescriptorSet::decode(fds_bytes.as_slice())?;
-  let pool = prost_reflect::DescriptorPool::from_file_descriptor_set(fds)?;
+/// Works like [`compile_protos_with_validators`], but the messages listed in `example_messages` (by their full name, such as `myapp.v1.User`) also get a `valid_example()` constructor, which returns an instance that satisfies all of their standard rules.
+/// The nested messages that must be set for a message to be valid must be listed as well, and a compile error is emitted for the messages with rules that cannot be satisfied automatically, such as Cel rules or complex patterns without an `example` value.
+pub fn compile_protos_with_valid_examples(
+  config: &mut Config,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  packages: &[&str],
+  example_messages: &[&str],
+) -> Result<(), Box<dyn Error>> {
+  compile_protos_with_options(
+    config,
+    proto_files,
+    proto_include_paths,
+    packages,
+    &ValidatorsOptions {
+      example_messages,
+      ..Default::default()
+    },
+  )
+}
+
+/// The options used by [`compile_protos_with_options`], which can combine the table-driven validation mode with the generation of valid examples.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ValidatorsOptions<'a> {
+  /// Whether the messages use the table-driven validation mode, as in [`compile_protos_with_table_validators`].
+  pub table_mode: bool,
+  /// The messages (by their full name) that keep using the inline validators when `table_mode` is set.
+  pub inline_messages: &'a [&'a str],
+  /// The messages (by their full name) that get a `valid_example()` constructor, as in [`compile_protos_with_valid_examples`].
+  pub example_messages: &'a [&'a str],
+}
+
+/// Works like [`compile_protos_with_validators`], with the given [`ValidatorsOptions`].
+pub fn compile_protos_with_options(
+  config: &mut Config,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  packages: &[&str],
+  options: &ValidatorsOptions,
+) -> Result<(), Box<dyn Error>> {
+  set_validators_attributes(
+    config,
+    proto_files,
+    proto_include_paths,
+    packages,
+    |message_name| options.table_mode && !options.inline_messages.contains(&message_name),
+    |message_name| options.example_messages.contains(&message_name),
+  )
+}
+
+fn set_validators_attributes(
+  config: &mut Config,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  packages: &[&str],
+  use_table_mode: impl Fn(&str) -> bool,
+  generate_example: impl Fn(&str) -> bool,
+) -> Result<(), Box<dyn Error>> {
+  let pool = load_descriptor_pool(proto_files, proto_include_paths)?;
 
   for message_desc in pool.all_messages() {
     let message_name = message_desc.full_name();
     if packages.contains(&message_desc.package_name()) {
+      let table_arg = if use_table_mode(message_name) {
+        ", table"
+      } else {
+        ""
+      };
+
+      let example_arg = if generate_example(message_name) {
+        ", example"
+      } else {
+        ""
+      };
+
       let attribute_str = format!(
-        r#"#[::protocheck::macros::protobuf_validate("{}")]"#,
-        message_name
+        r#"#[::protocheck::macros::protobuf_validate("{}"{}{})]"#,
+        message_name, table_arg, example_arg
       );
       config.message_attribute(message_name, &attribute_str);
 
@@ -86,9 +179,123 @@ pub fn compile_protos_with_validators(
     .extern_path(".google.protobuf", "::protocheck::types")
     .compile_well_known_types();
 
+  Ok(())
+}
+
+// Compiles the proto files into a temporary descriptor set, in order to extract the information about the messages and services
+fn load_descriptor_pool(
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+) -> Result<DescriptorPool, Box<dyn Error>> {
+  let out_dir = env::var("OUT_DIR")
+    .map(PathBuf::from)
+    .unwrap_or_else(|_| env::temp_dir());
+
+  let temp_descriptor_path = out_dir.join("temp_file_descriptor_set_for_protocheck.bin");
+  {
+    let mut temp_config = prost_build::Config::new();
+    temp_config.file_descriptor_set_path(&temp_descriptor_path);
+    temp_config.out_dir(&out_dir);
+    temp_config.compile_protos(proto_files, proto_include_paths)?;
+  }
+
+  let mut fds_file = std::fs::File::open(&temp_descriptor_path)?;
+  let mut fds_bytes = Vec::new();
+  fds_file.read_to_end(&mut fds_bytes)?;
+  // Decoding the bytes directly keeps the extension values in the options, such as `(protocheck.skip_validation)`
+  let pool = DescriptorPool::decode(fds_bytes.as_slice())?;
+
   std::fs::remove_file(&temp_descriptor_path)?;
 
-  Ok(())
+  Ok(pool)
+}
+
+/// The type of a Cel variable declared with [`declare_cel_variables`].
+#[cfg(feature = "cel")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CelType {
+  Int,
+  Uint,
+  Double,
+  Bool,
+  String,
+  Bytes,
+  Timestamp,
+  Duration,
+  List,
+  Map,
+}
+
+#[cfg(feature = "cel")]
+impl CelType {
+  fn name(&self) -> &'static str {
+    match self {
+      Self::Int => "int",
+      Self::Uint => "uint",
+      Self::Double => "double",
+      Self::Bool => "bool",
+      Self::String => "string",
+      Self::Bytes => "bytes",
+      Self::Timestamp => "timestamp",
+      Self::Duration => "duration",
+      Self::List => "list",
+      Self::Map => "map",
+    }
+  }
+}
+
+/// Declares the extra variables that will be bound to the Cel programs at runtime with a `ValidationContext`.
+/// This allows the Cel expressions referencing them to be checked at compile time, like the other ones.
+/// It must be called from a build script, as it works by setting an env variable for the crate being compiled.
+#[cfg(feature = "cel")]
+pub fn declare_cel_variables(variables: &[(&str, CelType)]) {
+  let declarations: Vec<String> = variables
+    .iter()
+    .map(|(name, cel_type)| format!("{}:{}", name, cel_type.name()))
+    .collect();
+
+  println!(
+    "cargo:rustc-env=PROTOCHECK_CEL_VARIABLES={}",
+    declarations.join(";")
+  );
+}
+
+/// The signature of a custom Cel function declared with [`declare_cel_functions`].
+#[cfg(feature = "cel")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CelFunction<'a> {
+  pub name: &'a str,
+  pub args: &'a [CelType],
+  pub returns: CelType,
+}
+
+/// Declares the signatures of the custom rust functions that will be registered at runtime with `protocheck::register_cel_functions!`.
+/// This allows the Cel expressions calling them to be checked at compile time, like the other ones.
+/// It must be called from a build script, as it works by setting an env variable for the crate being compiled.
+#[cfg(feature = "cel")]
+pub fn declare_cel_functions(functions: &[CelFunction]) {
+  let declarations: Vec<String> = functions
+    .iter()
+    .map(|function| {
+      let args: Vec<&str> = function
+        .args
+        .iter()
+        .map(|arg| arg.name())
+        .collect();
+
+      format!(
+        "{}({}):{}",
+        function.name,
+        args.join(","),
+        function.returns.name()
+      )
+    })
+    .collect();
+
+  println!(
+    "cargo:rustc-env=PROTOCHECK_CEL_FUNCTIONS={}",
+    declarations.join(";")
+  );
 }
 
 /// A helper to use when gathering the names of proto files to pass to [`prost_build::Config::compile_protos`].
@@ -0,0 +1,212 @@
+use std::{collections::HashSet, error::Error, fmt::Write, path::Path};
+
+use prost_build::{Config, Method, Service, ServiceGenerator};
+use prost_reflect::{DescriptorPool, Value};
+
+use crate::load_descriptor_pool;
+
+/// The full name of the method option that disables the validation of the requests of a method, defined in `protocheck/options.proto` (see [`PROTO_INCLUDE_PATH`](crate::PROTO_INCLUDE_PATH)).
+pub const SKIP_VALIDATION_OPTION: &str = "protocheck.skip_validation";
+
+/// Adds a wrapper type for each service to the code generated by the given service generator (normally the one from `tonic_prost_build::configure().service_generator()`).
+///
+/// For a service named `UserService`, this generates a `ValidatedUserService<T>` which implements the `user_service_server::UserService` trait for any `T` that implements it, calling `validate()` on every request before delegating to `T`. Invalid requests are rejected with an `InvalidArgument` status (see `protocheck::status`), so the `tonic` feature of `protocheck` must be enabled.
+///
+/// Only the request types that belong to one of the `packages` (and therefore have validators) are validated. The validation can be disabled for a method by setting the `(protocheck.skip_validation) = true` option on it. The requests of client-streaming methods are not validated by the wrapper, and should be handled with `protocheck::stream::ValidatedStream` instead.
+pub fn generate_validated_services(
+  config: &mut Config,
+  service_generator: Box<dyn ServiceGenerator>,
+  proto_files: &[impl AsRef<Path>],
+  proto_include_paths: &[impl AsRef<Path>],
+  packages: &[&str],
+) -> Result<(), Box<dyn Error>> {
+  let pool = load_descriptor_pool(proto_files, proto_include_paths)?;
+
+  config.service_generator(Box::new(ValidatedServiceGenerator {
+    inner: service_generator,
+    validated_messages: validated_messages(&pool, packages),
+    skipped_methods: skipped_methods(&pool),
+  }));
+
+  Ok(())
+}
+
+struct ValidatedServiceGenerator {
+  inner: Box<dyn ServiceGenerator>,
+  validated_messages: HashSet<String>,
+  skipped_methods: HashSet<String>,
+}
+
+impl ServiceGenerator for ValidatedServiceGenerator {
+  fn generate(&mut self, service: Service, buf: &mut String) {
+    self.inner.generate(service.clone(), buf);
+    self.generate_wrapper(&service, buf);
+  }
+
+  fn finalize(&mut self, buf: &mut String) {
+    self.inner.finalize(buf);
+  }
+
+  fn finalize_package(&mut self, package: &str, buf: &mut String) {
+    self.inner.finalize_package(package, buf);
+  }
+}
+
+impl ValidatedServiceGenerator {
+  fn generate_wrapper(&self, service: &Service, buf: &mut String) {
+    let trait_name = &service.name;
+    let trait_path = format!("{}_server::{}", naive_snake_case(trait_name), trait_name);
+    let server_path = format!(
+      "{}_server::{}Server",
+      naive_snake_case(trait_name),
+      trait_name
+    );
+    let wrapper_name = format!("Validated{}", trait_name);
+
+    let mut methods = String::new();
+
+    for method in &service.methods {
+      let full_name = method_full_name(service, method);
+
+      let should_validate = !method.client_streaming
+        && !self.skipped_methods.contains(&full_name)
+        && self
+          .validated_messages
+          .contains(method.input_proto_type.trim_start_matches('.'));
+
+      methods.push_str(&method_tokens(method, should_validate));
+    }
+
+    // Writing to a String cannot fail
+    let _ = write!(
+      buf,
+      r#"
+/// Wraps an implementation of [`{trait_path}`] and validates the requests before delegating to it.
+#[derive(Debug, Clone, Default)]
+pub struct {wrapper_name}<T>(pub T);
+
+#[allow(dead_code)]
+impl<T: {trait_path}> {wrapper_name}<T> {{
+    pub fn new(inner: T) -> Self {{
+        Self(inner)
+    }}
+
+    /// Creates the tonic server for this service, which can be added to a router.
+    pub fn into_server(self) -> {server_path}<Self> {{
+        {server_path}::new(self)
+    }}
+}}
+
+#[::tonic::async_trait]
+impl<T: {trait_path}> {trait_path} for {wrapper_name}<T> {{
+{methods}}}
+"#
+    );
+  }
+}
+
+fn method_tokens(method: &Method, should_validate: bool) -> String {
+  let name = &method.name;
+  let input_type = request_type(method);
+  let output_type = &method.output_type;
+
+  let mut tokens = String::new();
+
+  let response_type = if method.server_streaming {
+    let stream_name = format!("{}Stream", method.proto_name);
+
+    let _ = writeln!(tokens, "    type {stream_name} = T::{stream_name};");
+
+    format!("Self::{}", stream_name)
+  } else {
+    output_type.clone()
+  };
+
+  let validation = if should_validate {
+    "        if let Err(violations) = request.get_ref().validate() {\n            return Err(::protocheck::status::IntoStatus::into_status(violations));\n        }\n"
+  } else {
+    ""
+  };
+
+  let _ = write!(
+    tokens,
+    r#"    async fn {name}(
+        &self,
+        request: ::tonic::Request<{input_type}>,
+    ) -> ::std::result::Result<::tonic::Response<{response_type}>, ::tonic::Status> {{
+{validation}        self.0.{name}(request).await
+    }}
+"#
+  );
+
+  tokens
+}
+
+// The full name of a method, as given by the descriptor pool for the skipped methods
+fn method_full_name(service: &Service, method: &Method) -> String {
+  if service.package.is_empty() {
+    format!("{}.{}", service.proto_name, method.proto_name)
+  } else {
+    format!(
+      "{}.{}.{}",
+      service.package, service.proto_name, method.proto_name
+    )
+  }
+}
+
+fn request_type(method: &Method) -> String {
+  if method.client_streaming {
+    format!("::tonic::Streaming<{}>", method.input_type)
+  } else {
+    method.input_type.clone()
+  }
+}
+
+// The messages that get validators from `set_validators_attributes`
+fn validated_messages(pool: &DescriptorPool, packages: &[&str]) -> HashSet<String> {
+  pool
+    .all_messages()
+    .filter(|message| packages.contains(&message.package_name()))
+    .map(|message| message.full_name().to_string())
+    .collect()
+}
+
+fn skipped_methods(pool: &DescriptorPool) -> HashSet<String> {
+  let Some(skip_validation) = pool.get_extension_by_name(SKIP_VALIDATION_OPTION) else {
+    return HashSet::new();
+  };
+
+  pool
+    .services()
+    .flat_map(|service| service.methods().collect::<Vec<_>>())
+    .filter(|method| {
+      matches!(
+        method
+          .options()
+          .get_extension(&skip_validation)
+          .as_ref(),
+        Value::Bool(true)
+      )
+    })
+    .map(|method| method.full_name().to_string())
+    .collect()
+}
+
+// The same conversion used by tonic for the names of the server modules
+fn naive_snake_case(name: &str) -> String {
+  let mut snake_case = String::new();
+  let mut chars = name.chars().peekable();
+
+  while let Some(current) = chars.next() {
+    snake_case.push(current.to_ascii_lowercase());
+
+    if chars
+      .peek()
+      .is_some_and(|next| next.is_uppercase())
+    {
+      snake_case.push('_');
+    }
+  }
+
+  snake_case
+}
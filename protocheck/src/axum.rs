@@ -0,0 +1,126 @@
+use ::axum::{
+  body::Bytes,
+  extract::{rejection::BytesRejection, FromRequest, Request},
+  http::{header::CONTENT_TYPE, StatusCode},
+  response::{IntoResponse, Response},
+};
+use prost::Message;
+use proto_types::protovalidate::Violations;
+use serde::de::DeserializeOwned;
+
+pub use crate::Validated;
+use crate::{
+  render::{ProblemDetails, PROBLEM_JSON_CONTENT_TYPE},
+  ProtoValidator,
+};
+
+/// [`Validated`] can be used as an axum extractor, which decodes the body of a request and validates it.
+///
+/// The body is decoded as JSON when the content type is `application/json` (or ends with `+json`), and as binary protobuf when it is `application/protobuf`, `application/x-protobuf` or `application/octet-stream`. If the message is invalid, the request is rejected with a `422 Unprocessable Entity` response whose body is the problem document rendered by [`ProblemDetails`], and with a `400 Bad Request` if the body cannot be decoded at all.
+///
+/// ```rust,ignore
+/// async fn create_user(user: Validated<User>) -> StatusCode {
+///   // `user` has already passed validation here
+/// }
+/// ```
+impl<T, S> FromRequest<S> for Validated<T>
+where
+  T: ProtoValidator + Message + Default + DeserializeOwned,
+  S: Send + Sync,
+{
+  type Rejection = ValidationRejection;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    let format = BodyFormat::from_content_type(
+      req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok()),
+    )
+    .ok_or(ValidationRejection::UnsupportedContentType)?;
+
+    let body = Bytes::from_request(req, state)
+      .await
+      .map_err(ValidationRejection::Body)?;
+
+    let message = match format {
+      BodyFormat::Json => serde_json::from_slice::<T>(&body).map_err(ValidationRejection::Json)?,
+      BodyFormat::Protobuf => T::decode(body).map_err(ValidationRejection::Protobuf)?,
+    };
+
+    message
+      .into_validated()
+      .map_err(ValidationRejection::Invalid)
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BodyFormat {
+  Json,
+  Protobuf,
+}
+
+impl BodyFormat {
+  fn from_content_type(content_type: Option<&str>) -> Option<Self> {
+    let mime = content_type?
+      .split(';')
+      .next()?
+      .trim()
+      .to_ascii_lowercase();
+
+    if mime == "application/json" || mime.ends_with("+json") {
+      Some(Self::Json)
+    } else if matches!(
+      mime.as_str(),
+      "application/protobuf" | "application/x-protobuf" | "application/octet-stream"
+    ) {
+      Some(Self::Protobuf)
+    } else {
+      None
+    }
+  }
+}
+
+/// The rejection used by the [`Validated`] extractor.
+#[derive(Debug)]
+pub enum ValidationRejection {
+  /// The content type is missing, or it is neither JSON nor protobuf.
+  UnsupportedContentType,
+  /// The body could not be read.
+  Body(BytesRejection),
+  /// The body is not a valid JSON representation of the message.
+  Json(serde_json::Error),
+  /// The body is not a valid protobuf encoding of the message.
+  Protobuf(prost::DecodeError),
+  /// The message was decoded, but it failed validation.
+  Invalid(Violations),
+}
+
+impl IntoResponse for ValidationRejection {
+  fn into_response(self) -> Response {
+    match self {
+      Self::UnsupportedContentType => (
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "expected a JSON or protobuf body",
+      )
+        .into_response(),
+      Self::Body(rejection) => rejection.into_response(),
+      Self::Json(e) => {
+        (StatusCode::BAD_REQUEST, format!("invalid JSON body: {}", e)).into_response()
+      }
+      Self::Protobuf(e) => (
+        StatusCode::BAD_REQUEST,
+        format!("invalid protobuf body: {}", e),
+      )
+        .into_response(),
+      Self::Invalid(violations) => (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        [(CONTENT_TYPE, PROBLEM_JSON_CONTENT_TYPE)],
+        ProblemDetails::new()
+          .render(&violations)
+          .to_string(),
+      )
+        .into_response(),
+    }
+  }
+}
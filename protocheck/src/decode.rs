@@ -0,0 +1,200 @@
+use std::fmt;
+
+use proto_types::protovalidate::Violations;
+#[cfg(feature = "json")]
+use proto_types::protovalidate::{
+  field_path_element::Subscript, FieldPath, FieldPathElement, Violation,
+};
+#[cfg(feature = "json")]
+use protocheck_core::schema::{FieldRulesInfo, MessageRulesInfo};
+#[cfg(feature = "json")]
+use serde_json::error::Category;
+#[cfg(feature = "json")]
+use serde_path_to_error::Segment;
+
+/// The error returned by [`decode_validated`](crate::ProtoValidator::decode_validated) and [`from_json_validated`](crate::ProtoValidator::from_json_validated), which covers both the failure to decode a message and the failure to validate it.
+#[derive(Debug)]
+pub enum ValidationError {
+  /// The bytes are not a valid protobuf encoding of the message. The error message contains the path to the field that could not be decoded.
+  Decode(prost::DecodeError),
+  /// The JSON could not be deserialized into the message.
+  #[cfg(feature = "json")]
+  Json {
+    /// The path to the value that caused the error, such as `items[0].name`, or `.` for the root.
+    path: String,
+    /// The same path as the elements of a field path, which is empty for the root. The fields are resolved with the schemas of the messages, so JSON names are converted to the proto names, and list indices and map keys become the subscripts of their fields.
+    field_path: Vec<FieldPathElement>,
+    error: serde_json::Error,
+  },
+  /// The message was decoded, but it failed validation.
+  Invalid(Violations),
+}
+
+impl ValidationError {
+  /// Returns the violations that caused the error. This includes the errors caused by JSON values that do not match their field (such as an unknown enum variant or a string instead of a number), which are turned into violations with the `json.invalid` id and the message of the error, so that they can be reported like the other violations. Returns `None` for protobuf decoding errors and malformed JSON.
+  pub fn violations(&self) -> Option<Violations> {
+    match self {
+      Self::Decode(_) => None,
+      #[cfg(feature = "json")]
+      Self::Json {
+        field_path, error, ..
+      } => json_violation(field_path, error).map(|violation| Violations {
+        violations: vec![violation],
+      }),
+      Self::Invalid(violations) => Some(violations.clone()),
+    }
+  }
+}
+
+impl fmt::Display for ValidationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Decode(e) => write!(f, "{}", e),
+      #[cfg(feature = "json")]
+      Self::Json { path, error, .. } => write!(f, "invalid JSON at `{}`: {}", path, error),
+      Self::Invalid(violations) => match violations.violations.len() {
+        1 => write!(f, "validation failed with 1 violation"),
+        len => write!(f, "validation failed with {} violations", len),
+      },
+    }
+  }
+}
+
+impl std::error::Error for ValidationError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::Decode(e) => Some(e),
+      #[cfg(feature = "json")]
+      Self::Json { error, .. } => Some(error),
+      Self::Invalid(_) => None,
+    }
+  }
+}
+
+impl From<prost::DecodeError> for ValidationError {
+  fn from(value: prost::DecodeError) -> Self {
+    Self::Decode(value)
+  }
+}
+
+impl From<Violations> for ValidationError {
+  fn from(value: Violations) -> Self {
+    Self::Invalid(value)
+  }
+}
+
+#[cfg(feature = "json")]
+impl ValidationError {
+  // Resolves the path of the error with the schema of the message that was being deserialized
+  pub(crate) fn from_json_error(
+    error: serde_path_to_error::Error<serde_json::Error>,
+    schema: &MessageRulesInfo,
+  ) -> Self {
+    Self::Json {
+      path: error.path().to_string(),
+      field_path: json_path_elements(error.path(), schema),
+      error: error.into_inner(),
+    }
+  }
+}
+
+// Only the errors about the data (and not about the syntax) can be attributed to a field
+#[cfg(feature = "json")]
+fn json_violation(field_path: &[FieldPathElement], error: &serde_json::Error) -> Option<Violation> {
+  if error.classify() != Category::Data {
+    return None;
+  }
+
+  // The position of the error is not relevant to a violation, since the path already points to the value
+  let error_message = error.to_string();
+  let position = format!(" at line {} column {}", error.line(), error.column());
+  let message = error_message
+    .strip_suffix(&position)
+    .unwrap_or(&error_message);
+
+  Some(Violation {
+    field: (!field_path.is_empty()).then(|| FieldPath {
+      elements: field_path.to_vec(),
+    }),
+    rule: None,
+    rule_id: Some("json.invalid".to_string()),
+    message: Some(message.to_string()),
+    for_key: None,
+  })
+}
+
+// Converts the path of a deserialization error into field path elements, where the list indices and the map keys become the subscripts of their fields.
+//
+// Both the fields of a message and the keys of a map are map segments in the path, so the schemas of the messages are followed to tell them apart. The messages that are not linked in the schema (such as the ones in a oneof) are assumed to have no map fields.
+#[cfg(feature = "json")]
+fn json_path_elements(
+  path: &serde_path_to_error::Path,
+  schema: &MessageRulesInfo,
+) -> Vec<FieldPathElement> {
+  let mut elements: Vec<FieldPathElement> = Vec::new();
+  // The message that contains the next field
+  let mut message = Some(schema);
+  // The repeated field or map whose index or key comes next
+  let mut container: Option<&FieldRulesInfo> = None;
+
+  for segment in path.iter() {
+    match segment {
+      Segment::Seq { index } => {
+        if let Some(element) = elements.last_mut() {
+          element.subscript = Some(Subscript::Index(*index as u64));
+        }
+
+        message = container.and_then(FieldRulesInfo::message_schema);
+        container = None;
+      }
+      Segment::Map { key } => {
+        if let Some(map_field) = container.filter(|field| field.is_map) {
+          if let Some(element) = elements.last_mut() {
+            element.subscript = Some(Subscript::StringKey(key.clone()));
+          }
+
+          message = map_field.message_schema();
+          container = None;
+          continue;
+        }
+
+        let field = message.and_then(|message| {
+          message
+            .fields
+            .iter()
+            .find(|field| field.name == key || field.json_name == key)
+        });
+
+        elements.push(FieldPathElement {
+          field_number: field.map(|field| field.tag as i32),
+          field_name: Some(
+            field
+              .map_or(key.as_str(), |field| field.name)
+              .to_string(),
+          ),
+          ..Default::default()
+        });
+
+        // Repeated fields and maps are followed by an index or a key, while singular messages are followed by their own fields
+        match field {
+          Some(field) if field.is_repeated || field.is_map => {
+            message = None;
+            container = Some(field);
+          }
+          Some(field) => {
+            message = field.message_schema();
+            container = None;
+          }
+          None => {
+            message = None;
+            container = None;
+          }
+        }
+      }
+      // The variants of enums and the unknown segments do not correspond to fields
+      _ => {}
+    }
+  }
+
+  elements
+}
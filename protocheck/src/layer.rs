@@ -0,0 +1,350 @@
+use std::{
+  collections::HashMap,
+  fmt,
+  pin::Pin,
+  sync::Arc,
+  task::{ready, Context, Poll},
+};
+
+use bytes::{Bytes, BytesMut};
+use http::{HeaderMap, Request, Response};
+use http_body::Frame;
+use prost::Message;
+use tonic::{body::Body, Code, Status};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{status::violations_to_status_with_code, ProtoValidator};
+
+type Validator = Arc<dyn Fn(&[u8]) -> Result<(), Status> + Send + Sync>;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+// The size of the header of each gRPC message, made of the compression flag and the length
+const GRPC_HEADER_SIZE: usize = 5;
+
+// The same default as tonic's `max_decoding_message_size`
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Clone, Default)]
+struct Validators {
+  requests: HashMap<String, Validator>,
+  responses: HashMap<String, Validator>,
+}
+
+/// A [`Layer`] that validates the messages of a tonic server before they reach the service.
+///
+/// Each validated method is registered with its path (such as `/myapp.v1.UserService/CreateUser`) and the type of its messages. Each message in the body of the matching requests is decoded and validated as soon as it has been received, and only the valid ones are passed on, so this works for streaming methods as well. An invalid request fails with an `InvalidArgument` status that contains the details described in [`violations_to_status`](crate::status::violations_to_status), which tonic returns to the client. Requests for other paths are forwarded untouched.
+///
+/// Only one message is kept in memory at a time, and messages that are longer than the maximum size (4 MiB by default, like tonic's `max_decoding_message_size`) are rejected with a `ResourceExhausted` status before they are read. Compressed messages cannot be decoded by the layer, so they are left to the service.
+///
+/// ```rust,ignore
+/// Server::builder()
+///   .layer(
+///     ValidationLayer::new()
+///       .validate_request::<CreateUserRequest>("/myapp.v1.UserService/CreateUser")
+///       .validate_response::<User>("/myapp.v1.UserService/CreateUser"),
+///   )
+///   .add_service(UserServiceServer::new(service))
+/// ```
+#[derive(Clone)]
+pub struct ValidationLayer {
+  validators: Validators,
+  max_message_size: usize,
+}
+
+impl Default for ValidationLayer {
+  fn default() -> Self {
+    Self {
+      validators: Validators::default(),
+      max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+    }
+  }
+}
+
+impl ValidationLayer {
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the maximum length of the messages that are validated, in bytes. Longer messages are rejected with a `ResourceExhausted` status.
+  #[must_use]
+  pub fn with_max_message_size(mut self, limit: usize) -> Self {
+    self.max_message_size = limit;
+    self
+  }
+
+  /// Validates the requests sent to the given path as messages of type `T`.
+  #[must_use]
+  pub fn validate_request<T>(mut self, path: impl Into<String>) -> Self
+  where
+    T: Message + Default + ProtoValidator + 'static,
+  {
+    self
+      .validators
+      .requests
+      .insert(path.into(), validator::<T>(Code::InvalidArgument));
+    self
+  }
+
+  /// Validates the responses returned for the given path as messages of type `T`. The response ends before the first invalid message, with an `Internal` status in its trailers. This only has an effect in debug builds (with `debug_assertions`), so that the server's own bugs are caught during development without costing anything in production.
+  #[must_use]
+  pub fn validate_response<T>(mut self, path: impl Into<String>) -> Self
+  where
+    T: Message + Default + ProtoValidator + 'static,
+  {
+    if cfg!(debug_assertions) {
+      self
+        .validators
+        .responses
+        .insert(path.into(), validator::<T>(Code::Internal));
+    }
+    self
+  }
+}
+
+impl fmt::Debug for ValidationLayer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ValidationLayer")
+      .field("requests", &self.validators.requests.keys())
+      .field("responses", &self.validators.responses.keys())
+      .field("max_message_size", &self.max_message_size)
+      .finish()
+  }
+}
+
+impl<S> Layer<S> for ValidationLayer {
+  type Service = ValidationService<S>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    ValidationService {
+      inner,
+      validators: Arc::new(self.validators.clone()),
+      max_message_size: self.max_message_size,
+    }
+  }
+}
+
+/// The service created by a [`ValidationLayer`].
+#[derive(Clone)]
+pub struct ValidationService<S> {
+  inner: S,
+  validators: Arc<Validators>,
+  max_message_size: usize,
+}
+
+impl<S: fmt::Debug> fmt::Debug for ValidationService<S> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ValidationService")
+      .field("inner", &self.inner)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<S> Service<Request<Body>> for ValidationService<S>
+where
+  S: Service<Request<Body>, Response = Response<Body>>,
+  S::Future: Send + 'static,
+  S::Error: Send,
+{
+  type Response = Response<Body>;
+  type Error = S::Error;
+  type Future = BoxFuture<Result<Response<Body>, S::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, request: Request<Body>) -> Self::Future {
+    let path = request.uri().path();
+    let request_validator = self.validators.requests.get(path).cloned();
+    let response_validator = self.validators.responses.get(path).cloned();
+    let max_message_size = self.max_message_size;
+
+    let request = match request_validator {
+      Some(validator) => request.map(|body| {
+        Body::new(ValidatingBody::new(
+          body,
+          validator,
+          Side::Request,
+          max_message_size,
+        ))
+      }),
+      None => request,
+    };
+
+    let future = self.inner.call(request);
+
+    Box::pin(async move {
+      let response = future.await?;
+
+      Ok(match response_validator {
+        Some(validator) => response.map(|body| {
+          Body::new(ValidatingBody::new(
+            body,
+            validator,
+            Side::Response,
+            max_message_size,
+          ))
+        }),
+        None => response,
+      })
+    })
+  }
+}
+
+fn validator<T>(code: Code) -> Validator
+where
+  T: Message + Default + ProtoValidator + 'static,
+{
+  Arc::new(move |bytes| match T::decode(bytes) {
+    Ok(message) => message
+      .validate()
+      .map_err(|violations| violations_to_status_with_code(code, &violations.violations)),
+    // Malformed messages are left to the service, which reports its own decoding error
+    Err(_) => Ok(()),
+  })
+}
+
+// Where the messages of a body come from, which decides how an invalid one is reported
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+  // The body yields the status as an error, which tonic returns to the client
+  Request,
+  // The body ends with the status in its trailers, since the response headers have already been sent
+  Response,
+}
+
+// A body that validates each length-prefixed message as soon as it has been received in full, and only yields the data of the messages that have been validated.
+//
+// Only one incomplete message is kept in memory at a time, and messages longer than the maximum size are rejected from their header, before they are read.
+struct ValidatingBody {
+  inner: Body,
+  validator: Validator,
+  side: Side,
+  max_message_size: usize,
+  buffer: BytesMut,
+  ending: Option<Result<HeaderMap, Status>>,
+  is_done: bool,
+}
+
+impl ValidatingBody {
+  fn new(inner: Body, validator: Validator, side: Side, max_message_size: usize) -> Self {
+    Self {
+      inner,
+      validator,
+      side,
+      max_message_size,
+      buffer: BytesMut::new(),
+      ending: None,
+      is_done: false,
+    }
+  }
+
+  // Takes the complete messages from the buffer, up to the first one that is invalid or too large
+  fn take_messages(&mut self) -> (Bytes, Option<Status>) {
+    let mut offset = 0;
+    let mut error = None;
+
+    while let Some(header) = self.buffer.get(offset..offset + GRPC_HEADER_SIZE) {
+      let is_compressed = header[0] != 0;
+      let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+
+      if len > self.max_message_size {
+        error = Some(Status::resource_exhausted(format!(
+          "message length {len} exceeds the maximum of {} bytes",
+          self.max_message_size
+        )));
+        break;
+      }
+
+      let start = offset + GRPC_HEADER_SIZE;
+
+      let Some(message) = self.buffer.get(start..start + len) else {
+        break;
+      };
+
+      // Compressed messages cannot be decoded here, so they are left to the service
+      if !is_compressed && let Err(status) = (self.validator)(message) {
+        error = Some(status);
+        break;
+      }
+
+      offset = start + len;
+    }
+
+    (self.buffer.split_to(offset).freeze(), error)
+  }
+
+  fn end_with(&mut self, status: Status) {
+    self.is_done = true;
+    self.ending = Some(match self.side {
+      Side::Request => Err(status),
+      Side::Response => {
+        let mut trailers = HeaderMap::new();
+
+        status
+          .add_header(&mut trailers)
+          .map(|()| trailers)
+      }
+    });
+  }
+}
+
+impl http_body::Body for ValidatingBody {
+  type Data = Bytes;
+  type Error = Status;
+
+  fn poll_frame(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+    let this = &mut *self;
+
+    loop {
+      if this.is_done {
+        return Poll::Ready(
+          this
+            .ending
+            .take()
+            .map(|ending| ending.map(Frame::trailers)),
+        );
+      }
+
+      let Some(frame) = ready!(Pin::new(&mut this.inner).poll_frame(cx)) else {
+        this.is_done = true;
+
+        // A trailing incomplete message is forwarded as it is, so that the service reports its own decoding error
+        if this.buffer.is_empty() {
+          return Poll::Ready(None);
+        }
+
+        return Poll::Ready(Some(Ok(Frame::data(this.buffer.split().freeze()))));
+      };
+
+      let data = match frame.map(Frame::into_data) {
+        Ok(Ok(data)) => data,
+        // Trailers and errors are forwarded untouched
+        Ok(Err(frame)) => return Poll::Ready(Some(Ok(frame))),
+        Err(status) => return Poll::Ready(Some(Err(status))),
+      };
+
+      this.buffer.extend_from_slice(&data);
+
+      let (messages, error) = this.take_messages();
+
+      if let Some(status) = error {
+        this.end_with(status);
+      }
+
+      if !messages.is_empty() {
+        return Poll::Ready(Some(Ok(Frame::data(messages))));
+      }
+    }
+  }
+
+  fn is_end_stream(&self) -> bool {
+    self.is_done && self.ending.is_none() && self.buffer.is_empty()
+  }
+}
@@ -3,6 +3,23 @@
 //! # Feature flags
 #![doc = document_features::document_features!()]
 
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod decode;
+#[cfg(feature = "tower")]
+pub mod layer;
+#[cfg(feature = "json")]
+pub mod render;
+pub mod span;
+#[cfg(feature = "tonic")]
+pub mod status;
+#[cfg(feature = "tonic")]
+pub mod stream;
+mod validated;
+
+pub use decode::ValidationError;
+pub use validated::Validated;
+
 pub mod types {
   pub use proto_types::*;
 }
@@ -12,9 +29,101 @@ pub trait ProtoValidator {
   /// The method that performs validation on the message struct.
   /// This is available on the generated structs on its own, so you don't need to import the trait unless you want to use it with a generic.
   fn validate(&self) -> Result<(), Violations>;
+
+  /// Performs validation using a [`ValidationContext`], which can be used to bind extra variables to the Cel programs being executed.
+  ///
+  /// The implementations generated by the macro use the context, while the default implementation ignores it and calls [`validate`](ProtoValidator::validate), so that the manual implementations of this trait only need to provide that method.
+  fn validate_with_context(
+    &self,
+    _validation_context: &ValidationContext,
+  ) -> Result<(), Violations> {
+    self.validate()
+  }
+
+  /// Performs validation with the given [`ValidationOptions`], which can limit the nesting depth and the amount of violations, or filter the rules being applied.
+  fn validate_with(&self, options: &ValidationOptions) -> Result<(), Violations> {
+    self.validate_with_context(&ValidationContext::default().with_options(options.clone()))
+  }
+
+  /// Only validates the fields listed in the given [`FieldMask`], along with their sub-paths. See [`ValidationContext::with_field_mask`] for how message-level Cel rules are handled.
+  fn validate_masked(&self, mask: &FieldMask) -> Result<(), Violations> {
+    self.validate_with_context(&ValidationContext::default().with_field_mask(mask))
+  }
+
+  /// Validates the message and stops at the first violation that is found.
+  fn validate_first(&self) -> Result<(), Violation> {
+    let validation_context = ValidationContext::default().with_fail_fast(true);
+
+    match self.validate_with_context(&validation_context) {
+      Ok(()) => Ok(()),
+      Err(Violations { mut violations }) => match violations.pop() {
+        Some(violation) => Err(violation),
+        None => Ok(()),
+      },
+    }
+  }
+
+  /// Returns `true` if the message passes validation. Like [`validate_first`](ProtoValidator::validate_first), this stops at the first violation.
+  fn is_valid(&self) -> bool {
+    self.validate_first().is_ok()
+  }
+
+  /// Validates the message and wraps it in [`Validated`], which can be used to require messages that are known to be valid.
+  fn into_validated(self) -> Result<Validated<Self>, Violations>
+  where
+    Self: Sized,
+  {
+    Validated::new(self)
+  }
+
+  /// Decodes a message from its protobuf encoding and validates it.
+  fn decode_validated(buf: impl prost::bytes::Buf) -> Result<Validated<Self>, ValidationError>
+  where
+    Self: prost::Message + Default + Sized,
+  {
+    Ok(Self::decode(buf)?.into_validated()?)
+  }
+
+  /// Deserializes a message from JSON and validates it. If the JSON does not match the message, the error contains the path to the value that caused it.
+  #[cfg(feature = "json")]
+  fn from_json_validated(json: &str) -> Result<Validated<Self>, ValidationError>
+  where
+    Self: serde::de::DeserializeOwned + ValidationSchema + Sized,
+  {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let message: Self = serde_path_to_error::deserialize(&mut deserializer)
+      .map_err(|error| ValidationError::from_json_error(error, Self::validation_schema()))?;
+
+    deserializer
+      .end()
+      .map_err(|error| ValidationError::Json {
+        path: ".".to_string(),
+        field_path: Vec::new(),
+        error,
+      })?;
+
+    Ok(message.into_validated()?)
+  }
+}
+
+/// The trait for the messages that describe their rules at runtime. It is implemented by the `protobuf_validate` macro, and it is separate from [`ProtoValidator`] so that the manual implementations of that trait do not need to provide a schema.
+pub trait ValidationSchema {
+  /// Returns the static description of the rules defined for this message, its fields and its oneofs.
+  fn validation_schema() -> &'static MessageRulesInfo;
 }
 
-use proto_types::protovalidate::Violations;
+#[cfg(feature = "proptest")]
+pub use proptest;
+use proto_types::{
+  protovalidate::{Violation, Violations},
+  FieldMask,
+};
 pub use protocheck_core::*;
+use protocheck_core::{
+  context::{ValidationContext, ValidationOptions},
+  schema::MessageRulesInfo,
+};
 #[doc(inline)]
 pub use protocheck_proc_macro as macros;
+#[cfg(feature = "tonic")]
+pub use tonic;
@@ -0,0 +1,301 @@
+use proto_types::{
+  field_descriptor_proto::Type as ProtoType,
+  protovalidate::{field_path_element::Subscript, FieldPath, Violation, Violations},
+};
+use protocheck_core::schema::{FieldRulesInfo, MessageRulesInfo};
+use serde_json::{json, Map, Value};
+
+use crate::span::{default_json_name, subscript_segment};
+
+/// The content type of the documents produced by [`ProblemDetails`].
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Renders the violations in the canonical JSON form of `buf.validate.Violations`, as defined by the proto3 JSON mapping (with camelCase keys, enum names and 64 bit integers as strings), which is the same output as the other protovalidate implementations.
+pub fn violations_to_json(violations: &Violations) -> Value {
+  let violations: Vec<Value> = violations
+    .violations
+    .iter()
+    .map(violation_to_json)
+    .collect();
+
+  json!({ "violations": violations })
+}
+
+/// Renders a single violation in the canonical JSON form of `buf.validate.Violation`.
+pub fn violation_to_json(violation: &Violation) -> Value {
+  let mut object = Map::new();
+
+  if let Some(field) = &violation.field {
+    object.insert("field".to_string(), field_path_to_json(field));
+  }
+
+  if let Some(rule) = &violation.rule {
+    object.insert("rule".to_string(), field_path_to_json(rule));
+  }
+
+  if let Some(rule_id) = &violation.rule_id {
+    object.insert("ruleId".to_string(), json!(rule_id));
+  }
+
+  if let Some(message) = &violation.message {
+    object.insert("message".to_string(), json!(message));
+  }
+
+  if let Some(for_key) = violation.for_key {
+    object.insert("forKey".to_string(), json!(for_key));
+  }
+
+  Value::Object(object)
+}
+
+fn field_path_to_json(path: &FieldPath) -> Value {
+  let elements: Vec<Value> = path
+    .elements
+    .iter()
+    .map(|element| {
+      let mut object = Map::new();
+
+      if let Some(field_number) = element.field_number {
+        object.insert("fieldNumber".to_string(), json!(field_number));
+      }
+
+      if let Some(field_name) = &element.field_name {
+        object.insert("fieldName".to_string(), json!(field_name));
+      }
+
+      for (key, field_type) in [
+        ("fieldType", element.field_type),
+        ("keyType", element.key_type),
+        ("valueType", element.value_type),
+      ] {
+        if let Some(field_type) = field_type {
+          object.insert(key.to_string(), proto_type_to_json(field_type));
+        }
+      }
+
+      let subscript = match &element.subscript {
+        Some(Subscript::Index(index)) => Some(("index", json!(index.to_string()))),
+        Some(Subscript::BoolKey(key)) => Some(("boolKey", json!(key))),
+        Some(Subscript::IntKey(key)) => Some(("intKey", json!(key.to_string()))),
+        Some(Subscript::UintKey(key)) => Some(("uintKey", json!(key.to_string()))),
+        Some(Subscript::StringKey(key)) => Some(("stringKey", json!(key))),
+        None => None,
+      };
+
+      if let Some((key, value)) = subscript {
+        object.insert(key.to_string(), value);
+      }
+
+      Value::Object(object)
+    })
+    .collect();
+
+  json!({ "elements": elements })
+}
+
+// Enums are rendered with their names, unless the value is unknown
+fn proto_type_to_json(value: i32) -> Value {
+  match ProtoType::try_from(value) {
+    Ok(proto_type) => json!(proto_type.as_str_name()),
+    Err(_) => json!(value),
+  }
+}
+
+/// The names used for the fields in a JSON Pointer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FieldNames {
+  /// The names of the fields in the proto file, such as `user_name`.
+  #[default]
+  Proto,
+  /// The JSON names of the fields, such as `userName`. Custom `json_name` options are not available in the violations, so they are only used when the schema of the message is given (see [`field_path_json_pointer`]), and otherwise the default conversion to lowerCamelCase is used.
+  Json,
+}
+
+/// Converts a field path into a JSON Pointer (RFC 6901), such as `/items/0/name` or `/labels/env`, where list indices and map keys are separate segments. An empty path (for the rules of the message as a whole) becomes an empty pointer, which refers to the whole document.
+pub fn field_path_pointer(path: &FieldPath, names: FieldNames) -> String {
+  match names {
+    FieldNames::Proto => build_pointer(path, |field_name| field_name.to_string()),
+    FieldNames::Json => build_pointer(path, default_json_name),
+  }
+}
+
+/// Converts a field path into a JSON Pointer with the JSON names of the fields, as defined in the schema of the message (as returned by the generated `validation_schema` method), so that custom `json_name` options are used as well. The schemas of the nested messages are followed through the fields, and the fields that are not found in them fall back to the default JSON names.
+pub fn field_path_json_pointer(path: &FieldPath, schema: &MessageRulesInfo) -> String {
+  // The message that contains the next field
+  let mut message = Some(schema);
+
+  build_pointer(path, |field_name| {
+    let field = message.and_then(|message| message.field(field_name));
+    message = field.and_then(FieldRulesInfo::message_schema);
+
+    field.map_or_else(
+      || default_json_name(field_name),
+      |field| field.json_name.to_string(),
+    )
+  })
+}
+
+fn build_pointer(path: &FieldPath, mut segment_name: impl FnMut(&str) -> String) -> String {
+  let mut pointer = String::new();
+
+  for element in &path.elements {
+    if let Some(field_name) = &element.field_name {
+      pointer.push('/');
+      push_escaped(&mut pointer, &segment_name(field_name));
+    }
+
+    if let Some(subscript) = &element.subscript {
+      pointer.push('/');
+      push_escaped(&mut pointer, &subscript_segment(subscript));
+    }
+  }
+
+  pointer
+}
+
+// The reference tokens of a JSON Pointer must escape `~` and `/`
+fn push_escaped(pointer: &mut String, segment: &str) {
+  for c in segment.chars() {
+    match c {
+      '~' => pointer.push_str("~0"),
+      '/' => pointer.push_str("~1"),
+      _ => pointer.push(c),
+    }
+  }
+}
+
+/// A renderer for RFC 9457 problem documents, to be sent with the [`PROBLEM_JSON_CONTENT_TYPE`] content type.
+///
+/// The violations are listed in the `errors` extension member, as objects with the `pointer` to the field (see [`field_path_pointer`]), the `rule` id and the `message`.
+///
+/// ```rust,ignore
+/// let body = ProblemDetails::new()
+///   .with_field_names(FieldNames::Json)
+///   .with_instance("/users")
+///   .render(&violations);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProblemDetails {
+  type_uri: String,
+  title: String,
+  status: u16,
+  detail: Option<String>,
+  instance: Option<String>,
+  field_names: FieldNames,
+  schema: Option<&'static MessageRulesInfo>,
+}
+
+impl Default for ProblemDetails {
+  fn default() -> Self {
+    Self {
+      type_uri: "about:blank".to_string(),
+      title: "Unprocessable Content".to_string(),
+      status: 422,
+      detail: None,
+      instance: None,
+      field_names: FieldNames::Proto,
+      schema: None,
+    }
+  }
+}
+
+impl ProblemDetails {
+  /// Creates a renderer with the `about:blank` type and the `422` status.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  #[must_use]
+  pub fn with_type(mut self, type_uri: impl Into<String>) -> Self {
+    self.type_uri = type_uri.into();
+    self
+  }
+
+  #[must_use]
+  pub fn with_title(mut self, title: impl Into<String>) -> Self {
+    self.title = title.into();
+    self
+  }
+
+  /// Sets the HTTP status code. With the `about:blank` type, the title should be changed accordingly (i.e. `Bad Request` for `400`).
+  #[must_use]
+  pub fn with_status(mut self, status: u16) -> Self {
+    self.status = status;
+    self
+  }
+
+  /// Sets the `detail` member. By default, it is a short summary of the violations.
+  #[must_use]
+  pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+    self.detail = Some(detail.into());
+    self
+  }
+
+  #[must_use]
+  pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+    self.instance = Some(instance.into());
+    self
+  }
+
+  /// Sets the names used for the fields in the pointers.
+  #[must_use]
+  pub fn with_field_names(mut self, field_names: FieldNames) -> Self {
+    self.field_names = field_names;
+    self
+  }
+
+  /// Sets the schema of the message (as returned by the generated `validation_schema` method), so that the custom `json_name` options are used in the pointers with [`FieldNames::Json`].
+  #[must_use]
+  pub fn with_schema(mut self, schema: &'static MessageRulesInfo) -> Self {
+    self.schema = Some(schema);
+    self
+  }
+
+  /// Renders the problem document for the given violations.
+  pub fn render(&self, violations: &Violations) -> Value {
+    let violations = &violations.violations;
+
+    let detail = self
+      .detail
+      .clone()
+      .unwrap_or_else(|| match violations.len() {
+        1 => format!("validation failed: {}", violations[0].message()),
+        len => format!("validation failed with {} violations", len),
+      });
+
+    let errors: Vec<Value> = violations
+      .iter()
+      .map(|violation| {
+        let pointer = violation
+          .field
+          .as_ref()
+          .map(|field| match (self.field_names, self.schema) {
+            (FieldNames::Json, Some(schema)) => field_path_json_pointer(field, schema),
+            (field_names, _) => field_path_pointer(field, field_names),
+          })
+          .unwrap_or_default();
+
+        json!({
+          "pointer": pointer,
+          "rule": violation.rule_id(),
+          "message": violation.message(),
+        })
+      })
+      .collect();
+
+    let mut document = json!({
+      "type": self.type_uri,
+      "title": self.title,
+      "status": self.status,
+      "detail": detail,
+    });
+
+    if let Some(instance) = &self.instance {
+      document["instance"] = json!(instance);
+    }
+
+    document["errors"] = json!(errors);
+
+    document
+  }
+}
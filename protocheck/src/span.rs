@@ -0,0 +1,431 @@
+use std::ops::Range;
+
+use proto_types::protovalidate::{
+  field_path_element::Subscript, FieldPathElement, Violation, Violations,
+};
+use protocheck_core::schema::{FieldRulesInfo, MessageRulesInfo};
+
+/// The position of a value in a JSON document.
+///
+/// The byte range can be used directly for the labels of diagnostics libraries, such as `miette::LabeledSpan::at(span.range(), ..)` or `ariadne::Label::new(span.range())`, and it also converts into the `(offset, length)` pair accepted by `miette::SourceSpan`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JsonSpan {
+  /// The byte offset where the value starts.
+  pub start: usize,
+  /// The byte offset right after the end of the value.
+  pub end: usize,
+  /// The line where the value starts, starting from 1.
+  pub line: usize,
+  /// The column where the value starts (in characters), starting from 1.
+  pub column: usize,
+}
+
+impl JsonSpan {
+  pub fn range(&self) -> Range<usize> {
+    self.start..self.end
+  }
+
+  pub fn len(&self) -> usize {
+    self.end - self.start
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.start == self.end
+  }
+}
+
+impl From<JsonSpan> for Range<usize> {
+  fn from(value: JsonSpan) -> Self {
+    value.range()
+  }
+}
+
+impl From<JsonSpan> for (usize, usize) {
+  fn from(value: JsonSpan) -> Self {
+    (value.start, value.len())
+  }
+}
+
+/// A violation along with the position of the value that caused it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViolationSpan<'a> {
+  pub violation: &'a Violation,
+  pub span: JsonSpan,
+  /// `false` if the violating value is not in the document (as with a missing required field), in which case the span points to the closest parent that is present.
+  pub is_exact: bool,
+}
+
+/// Finds the values that caused the violations in the JSON document that the message was deserialized from.
+///
+/// The fields are matched by their proto name, by their default JSON name (in lowerCamelCase) and by their custom `json_name` options, which are taken from the schema of the message given with [`with_schema`](JsonLocator::with_schema) or registered with [`with_json_name`](JsonLocator::with_json_name). List indices and map keys are followed as well, and the violations of map keys point to the key itself rather than to the value.
+///
+/// ```rust,ignore
+/// let locator = JsonLocator::new(&json).with_schema(User::validation_schema());
+///
+/// for located in locator.locate_all(&violations) {
+///   println!("{}:{}: {}", located.span.line, located.span.column, located.violation.message());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct JsonLocator<'a> {
+  json: &'a str,
+  json_names: Vec<(String, String)>,
+  schema: Option<&'a MessageRulesInfo>,
+}
+
+impl<'a> JsonLocator<'a> {
+  pub fn new(json: &'a str) -> Self {
+    Self {
+      json,
+      json_names: Vec::new(),
+      schema: None,
+    }
+  }
+
+  /// Registers a custom JSON name for a field. Since the violations only contain the proto names of the fields, this applies to all the fields with this name, at any depth, so [`with_schema`](JsonLocator::with_schema) should be preferred for the generated messages. This is meant for the messages that are not linked in the schema, such as the ones in a oneof.
+  #[must_use]
+  pub fn with_json_name(
+    mut self,
+    field_name: impl Into<String>,
+    json_name: impl Into<String>,
+  ) -> Self {
+    self
+      .json_names
+      .push((field_name.into(), json_name.into()));
+    self
+  }
+
+  /// Sets the schema of the message that the document represents, as returned by the generated `validation_schema` method. The custom JSON name of each field is looked up in the schema of the message that contains it, following the schemas of the nested messages, so fields with the same name in different messages can have different JSON names.
+  #[must_use]
+  pub fn with_schema(mut self, schema: &'a MessageRulesInfo) -> Self {
+    self.schema = Some(schema);
+    self
+  }
+
+  /// Finds the positions of all the violations. The document is only scanned along the path of each violation, so a document that is not entirely valid JSON (such as one with an invalid value in another field) still produces the spans of the values that can be reached. The result is only empty when the root value cannot be found, such as when its brackets are not balanced.
+  pub fn locate_all<'v>(&self, violations: &'v Violations) -> Vec<ViolationSpan<'v>> {
+    violations
+      .violations
+      .iter()
+      .filter_map(|violation| self.locate(violation))
+      .collect()
+  }
+
+  /// Finds the position of the value that caused a violation. The violations without a field path (such as those of the message-level Cel rules) point to the whole document.
+  pub fn locate<'v>(&self, violation: &'v Violation) -> Option<ViolationSpan<'v>> {
+    let elements = violation
+      .field
+      .as_ref()
+      .map_or(&[][..], |field| field.elements.as_slice());
+
+    let (range, is_exact) = self.walk(elements, violation.for_key())?;
+
+    Some(ViolationSpan {
+      violation,
+      span: self.span(range),
+      is_exact,
+    })
+  }
+
+  // Follows the path from the root value, and stops at the last value that could be found
+  fn walk(&self, elements: &[FieldPathElement], for_key: bool) -> Option<(Range<usize>, bool)> {
+    let json = self.json;
+    let root_start = skip_whitespace(json, 0);
+    let mut current = root_start..value_end(json, root_start)?;
+    // The message that contains the next field
+    let mut message = self.schema;
+
+    for (i, element) in elements.iter().enumerate() {
+      let is_last = i == elements.len() - 1;
+
+      if let Some(field_name) = &element.field_name {
+        let field = message.and_then(|message| message.field(field_name));
+        message = field.and_then(FieldRulesInfo::message_schema);
+
+        match find_member(json, current.start, |key| {
+          self.is_field_key(key, field_name, field)
+        }) {
+          Some(member) => current = member.value,
+          None => return Some((current, false)),
+        }
+      }
+
+      if let Some(subscript) = &element.subscript {
+        let next = if let Subscript::Index(index) = subscript {
+          find_element(json, current.start, *index)
+        } else {
+          let map_key = subscript_segment(subscript);
+
+          find_member(json, current.start, |key| key == map_key).map(|member| {
+            if is_last && for_key {
+              member.key
+            } else {
+              member.value
+            }
+          })
+        };
+
+        match next {
+          Some(range) => current = range,
+          None => return Some((current, false)),
+        }
+      }
+    }
+
+    Some((current, true))
+  }
+
+  fn is_field_key(&self, key: &str, field_name: &str, field: Option<&FieldRulesInfo>) -> bool {
+    key == field_name
+      || key == default_json_name(field_name)
+      || field.is_some_and(|field| field.json_name == key)
+      || self
+        .json_names
+        .iter()
+        .any(|(name, json_name)| name == field_name && json_name == key)
+  }
+
+  fn span(&self, range: Range<usize>) -> JsonSpan {
+    let before = &self.json[..range.start];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    JsonSpan {
+      start: range.start,
+      end: range.end,
+      line: before.matches('\n').count() + 1,
+      column: before[line_start..].chars().count() + 1,
+    }
+  }
+}
+
+/// The conversion used by protoc for the default `json_name` of a field, which turns `user_name` into `userName`.
+pub(crate) fn default_json_name(field_name: &str) -> String {
+  let mut name = String::with_capacity(field_name.len());
+  let mut capitalize_next = false;
+
+  for c in field_name.chars() {
+    if c == '_' {
+      capitalize_next = true;
+    } else if capitalize_next {
+      name.push(c.to_ascii_uppercase());
+      capitalize_next = false;
+    } else {
+      name.push(c);
+    }
+  }
+
+  name
+}
+
+/// Converts a list index or a map key into a string, as it appears in a JSON Pointer or as the key of a JSON object.
+pub(crate) fn subscript_segment(subscript: &Subscript) -> String {
+  match subscript {
+    Subscript::Index(index) => index.to_string(),
+    Subscript::BoolKey(key) => key.to_string(),
+    Subscript::IntKey(key) => key.to_string(),
+    Subscript::UintKey(key) => key.to_string(),
+    Subscript::StringKey(key) => key.clone(),
+  }
+}
+
+struct Member {
+  key: Range<usize>,
+  value: Range<usize>,
+}
+
+fn skip_whitespace(json: &str, mut pos: usize) -> usize {
+  let bytes = json.as_bytes();
+
+  while bytes
+    .get(pos)
+    .is_some_and(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+  {
+    pos += 1;
+  }
+
+  pos
+}
+
+// Returns the position right after the value that starts at `start`
+fn value_end(json: &str, start: usize) -> Option<usize> {
+  let bytes = json.as_bytes();
+
+  match bytes.get(start)? {
+    b'"' => string_end(json, start),
+    b'{' | b'[' => {
+      let mut depth = 0usize;
+      let mut pos = start;
+
+      while let Some(b) = bytes.get(pos) {
+        match b {
+          b'"' => {
+            pos = string_end(json, pos)?;
+            continue;
+          }
+          b'{' | b'[' => depth += 1,
+          b'}' | b']' => {
+            depth -= 1;
+
+            if depth == 0 {
+              return Some(pos + 1);
+            }
+          }
+          _ => {}
+        }
+
+        pos += 1;
+      }
+
+      None
+    }
+    // Numbers, booleans and null
+    _ => {
+      let mut pos = start;
+
+      while bytes
+        .get(pos)
+        .is_some_and(|b| !matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r'))
+      {
+        pos += 1;
+      }
+
+      (pos > start).then_some(pos)
+    }
+  }
+}
+
+// The escaped characters are skipped in pairs, so that an escaped quote does not end the string. Bytes of multi-byte characters can never be mistaken for quotes or backslashes.
+fn string_end(json: &str, start: usize) -> Option<usize> {
+  let bytes = json.as_bytes();
+  let mut pos = start + 1;
+
+  loop {
+    match bytes.get(pos)? {
+      b'\\' => pos += 2,
+      b'"' => return Some(pos + 1),
+      _ => pos += 1,
+    }
+  }
+}
+
+// Returns the end of the string that starts at `start` and its decoded content
+fn parse_string(json: &str, start: usize) -> Option<(usize, String)> {
+  if json.as_bytes().get(start) != Some(&b'"') {
+    return None;
+  }
+
+  let mut decoded = String::new();
+  let mut chars = json[start + 1..].char_indices();
+
+  while let Some((offset, c)) = chars.next() {
+    match c {
+      '"' => return Some((start + 1 + offset + 1, decoded)),
+      '\\' => match chars.next()?.1 {
+        'n' => decoded.push('\n'),
+        't' => decoded.push('\t'),
+        'r' => decoded.push('\r'),
+        'b' => decoded.push('\u{8}'),
+        'f' => decoded.push('\u{c}'),
+        'u' => {
+          let mut code = hex_code(&mut chars)?;
+
+          // Characters outside of the basic plane are escaped as surrogate pairs
+          if (0xD800..0xDC00).contains(&code) && chars.as_str().starts_with("\\u") {
+            chars.next();
+            chars.next();
+
+            let low = hex_code(&mut chars)?;
+            code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+          }
+
+          decoded.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+        other => decoded.push(other),
+      },
+      _ => decoded.push(c),
+    }
+  }
+
+  None
+}
+
+fn hex_code(chars: &mut std::str::CharIndices<'_>) -> Option<u32> {
+  let digits: String = chars.take(4).map(|(_, c)| c).collect();
+
+  u32::from_str_radix(&digits, 16).ok()
+}
+
+fn find_member(json: &str, object_start: usize, is_match: impl Fn(&str) -> bool) -> Option<Member> {
+  let bytes = json.as_bytes();
+
+  if bytes.get(object_start) != Some(&b'{') {
+    return None;
+  }
+
+  let mut pos = skip_whitespace(json, object_start + 1);
+
+  if bytes.get(pos) == Some(&b'}') {
+    return None;
+  }
+
+  loop {
+    let (key_end, key) = parse_string(json, pos)?;
+    let key_range = pos..key_end;
+
+    pos = skip_whitespace(json, key_end);
+
+    if bytes.get(pos) != Some(&b':') {
+      return None;
+    }
+
+    let value_start = skip_whitespace(json, pos + 1);
+    let value_end = value_end(json, value_start)?;
+
+    if is_match(&key) {
+      return Some(Member {
+        key: key_range,
+        value: value_start..value_end,
+      });
+    }
+
+    pos = skip_whitespace(json, value_end);
+
+    if bytes.get(pos) != Some(&b',') {
+      return None;
+    }
+
+    pos = skip_whitespace(json, pos + 1);
+  }
+}
+
+fn find_element(json: &str, array_start: usize, index: u64) -> Option<Range<usize>> {
+  let bytes = json.as_bytes();
+
+  if bytes.get(array_start) != Some(&b'[') {
+    return None;
+  }
+
+  let mut pos = skip_whitespace(json, array_start + 1);
+  let mut current = 0;
+
+  if bytes.get(pos) == Some(&b']') {
+    return None;
+  }
+
+  loop {
+    let end = value_end(json, pos)?;
+
+    if current == index {
+      return Some(pos..end);
+    }
+
+    pos = skip_whitespace(json, end);
+
+    if bytes.get(pos) != Some(&b',') {
+      return None;
+    }
+
+    pos = skip_whitespace(json, pos + 1);
+    current += 1;
+  }
+}
@@ -0,0 +1,109 @@
+use bytes::Bytes;
+use prost::Message;
+use proto_types::{
+  bad_request::FieldViolation,
+  protovalidate::{Violation, Violations},
+  Any, BadRequest, ErrorInfo, Status,
+};
+use tonic::Code;
+
+/// The domain used for the [`ErrorInfo`] details that carry the rule ids.
+pub const ERROR_INFO_DOMAIN: &str = "buf.validate";
+
+/// Converts violations into a [`tonic::Status`] with the `InvalidArgument` code.
+///
+/// This is a trait rather than a `From` implementation because both [`Violations`] and [`tonic::Status`] are foreign types, so `Violations::into_status` (or `.map_err(IntoStatus::into_status)`) is the way to do the conversion.
+pub trait IntoStatus {
+  fn into_status(self) -> tonic::Status;
+}
+
+impl IntoStatus for Violations {
+  fn into_status(self) -> tonic::Status {
+    violations_to_status(&self.violations)
+  }
+}
+
+impl IntoStatus for Violation {
+  fn into_status(self) -> tonic::Status {
+    violations_to_status(std::slice::from_ref(&self))
+  }
+}
+
+/// Builds an `InvalidArgument` [`tonic::Status`] with the details encoded in the `grpc-status-details-bin` trailer, as a `google.rpc.Status`.
+///
+/// The details contain a `google.rpc.BadRequest` with a `FieldViolation` for each violation, using the field path (such as `items[0].name`) and the message of the violation. They are followed by an `ErrorInfo` for each violation, in the same order, where the `reason` is the rule id and the `metadata` contains the `field` path, so that clients can tell which rule was violated without parsing the messages.
+pub fn violations_to_status(violations: &[Violation]) -> tonic::Status {
+  violations_to_status_with_code(Code::InvalidArgument, violations)
+}
+
+/// Like [`violations_to_status`], but with a custom [`Code`], such as `Internal` for messages produced by the server itself.
+pub fn violations_to_status_with_code(code: Code, violations: &[Violation]) -> tonic::Status {
+  build_status(code, violations, None)
+}
+
+// Builds the status, adding the position of the message to the message and to the metadata of the ErrorInfo details when it comes from a stream
+pub(crate) fn build_status(
+  code: Code,
+  violations: &[Violation],
+  stream_index: Option<u64>,
+) -> tonic::Status {
+  let subject = match stream_index {
+    Some(index) => format!("validation failed for message {} of the stream", index),
+    None => "validation failed".to_string(),
+  };
+
+  let message = match violations.len() {
+    1 => format!("{}: {}", subject, violations[0].message()),
+    len => format!("{} with {} violations", subject, len),
+  };
+
+  let field_violations = violations
+    .iter()
+    .map(|violation| FieldViolation {
+      field: violation.field_path_str().unwrap_or_default(),
+      description: violation.message().to_string(),
+      ..Default::default()
+    })
+    .collect();
+
+  let mut details = vec![any(
+    "google.rpc.BadRequest",
+    &BadRequest { field_violations },
+  )];
+
+  details.extend(violations.iter().map(|violation| {
+    let mut metadata = std::collections::HashMap::new();
+
+    if let Some(field) = violation.field_path_str() {
+      metadata.insert("field".to_string(), field);
+    }
+
+    if let Some(index) = stream_index {
+      metadata.insert("stream_index".to_string(), index.to_string());
+    }
+
+    any(
+      "google.rpc.ErrorInfo",
+      &ErrorInfo {
+        reason: violation.rule_id().to_string(),
+        domain: ERROR_INFO_DOMAIN.to_string(),
+        metadata,
+      },
+    )
+  }));
+
+  let status = Status {
+    code: code as i32,
+    message: message.clone(),
+    details,
+  };
+
+  tonic::Status::with_details(code, message, Bytes::from(status.encode_to_vec()))
+}
+
+fn any(type_name: &str, message: &impl Message) -> Any {
+  Any {
+    type_url: format!("type.googleapis.com/{}", type_name),
+    value: message.encode_to_vec(),
+  }
+}
@@ -0,0 +1,143 @@
+use std::{
+  pin::Pin,
+  task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use tonic::{Code, Status};
+
+use crate::{status::build_status, ProtoValidator};
+
+/// What a [`ValidatedStream`] does when it receives an invalid message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnInvalid {
+  /// Yields the error and ends the stream.
+  #[default]
+  End,
+  /// Yields the error in place of the invalid message and keeps going, so that the caller can report it and move on to the next message.
+  Skip,
+}
+
+/// A stream adapter that validates each message of a stream, such as the [`tonic::Streaming`] of a client-streaming or bidirectional method.
+///
+/// Invalid messages are replaced with an `InvalidArgument` status, with the same details as [`violations_to_status`](crate::status::violations_to_status), along with the position of the message in the stream (starting from 0) in the status message and in the `stream_index` metadata of the `ErrorInfo` details. By default, the stream ends after the first invalid message. The errors that come from the inner stream are passed through, and they do not count as messages.
+///
+/// The inner stream must be [`Unpin`], which is the case for [`tonic::Streaming`]. Other streams can be wrapped with [`Box::pin`].
+///
+/// ```rust,ignore
+/// async fn upload(&self, request: Request<Streaming<Chunk>>) -> Result<Response<Summary>, Status> {
+///   let mut chunks = ValidatedStream::new(request.into_inner());
+///
+///   while let Some(chunk) = chunks.message().await? {
+///     // ...
+///   }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ValidatedStream<S> {
+  inner: S,
+  on_invalid: OnInvalid,
+  index: u64,
+  invalid_count: u64,
+  is_finished: bool,
+}
+
+impl<S> ValidatedStream<S> {
+  pub fn new(inner: S) -> Self {
+    Self {
+      inner,
+      on_invalid: OnInvalid::End,
+      index: 0,
+      invalid_count: 0,
+      is_finished: false,
+    }
+  }
+
+  /// Sets what happens when an invalid message is received.
+  #[must_use]
+  pub fn on_invalid(mut self, on_invalid: OnInvalid) -> Self {
+    self.on_invalid = on_invalid;
+    self
+  }
+
+  /// Shorthand for `on_invalid(OnInvalid::Skip)`.
+  #[must_use]
+  pub fn skip_invalid(self) -> Self {
+    self.on_invalid(OnInvalid::Skip)
+  }
+
+  /// The amount of messages received so far, both valid and invalid.
+  pub fn received_count(&self) -> u64 {
+    self.index
+  }
+
+  /// The amount of invalid messages received so far.
+  pub fn invalid_count(&self) -> u64 {
+    self.invalid_count
+  }
+
+  pub fn into_inner(self) -> S {
+    self.inner
+  }
+}
+
+impl<S, T> ValidatedStream<S>
+where
+  S: Stream<Item = Result<T, Status>> + Unpin,
+  T: ProtoValidator,
+{
+  /// Returns the next valid message, like [`tonic::Streaming::message`]. The invalid messages are returned as errors.
+  pub async fn message(&mut self) -> Result<Option<T>, Status> {
+    std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx))
+      .await
+      .transpose()
+  }
+}
+
+impl<S, T> Stream for ValidatedStream<S>
+where
+  S: Stream<Item = Result<T, Status>> + Unpin,
+  T: ProtoValidator,
+{
+  type Item = Result<T, Status>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    if self.is_finished {
+      return Poll::Ready(None);
+    }
+
+    let item = ready!(Pin::new(&mut self.inner).poll_next(cx));
+
+    let Some(Ok(message)) = item else {
+      return Poll::Ready(item);
+    };
+
+    let index = self.index;
+    self.index += 1;
+
+    match message.validate() {
+      Ok(()) => Poll::Ready(Some(Ok(message))),
+      Err(violations) => {
+        self.invalid_count += 1;
+
+        if self.on_invalid == OnInvalid::End {
+          self.is_finished = true;
+        }
+
+        Poll::Ready(Some(Err(build_status(
+          Code::InvalidArgument,
+          &violations.violations,
+          Some(index),
+        ))))
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    if self.is_finished {
+      (0, Some(0))
+    } else {
+      self.inner.size_hint()
+    }
+  }
+}
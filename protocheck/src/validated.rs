@@ -0,0 +1,168 @@
+use std::{fmt::Write, ops::Deref};
+
+use prost::{
+  bytes::{Buf, BufMut},
+  encoding::{DecodeContext, WireType},
+  DecodeError,
+};
+use proto_types::protovalidate::Violations;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ProtoValidator;
+
+/// A message that is known to have passed validation.
+///
+/// It can only be created with [`ProtoValidator::into_validated`] (or by deserializing it, which runs the validation as well), so functions that take a `Validated<T>` can rely on their input being valid. It gives read-only access to the message through [`Deref`], while [`into_inner`](Validated::into_inner) gives back the message itself for modifications (which then require a new validation). It also implements [`prost::Message`] itself, so it can be used where a message type is expected, such as in tonic codecs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Validated<T>(T);
+
+impl<T: ProtoValidator> Validated<T> {
+  /// Validates the message. This is the same as [`ProtoValidator::into_validated`].
+  pub fn new(message: T) -> Result<Self, Violations> {
+    message.validate()?;
+
+    Ok(Self(message))
+  }
+}
+
+impl<T> Validated<T> {
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T> Deref for Validated<T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<T> AsRef<T> for Validated<T> {
+  fn as_ref(&self) -> &T {
+    &self.0
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for Validated<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.0.serialize(serializer)
+  }
+}
+
+/// Deserializes the message and validates it. The violations are reported as a custom deserialization error.
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for Validated<T>
+where
+  T: Deserialize<'de> + ProtoValidator,
+{
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    Self::new(T::deserialize(deserializer)?)
+      .map_err(|violations| de::Error::custom(violations_summary(&violations)))
+  }
+}
+
+/// Encodes like the inner message. Decoding and merging validate the resulting message, and report the violations as a [`DecodeError`], so a `Validated<T>` is never created from invalid data.
+impl<T> prost::Message for Validated<T>
+where
+  T: prost::Message + ProtoValidator + Clone + Default,
+{
+  fn encode_raw(&self, buf: &mut impl BufMut) {
+    self.0.encode_raw(buf)
+  }
+
+  // This is only used by the default implementations of the merging methods, which are all replaced below
+  fn merge_field(
+    &mut self,
+    tag: u32,
+    wire_type: WireType,
+    buf: &mut impl Buf,
+    ctx: DecodeContext,
+  ) -> Result<(), DecodeError> {
+    self.0.merge_field(tag, wire_type, buf, ctx)
+  }
+
+  fn encoded_len(&self) -> usize {
+    self.0.encoded_len()
+  }
+
+  fn decode(buf: impl Buf) -> Result<Self, DecodeError> {
+    validate_decoded(T::decode(buf)?)
+  }
+
+  fn decode_length_delimited(buf: impl Buf) -> Result<Self, DecodeError> {
+    validate_decoded(T::decode_length_delimited(buf)?)
+  }
+
+  // The message is merged into a copy, so that it is left untouched if the result is invalid
+  fn merge(&mut self, buf: impl Buf) -> Result<(), DecodeError> {
+    let mut message = self.0.clone();
+    message.merge(buf)?;
+
+    *self = validate_decoded(message)?;
+
+    Ok(())
+  }
+
+  fn merge_length_delimited(&mut self, buf: impl Buf) -> Result<(), DecodeError> {
+    let mut message = self.0.clone();
+    message.merge_length_delimited(buf)?;
+
+    *self = validate_decoded(message)?;
+
+    Ok(())
+  }
+
+  /// Resets the message to its default value.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the default message is not valid, like [`Default`].
+  fn clear(&mut self) {
+    *self = Self::default();
+  }
+}
+
+/// The default message, which is required by [`prost::Message::decode`] and by tonic codecs.
+///
+/// # Panics
+///
+/// Panics if the default message is not valid, such as when it has required fields.
+impl<T: ProtoValidator + Default> Default for Validated<T> {
+  fn default() -> Self {
+    Self::new(T::default()).unwrap_or_else(|violations| {
+      panic!(
+        "the default message is not valid ({})",
+        violations_summary(&violations)
+      )
+    })
+  }
+}
+
+#[allow(
+  deprecated,
+  reason = "prost has no other way to create a DecodeError with a custom description, which is the error type required by prost::Message::decode"
+)]
+fn validate_decoded<T: ProtoValidator>(message: T) -> Result<Validated<T>, DecodeError> {
+  Validated::new(message).map_err(|violations| DecodeError::new(violations_summary(&violations)))
+}
+
+// Lists the violations in a single line, such as `validation failed: name: value length must be at least 3 characters; age: ...`
+fn violations_summary(violations: &Violations) -> String {
+  let mut summary = String::from("validation failed");
+
+  for (i, violation) in violations.violations.iter().enumerate() {
+    summary.push_str(if i == 0 { ": " } else { "; " });
+
+    if let Some(field) = violation.field_path_str() {
+      let _ = write!(summary, "{}: ", field);
+    }
+
+    summary.push_str(violation.message());
+  }
+
+  summary
+}
@@ -1,7 +1,10 @@
 use std::{env, path::PathBuf};
 
 use prost_build::Config;
-use protocheck_build::{compile_protos_with_validators, get_proto_files_recursive};
+use protocheck_build::{
+  compile_protos_with_valid_examples, compile_protos_with_validators, declare_cel_functions,
+  declare_cel_variables, get_proto_files_recursive, CelFunction, CelType, PROTO_INCLUDE_PATH,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
   println!("cargo:rerun-if-changed=proto/");
@@ -12,9 +15,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     .unwrap_or(env::temp_dir());
   let descriptor_path = out_dir.join("file_descriptor_set.bin");
 
-  let proto_include_paths = &["proto", "proto_deps"];
+  let proto_include_paths = &["proto", "proto_deps", PROTO_INCLUDE_PATH];
 
   let files = get_proto_files_recursive(PathBuf::from("proto/myapp/v1"))?;
+  let plain_files = get_proto_files_recursive(PathBuf::from("proto/plain/v1"))?;
+  let unpackaged_files = get_proto_files_recursive(PathBuf::from("proto/unpackaged"))?;
+
+  // The files without a package are validated and get the service wrappers just like the ones in myapp.v1
+  let files: Vec<String> = files
+    .into_iter()
+    .chain(unpackaged_files)
+    .collect();
+  let packages = &["myapp.v1", ""];
 
   let mut config = Config::new();
   config
@@ -26,9 +38,52 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     .type_attribute(".", "#[derive(::serde::Serialize, ::serde::Deserialize)]")
     .out_dir(&out_dir);
 
-  compile_protos_with_validators(&mut config, &files, proto_include_paths, &["myapp.v1"])?;
+  declare_cel_variables(&[("max_items", CelType::Int)]);
+  declare_cel_functions(&[CelFunction {
+    name: "isValidSku",
+    args: &[CelType::String],
+    returns: CelType::Bool,
+  }]);
+
+  compile_protos_with_valid_examples(
+    &mut config,
+    &files,
+    proto_include_paths,
+    packages,
+    &["myapp.v1.ExampleTests", "myapp.v1.ExampleTests.Nested"],
+  )?;
+
+  // The service wrappers need tonic, so they are only generated with the `tonic` feature
+  #[cfg(feature = "tonic")]
+  protocheck_build::generate_validated_services(
+    &mut config,
+    tonic_prost_build::configure()
+      .build_client(false)
+      .service_generator(),
+    &files,
+    proto_include_paths,
+    packages,
+  )?;
+
+  // The plain files are only added to the descriptor set here, since they are generated separately below
+  let all_files: Vec<&String> = files.iter().chain(plain_files.iter()).collect();
+  config.compile_protos(&all_files, proto_include_paths)?;
+
+  // The plain package is generated on its own with the default options, so that compile_protos_with_validators is covered too
+  let plain_out_dir = out_dir.join("plain");
+  std::fs::create_dir_all(&plain_out_dir)?;
+
+  let mut plain_config = Config::new();
+  plain_config.out_dir(&plain_out_dir);
+
+  compile_protos_with_validators(
+    &mut plain_config,
+    &plain_files,
+    proto_include_paths,
+    &["plain.v1"],
+  )?;
 
-  config.compile_protos(&files, proto_include_paths)?;
+  plain_config.compile_protos(&plain_files, proto_include_paths)?;
 
   println!(
     "cargo:rustc-env=PROTO_DESCRIPTOR_SET={}",
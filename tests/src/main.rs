@@ -6,6 +6,17 @@ mod myapp {
   }
 }
 
+mod plain {
+  pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/plain/plain.v1.rs"));
+  }
+}
+
+// prost names the file for the messages without a package `_.rs`
+mod unpackaged {
+  include!(concat!(env!("OUT_DIR"), "/_.rs"));
+}
+
 fn main() {}
 
 #[cfg(test)]
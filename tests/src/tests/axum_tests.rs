@@ -0,0 +1,97 @@
+use axum::{body::Body, http::StatusCode, routing::post, Router};
+use http_body_util::BodyExt;
+use prost::Message;
+use protocheck::{axum::Validated, render::PROBLEM_JSON_CONTENT_TYPE};
+use tower::ServiceExt;
+
+use super::invalid_message;
+use crate::myapp::v1::ExampleTests;
+
+async fn handler(message: Validated<ExampleTests>) -> String {
+  message.into_inner().name
+}
+
+fn router() -> Router {
+  Router::new().route("/", post(handler))
+}
+
+async fn send(content_type: &str, body: Vec<u8>) -> (StatusCode, String, String) {
+  let request = http::Request::builder()
+    .method("POST")
+    .uri("/")
+    .header("content-type", content_type)
+    .body(Body::from(body))
+    .unwrap();
+
+  let response = router().oneshot(request).await.unwrap();
+  let status = response.status();
+  let content_type = response
+    .headers()
+    .get("content-type")
+    .and_then(|value| value.to_str().ok())
+    .unwrap_or_default()
+    .to_string();
+  let body = response
+    .into_body()
+    .collect()
+    .await
+    .unwrap()
+    .to_bytes();
+
+  (
+    status,
+    content_type,
+    String::from_utf8(body.to_vec()).unwrap(),
+  )
+}
+
+#[tokio::test]
+async fn valid_json_body() {
+  let message = ExampleTests::valid_example();
+
+  let (status, _, body) = send("application/json", serde_json::to_vec(&message).unwrap()).await;
+
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, message.name);
+}
+
+#[tokio::test]
+async fn valid_protobuf_body() {
+  let message = ExampleTests::valid_example();
+
+  let (status, _, body) = send("application/x-protobuf", message.encode_to_vec()).await;
+
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, message.name);
+}
+
+#[tokio::test]
+async fn invalid_message_is_rejected() {
+  let (status, content_type, body) = send(
+    "application/json; charset=utf-8",
+    serde_json::to_vec(&invalid_message()).unwrap(),
+  )
+  .await;
+
+  assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+  assert_eq!(content_type, PROBLEM_JSON_CONTENT_TYPE);
+
+  let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+  let error = &body["errors"][0];
+
+  assert_eq!(body["status"], 422);
+  assert_eq!(error["pointer"], "/age");
+  assert_eq!(error["rule"], "int32.gte");
+}
+
+#[tokio::test]
+async fn malformed_bodies_are_rejected() {
+  let (status, _, _) = send("application/json", b"{".to_vec()).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+
+  let (status, _, _) = send("application/protobuf", vec![0xff]).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+
+  let (status, _, _) = send("text/plain", Vec::new()).await;
+  assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
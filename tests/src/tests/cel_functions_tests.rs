@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::CelFunctionsTests;
+
+fn is_valid_sku(sku: Arc<String>) -> bool {
+  sku.starts_with("SKU-")
+}
+
+fn accept_any_sku(_: Arc<String>) -> bool {
+  true
+}
+
+#[test]
+fn cel_functions() {
+  protocheck::register_cel_functions!("isValidSku" => is_valid_sku);
+
+  let valid = CelFunctionsTests {
+    sku: "SKU-1234".to_string(),
+  };
+
+  assert!(valid.validate().is_ok());
+
+  let invalid = CelFunctionsTests {
+    sku: "1234".to_string(),
+  };
+
+  let Violations { violations } = invalid.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "sku.valid");
+
+  // Registering a function with the same name replaces the previous one
+  protocheck::register_cel_functions!("isValidSku" => accept_any_sku);
+
+  assert!(invalid.validate().is_ok());
+
+  protocheck::register_cel_functions!("isValidSku" => is_valid_sku);
+
+  assert!(invalid.validate().is_err());
+}
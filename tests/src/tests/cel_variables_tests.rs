@@ -0,0 +1,21 @@
+use protocheck::{context::ValidationContext, types::protovalidate::Violations};
+
+use crate::myapp::v1::CelVariablesTests;
+
+#[test]
+fn cel_variables() {
+  let msg = CelVariablesTests {
+    items: vec!["abc".to_string(), "def".to_string()],
+  };
+
+  let ctx = ValidationContext::new().with_cel_variable("max_items", 1i64);
+
+  let Violations { violations } = msg.validate_with_context(&ctx).unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "tenant.max_items");
+
+  let ctx = ValidationContext::new().with_cel_variable("max_items", 2i64);
+
+  assert!(msg.validate_with_context(&ctx).is_ok());
+}
@@ -4,7 +4,7 @@ use protocheck::types::{
   Any, Duration,
 };
 
-use crate::myapp::v1::ContainingTests;
+use crate::myapp::v1::{ContainingTests, LargeListsTests};
 
 #[test]
 fn containing_tests() {
@@ -44,3 +44,44 @@ fn containing_tests() {
     .iter()
     .any(|v| v.rule_id() == "enum.defined_only"));
 }
+
+#[test]
+fn large_lists_tests() {
+  let valid_message = LargeListsTests {
+    enum_field: 1,
+    bytes_field: Bytes::from_static(b"\x03\x04"),
+    name: "alfredo".to_string(),
+    any_field: Some(Any {
+      type_url: "a   16".to_string(),
+      value: vec![],
+    }),
+    float_field: 1.0,
+    double_field: 1.0,
+    duration_field: Some(Duration {
+      seconds: 15,
+      nanos: 0,
+    }),
+    int64_field: 18,
+  };
+
+  assert!(valid_message.validate().is_ok());
+
+  let invalid_message = LargeListsTests {
+    duration_field: Some(Duration {
+      seconds: 32,
+      nanos: 0,
+    }),
+    int64_field: 19,
+    ..valid_message
+  };
+
+  let Violations { violations } = invalid_message.validate().unwrap_err();
+
+  let mut rule_ids: Vec<&str> = violations.iter().map(|v| v.rule_id()).collect();
+  rule_ids.sort();
+
+  assert_eq!(
+    rule_ids,
+    vec!["duration.in", "duration.not_in", "int64.in", "int64.not_in"]
+  );
+}
@@ -0,0 +1,115 @@
+use prost::Message;
+use protocheck::{ProtoValidator, ValidationError};
+
+use super::invalid_message;
+use crate::myapp::v1::ExampleTests;
+
+#[test]
+fn decode_validated() {
+  let message = ExampleTests::valid_example();
+
+  let validated = ExampleTests::decode_validated(message.encode_to_vec().as_slice()).unwrap();
+
+  assert_eq!(validated.into_inner(), message);
+
+  let error =
+    ExampleTests::decode_validated(invalid_message().encode_to_vec().as_slice()).unwrap_err();
+
+  assert!(matches!(error, ValidationError::Invalid(_)));
+  assert_eq!(error.to_string(), "validation failed with 1 violation");
+  assert_eq!(
+    error.violations().unwrap().violations[0].rule_id(),
+    "int32.gte"
+  );
+
+  let error = ExampleTests::decode_validated([0xff].as_slice()).unwrap_err();
+
+  assert!(matches!(error, ValidationError::Decode(_)));
+  assert!(error.violations().is_none());
+}
+
+#[cfg(feature = "json")]
+mod json {
+  use protocheck::types::protovalidate::field_path_element::Subscript;
+
+  use super::*;
+
+  #[test]
+  fn from_json_validated() {
+    let message = ExampleTests::valid_example();
+    let json = serde_json::to_string(&message).unwrap();
+
+    let validated = ExampleTests::from_json_validated(&json).unwrap();
+
+    assert_eq!(validated.into_inner(), message);
+
+    let json = serde_json::to_string(&invalid_message()).unwrap();
+
+    assert!(matches!(
+      ExampleTests::from_json_validated(&json),
+      Err(ValidationError::Invalid(_))
+    ));
+  }
+
+  #[test]
+  fn type_errors_become_violations() {
+    let mut json = serde_json::to_value(ExampleTests::valid_example()).unwrap();
+    json["tags"][1] = serde_json::json!(5);
+
+    let error = ExampleTests::from_json_validated(&json.to_string()).unwrap_err();
+
+    let ValidationError::Json { path, .. } = &error else {
+      panic!("expected a JSON error, found {:?}", error);
+    };
+
+    assert_eq!(path, "tags[1]");
+
+    let violations = error.violations().unwrap().violations;
+
+    assert_eq!(violations[0].rule_id(), "json.invalid");
+    // The subscripts are joined with a dot in the string form of the field path
+    assert_eq!(violations[0].field_path_str().unwrap(), "tags.1");
+  }
+
+  #[test]
+  fn map_keys_become_subscripts() {
+    let mut json = serde_json::to_value(ExampleTests::valid_example()).unwrap();
+    json["scores"]["team.a"] = serde_json::json!("high");
+
+    let error = ExampleTests::from_json_validated(&json.to_string()).unwrap_err();
+    let violations = error.violations().unwrap().violations;
+    let elements = &violations[0].field.as_ref().unwrap().elements;
+
+    assert_eq!(elements.len(), 1);
+    assert_eq!(elements[0].field_name(), "scores");
+    assert_eq!(elements[0].field_number(), 12);
+    assert_eq!(
+      elements[0].subscript,
+      Some(Subscript::StringKey("team.a".to_string()))
+    );
+  }
+
+  #[test]
+  fn nested_messages_are_followed() {
+    let mut json = serde_json::to_value(ExampleTests::valid_example()).unwrap();
+    json["nested"]["code"] = serde_json::json!(5);
+
+    let error = ExampleTests::from_json_validated(&json.to_string()).unwrap_err();
+    let violations = error.violations().unwrap().violations;
+    let elements = &violations[0].field.as_ref().unwrap().elements;
+
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[0].field_name(), "nested");
+    assert_eq!(elements[1].field_name(), "code");
+    assert_eq!(elements[1].field_number(), 1);
+    assert!(!violations[0].message().contains(" at line "));
+  }
+
+  #[test]
+  fn syntax_errors_are_not_violations() {
+    let error = ExampleTests::from_json_validated("{").unwrap_err();
+
+    assert!(matches!(error, ValidationError::Json { .. }));
+    assert!(error.violations().is_none());
+  }
+}
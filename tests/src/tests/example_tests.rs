@@ -0,0 +1,34 @@
+use crate::myapp::v1::{example_tests::Contact, ExampleTests, TestEnum};
+
+#[test]
+fn valid_example_passes_validation() {
+  let example = ExampleTests::valid_example();
+
+  assert!(example.validate().is_ok());
+}
+
+#[test]
+fn valid_example_values() {
+  let example = ExampleTests::valid_example();
+
+  assert!(example.name.starts_with("ex_"));
+  assert_eq!(example.sku, "ABC-1234");
+  assert_eq!(example.age, 18);
+  assert_eq!(example.count, 13);
+  assert_eq!(example.kind, TestEnum::Active as i32);
+  assert_eq!(example.color, "red");
+  assert_eq!(example.even, 4);
+  assert_eq!(example.tags.len(), 2);
+  assert_eq!(example.scores.len(), 1);
+  assert_eq!(example.nested.unwrap().code.len(), 4);
+  assert!(example.nickname.is_none());
+  assert!(matches!(example.contact, Some(Contact::Phone(_))));
+}
+
+#[test]
+fn valid_example_narrow_float_ranges() {
+  let example = ExampleTests::valid_example();
+
+  assert!(example.fraction > 0.0 && example.fraction < 1.0);
+  assert!(example.narrow > 0.1 && example.narrow < 0.1000001);
+}
@@ -0,0 +1,44 @@
+use protocheck::ProtoValidator;
+
+use crate::myapp::v1::NativeCelTests;
+
+fn invalid_msg() -> NativeCelTests {
+  NativeCelTests {
+    quantity: 100,
+    unit_price: 3,
+    limit: 200,
+    note: None,
+    code: "xxxx".to_string(),
+  }
+}
+
+#[test]
+fn validate_first() {
+  let msg = invalid_msg();
+
+  assert_eq!(msg.validate().unwrap_err().violations.len(), 3);
+
+  let violation = msg.validate_first().unwrap_err();
+
+  assert_eq!(violation.rule_id(), "quantity.total");
+  assert!(!msg.is_valid());
+}
+
+#[test]
+fn validate_first_with_trait() {
+  fn check<T: ProtoValidator>(msg: &T) -> bool {
+    msg.is_valid()
+  }
+
+  let mut msg = invalid_msg();
+
+  assert!(!check(&msg));
+  assert!(ProtoValidator::validate_first(&msg).is_err());
+
+  msg.unit_price = 2;
+  msg.note = Some("note".to_string());
+  msg.code = "abcd".to_string();
+
+  assert!(check(&msg));
+  assert!(ProtoValidator::validate_first(&msg).is_ok());
+}
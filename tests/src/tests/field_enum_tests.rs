@@ -0,0 +1,52 @@
+use protocheck::violations::ViolationsExt;
+
+use crate::myapp::v1::{
+  Anakin, AnakinField, JediFight, JediFightField, KeywordFieldsTests, KeywordFieldsTestsField,
+  MaskedTests, MaskedTestsField, ObiWan, ObiWanField,
+};
+
+#[test]
+fn field_enum_lookup() {
+  let jedi_fight = JediFight {
+    anakin: Some(Anakin {
+      has_high_ground: true,
+    }),
+    obi_wan: Some(ObiWan {
+      has_high_ground: false,
+    }),
+  };
+
+  let violations = jedi_fight.validate().unwrap_err();
+
+  let obi_wan_path = JediFightField::obi_wan(ObiWanField::HasHighGround);
+
+  assert_eq!(obi_wan_path.to_string(), "obi_wan.has_high_ground");
+  assert_eq!(violations.for_field(obi_wan_path).len(), 1);
+  assert!(violations.has_field(JediFightField::anakin(AnakinField::HasHighGround)));
+  // The message-level Cel rule of ObiWan reports its violation at `obi_wan` itself
+  assert!(violations.has_field(JediFightField::ObiWan));
+}
+
+#[test]
+fn field_enum_metadata() {
+  assert_eq!(MaskedTestsField::FirstName.proto_name(), "first_name");
+  assert_eq!(MaskedTestsField::Address.tag(), 3);
+
+  let violations = MaskedTests::default().validate().unwrap_err();
+
+  assert_eq!(
+    violations.for_field(MaskedTestsField::Address)[0].rule_id(),
+    "field.required"
+  );
+}
+
+#[test]
+fn field_enum_keywords() {
+  assert_eq!(KeywordFieldsTestsField::Self_.proto_name(), "self");
+  assert_eq!(KeywordFieldsTestsField::Type.proto_name(), "type");
+
+  let violations = KeywordFieldsTests::default().validate().unwrap_err();
+
+  assert_eq!(violations.for_field(KeywordFieldsTestsField::Self_).len(), 1);
+  assert_eq!(violations.for_field(KeywordFieldsTestsField::Type).len(), 1);
+}
@@ -0,0 +1,195 @@
+use std::convert::Infallible;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use prost::Message;
+use proto_types::{BadRequest, Status as StatusDetails};
+use protocheck::{
+  layer::ValidationLayer,
+  tonic::{body::Body, Code, Status},
+};
+use tower::{service_fn, Layer, ServiceExt};
+
+use super::invalid_message;
+use crate::myapp::v1::ExampleTests;
+
+const PATH: &str = "/myapp.v1.ExampleService/Create";
+
+fn grpc_frame(message: &ExampleTests) -> Bytes {
+  let encoded = message.encode_to_vec();
+
+  let mut frame = vec![0];
+  frame.extend((encoded.len() as u32).to_be_bytes());
+  frame.extend(encoded);
+
+  frame.into()
+}
+
+fn grpc_request(path: &str, message: &ExampleTests) -> http::Request<Body> {
+  grpc_stream_request(path, std::slice::from_ref(message))
+}
+
+fn grpc_stream_request(path: &str, messages: &[ExampleTests]) -> http::Request<Body> {
+  let body: Vec<u8> = messages.iter().flat_map(grpc_frame).collect();
+
+  http::Request::builder()
+    .uri(path)
+    .body(Body::new(Full::new(Bytes::from(body))))
+    .unwrap()
+}
+
+// A service that sends back the body of the request, or the status it failed with like tonic does
+async fn echo(request: http::Request<Body>) -> Result<http::Response<Body>, Infallible> {
+  match request.into_body().collect().await {
+    Ok(collected) => Ok(http::Response::new(Body::new(Full::new(
+      collected.to_bytes(),
+    )))),
+    Err(status) => Ok(status.into_http()),
+  }
+}
+
+async fn response_body(response: http::Response<Body>) -> Bytes {
+  response
+    .into_body()
+    .collect()
+    .await
+    .unwrap()
+    .to_bytes()
+}
+
+#[tokio::test]
+async fn valid_requests_reach_the_service() {
+  let service = ValidationLayer::new()
+    .validate_request::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let message = ExampleTests::valid_example();
+
+  let response = service
+    .oneshot(grpc_request(PATH, &message))
+    .await
+    .unwrap();
+
+  assert!(Status::from_header_map(response.headers()).is_none());
+  assert_eq!(response_body(response).await, grpc_frame(&message));
+}
+
+#[tokio::test]
+async fn invalid_requests_are_rejected() {
+  let service = ValidationLayer::new()
+    .validate_request::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let response = service
+    .oneshot(grpc_request(PATH, &invalid_message()))
+    .await
+    .unwrap();
+
+  let status = Status::from_header_map(response.headers()).unwrap();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+
+  let details = StatusDetails::decode(status.details()).unwrap();
+  let bad_request = BadRequest::decode(details.details[0].value.as_slice()).unwrap();
+
+  assert_eq!(bad_request.field_violations.len(), 1);
+  assert_eq!(bad_request.field_violations[0].field, "age");
+}
+
+#[tokio::test]
+async fn other_paths_are_not_validated() {
+  let service = ValidationLayer::new()
+    .validate_request::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let message = invalid_message();
+
+  let response = service
+    .oneshot(grpc_request("/myapp.v1.ExampleService/Other", &message))
+    .await
+    .unwrap();
+
+  assert!(Status::from_header_map(response.headers()).is_none());
+  assert_eq!(response_body(response).await, grpc_frame(&message));
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn invalid_responses_are_replaced_in_debug_builds() {
+  let service = ValidationLayer::new()
+    .validate_response::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let response = service
+    .oneshot(grpc_request(PATH, &invalid_message()))
+    .await
+    .unwrap();
+
+  let collected = response.into_body().collect().await.unwrap();
+  let status = Status::from_header_map(collected.trailers().unwrap()).unwrap();
+
+  assert_eq!(status.code(), Code::Internal);
+  assert!(collected.to_bytes().is_empty());
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn streamed_responses_end_before_the_first_invalid_message() {
+  let service = ValidationLayer::new()
+    .validate_response::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let message = ExampleTests::valid_example();
+
+  let response = service
+    .oneshot(grpc_stream_request(
+      PATH,
+      &[message.clone(), invalid_message(), message.clone()],
+    ))
+    .await
+    .unwrap();
+
+  let collected = response.into_body().collect().await.unwrap();
+  let status = Status::from_header_map(collected.trailers().unwrap()).unwrap();
+
+  assert_eq!(status.code(), Code::Internal);
+  assert_eq!(collected.to_bytes(), grpc_frame(&message));
+}
+
+#[tokio::test]
+async fn each_message_of_a_stream_is_validated() {
+  let service = ValidationLayer::new()
+    .validate_request::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let message = ExampleTests::valid_example();
+
+  let response = service
+    .oneshot(grpc_stream_request(
+      PATH,
+      &[message.clone(), message, invalid_message()],
+    ))
+    .await
+    .unwrap();
+
+  let status = Status::from_header_map(response.headers()).unwrap();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn messages_over_the_size_limit_are_rejected() {
+  let service = ValidationLayer::new()
+    .with_max_message_size(4)
+    .validate_request::<ExampleTests>(PATH)
+    .layer(service_fn(echo));
+
+  let response = service
+    .oneshot(grpc_request(PATH, &ExampleTests::valid_example()))
+    .await
+    .unwrap();
+
+  let status = Status::from_header_map(response.headers()).unwrap();
+
+  assert_eq!(status.code(), Code::ResourceExhausted);
+}
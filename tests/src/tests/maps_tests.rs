@@ -62,6 +62,40 @@ fn message_map() {
   }
 }
 
+#[test]
+fn map_violations_are_sorted_by_key() {
+  let person = Person {
+    name: "lucrezio".to_string(),
+  };
+
+  let message_map = (0..20)
+    .map(|i| (i, person.clone()))
+    .collect();
+
+  let msg = MessageMap { message_map };
+
+  let keys: Vec<i64> = msg
+    .validate()
+    .unwrap_err()
+    .violations
+    .iter()
+    .filter(|v| v.rule_id() == "map_value.person_name")
+    .map(|v| {
+      match v
+        .field
+        .as_ref()
+        .and_then(|path| path.elements.first())
+        .and_then(|element| element.subscript.clone())
+      {
+        Some(Subscript::IntKey(key)) => key,
+        other => panic!("Unexpected subscript {:?}", other),
+      }
+    })
+    .collect();
+
+  assert_eq!(keys, (0..20).collect::<Vec<i64>>());
+}
+
 #[test]
 fn timestamp_map() {
   let timestamp_map = hashmap! { "hello there".to_string() => Timestamp::default(), "general kenobi".to_string() => Timestamp::default() };
@@ -0,0 +1,134 @@
+use protocheck::types::FieldMask;
+
+use crate::myapp::v1::{
+  masked_tests::{Address, Contact},
+  MaskedTests,
+};
+
+fn mask(paths: &[&str]) -> FieldMask {
+  FieldMask::new(
+    paths
+      .iter()
+      .map(|path| path.to_string())
+      .collect(),
+  )
+}
+
+fn rule_ids(msg: &MaskedTests, paths: &[&str]) -> Vec<String> {
+  let mut rule_ids: Vec<String> = match msg.validate_masked(&mask(paths)) {
+    Ok(()) => Vec::new(),
+    Err(violations) => violations
+      .violations
+      .iter()
+      .map(|v| format!("{}:{}", v.field_path_str().unwrap_or_default(), v.rule_id()))
+      .collect(),
+  };
+
+  rule_ids.sort();
+  rule_ids
+}
+
+#[test]
+fn masked_validation() {
+  let msg = MaskedTests::default();
+
+  assert_eq!(msg.validate().unwrap_err().violations.len(), 6);
+
+  assert_eq!(
+    rule_ids(&msg, &["first_name"]),
+    vec!["first_name:string.min_len"]
+  );
+
+  assert_eq!(
+    rule_ids(&msg, &["first_name", "last_name"]),
+    vec![
+      ":masked_tests.names_differ",
+      "first_name:string.min_len",
+      "last_name:string.min_len"
+    ]
+  );
+
+  assert!(rule_ids(&msg, &[]).is_empty());
+}
+
+#[test]
+fn masked_nested_paths() {
+  let msg = MaskedTests {
+    first_name: "a".to_string(),
+    address: Some(Address {
+      street: "a".to_string(),
+      city: String::new(),
+    }),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    rule_ids(&msg, &["address.street"]),
+    vec!["address.street:string.min_len"]
+  );
+
+  assert_eq!(
+    rule_ids(&msg, &["address"]),
+    vec![
+      "address.city:string.min_len",
+      "address.street:string.min_len"
+    ]
+  );
+
+  let missing_address = MaskedTests::default();
+
+  assert_eq!(
+    rule_ids(&missing_address, &["address.street"]),
+    vec!["address:field.required"]
+  );
+}
+
+#[test]
+fn masked_message_rules() {
+  let msg = MaskedTests::default();
+
+  // The rule that uses the whole message only runs when every field is covered
+  assert_eq!(
+    rule_ids(
+      &msg,
+      &["first_name", "last_name", "address", "email", "phone"]
+    ),
+    vec![
+      ":masked_tests.has_first_name",
+      ":masked_tests.names_differ",
+      "address:field.required",
+      "contact:oneof.required",
+      "first_name:string.min_len",
+      "last_name:string.min_len"
+    ]
+  );
+
+  // A masked sub-path does not cover the field that contains it
+  assert_eq!(
+    rule_ids(&msg, &["first_name", "address.street"]),
+    vec!["address:field.required", "first_name:string.min_len"]
+  );
+}
+
+#[test]
+fn masked_oneof() {
+  let msg = MaskedTests {
+    contact: Some(Contact::Phone("123".to_string())),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    rule_ids(&msg, &["first_name"]),
+    vec!["first_name:string.min_len"]
+  );
+
+  assert_eq!(rule_ids(&msg, &["phone"]), vec!["phone:string.min_len"]);
+
+  // Only the field that is set is validated
+  assert!(rule_ids(&msg, &["email"]).is_empty());
+
+  assert_eq!(
+    rule_ids(&MaskedTests::default(), &["email"]),
+    vec!["contact:oneof.required"]
+  );
+}
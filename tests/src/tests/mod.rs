@@ -1,14 +1,54 @@
+use crate::myapp::v1::ExampleTests;
+
+#[cfg(feature = "axum")]
+mod axum_tests;
 mod bytes_tests;
+mod cel_functions_tests;
+mod cel_variables_tests;
 mod common_types_tests;
 mod comparables_tests;
 mod containing_tests;
+mod decode_tests;
 mod docs_example;
+mod example_tests;
+mod fail_fast_tests;
+mod field_enum_tests;
 mod finite_floats_tests;
 mod ignores_tests;
+#[cfg(feature = "tower")]
+mod layer_tests;
 mod maps_tests;
+mod masked_tests;
+mod native_cel_tests;
 mod optional_tests;
+#[cfg(feature = "rayon")]
+mod parallel_tests;
+mod plain_tests;
 mod recursion_tests;
+#[cfg(feature = "json")]
+mod render_tests;
 mod repeated_tests;
+mod schema_tests;
+#[cfg(feature = "tonic")]
+mod services_tests;
+mod single_field_tests;
+mod span_tests;
+#[cfg(feature = "tonic")]
+mod status_tests;
+#[cfg(feature = "proptest")]
+mod strategies_tests;
+#[cfg(feature = "tonic")]
+mod stream_tests;
 mod strings_tests;
+mod table_mode_tests;
 mod timestamps_tests;
+mod validated_tests;
+mod validation_options_tests;
 mod well_known_strings_tests;
+
+// The valid example with a single violation, for the `int32.gte` rule of `age`
+fn invalid_message() -> ExampleTests {
+  let mut message = ExampleTests::valid_example();
+  message.age = 10;
+  message
+}
@@ -0,0 +1,51 @@
+use protocheck::types::protovalidate::Violations;
+
+use crate::myapp::v1::NativeCelTests;
+
+#[test]
+fn native_cel() {
+  let mut msg = NativeCelTests {
+    quantity: 100,
+    unit_price: 2,
+    limit: 200,
+    note: Some("handle with care".to_string()),
+    code: "abcd".to_string(),
+  };
+
+  assert!(msg.validate().is_ok());
+
+  msg.unit_price = 3;
+  msg.note = None;
+  msg.code = "xxxx".to_string();
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  assert_eq!(violations.len(), 3);
+  assert!(violations
+    .iter()
+    .any(|v| v.rule_id() == "quantity.total"));
+  assert!(violations
+    .iter()
+    .any(|v| v.rule_id() == "note.present"));
+  assert!(violations
+    .iter()
+    .any(|v| v.rule_id() == "code.format"));
+}
+
+#[test]
+fn native_cel_overflow() {
+  let msg = NativeCelTests {
+    quantity: i64::MAX,
+    unit_price: 2,
+    limit: 0,
+    note: Some("note".to_string()),
+    code: "abcd".to_string(),
+  };
+
+  let Violations { violations } = msg.validate().unwrap_err();
+
+  // The overflow in `total.sign` is absorbed by the other operand of `||`, which is true
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "internal_server_error");
+  assert_eq!(violations[0].message(), "internal server error");
+}
@@ -0,0 +1,119 @@
+use protocheck::{
+  context::ValidationOptions,
+  types::protovalidate::{field_path_element::Subscript, Violation},
+};
+
+use crate::myapp::v1::{
+  message_map::Person as MapPerson, repeated_tests::Person, MessageMap, RepeatedTests,
+};
+
+fn subscripts(violations: &[Violation]) -> Vec<Option<Subscript>> {
+  violations
+    .iter()
+    .map(|v| {
+      v.field
+        .as_ref()
+        .and_then(|path| path.elements.first())
+        .and_then(|element| element.subscript.clone())
+    })
+    .collect()
+}
+
+#[test]
+fn parallel_repeated_matches_sequential() {
+  let people: Vec<Person> = (0..50)
+    .map(|i| Person {
+      name: if i % 2 == 0 {
+        "alfonso".to_string()
+      } else {
+        format!("person_{}", i)
+      },
+    })
+    .collect();
+
+  let msg = RepeatedTests {
+    people,
+    unique_floats: vec![1.1, 2.2, 3.3],
+    ..Default::default()
+  };
+
+  let parallel = msg
+    .validate_with(&ValidationOptions::new().with_parallel_threshold(1))
+    .unwrap_err();
+  let sequential = msg
+    .validate_with(&ValidationOptions::new().with_parallel_threshold(usize::MAX))
+    .unwrap_err();
+
+  assert_eq!(parallel.violations.len(), 75);
+  assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn parallel_repeated_respects_max_violations() {
+  let msg = RepeatedTests {
+    people: vec![Person::default(); 20],
+    unique_floats: vec![1.1, 2.2, 3.3],
+    ..Default::default()
+  };
+
+  let violations = msg
+    .validate_with(
+      &ValidationOptions::new()
+        .with_parallel_threshold(1)
+        .with_max_violations(4),
+    )
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations.len(), 4);
+  assert_eq!(
+    subscripts(&violations),
+    vec![
+      Some(Subscript::Index(0)),
+      Some(Subscript::Index(0)),
+      Some(Subscript::Index(0)),
+      Some(Subscript::Index(1)),
+    ]
+  );
+}
+
+#[test]
+fn parallel_map_is_sorted_by_key() {
+  let message_map = (0..20)
+    .map(|i| {
+      (
+        i,
+        MapPerson {
+          name: "lucrezio".to_string(),
+        },
+      )
+    })
+    .collect();
+
+  let msg = MessageMap { message_map };
+
+  let violations = msg
+    .validate_with(&ValidationOptions::new().with_parallel_threshold(1))
+    .unwrap_err()
+    .violations;
+
+  let sequential = msg
+    .validate_with(&ValidationOptions::new().with_parallel_threshold(usize::MAX))
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations, sequential);
+
+  let keys: Vec<i64> = subscripts(&violations)
+    .into_iter()
+    .map(|subscript| match subscript {
+      Some(Subscript::IntKey(key)) => key,
+      other => panic!("Unexpected subscript {:?}", other),
+    })
+    .collect();
+
+  let mut sorted_keys = keys.clone();
+  sorted_keys.sort();
+
+  assert_eq!(keys, sorted_keys);
+}
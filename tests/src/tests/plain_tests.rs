@@ -0,0 +1,23 @@
+use crate::plain::v1::PlainTests;
+
+#[test]
+fn plain_validators() {
+  let msg = PlainTests {
+    name: "ab".to_string(),
+    scores: vec![1, 0],
+  };
+
+  let violations = msg.validate().unwrap_err().violations;
+
+  assert_eq!(violations.len(), 2);
+  assert_eq!(violations[0].rule_id(), "string.min_len");
+  assert_eq!(violations[1].rule_id(), "int32.gt");
+  assert_eq!(violations[1].field_path_str().unwrap(), "scores.1");
+
+  let msg = PlainTests {
+    name: "abc".to_string(),
+    scores: vec![1],
+  };
+
+  assert!(msg.validate().is_ok());
+}
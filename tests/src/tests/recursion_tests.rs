@@ -1,6 +1,6 @@
-use protocheck::types::protovalidate::Violations;
+use protocheck::{context::ValidationOptions, types::protovalidate::Violations};
 
-use crate::myapp::v1::{recursive::Oneofs, Recursive};
+use crate::myapp::v1::{recursive::Oneofs, Recursive, RuleFreeNode, RuleFreeParent};
 
 #[test]
 fn recursion_test() {
@@ -26,3 +26,27 @@ fn recursion_test() {
   assert!(err2);
   assert!(err3);
 }
+
+#[test]
+fn rule_free_subtrees_are_skipped() {
+  let mut node = RuleFreeNode::default();
+
+  for _ in 0..5 {
+    node = RuleFreeNode {
+      value: 1,
+      next: Some(Box::new(node.clone())),
+      children: vec![node],
+    };
+  }
+
+  let msg = RuleFreeParent {
+    name: "anakin".to_string(),
+    node: Some(node),
+  };
+
+  // The depth limit is never reached because the nested messages
+  // have no rules, so they are never visited
+  let options = ValidationOptions::new().with_max_depth(1);
+
+  assert!(msg.validate_with(&options).is_ok());
+}
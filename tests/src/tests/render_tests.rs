@@ -0,0 +1,147 @@
+use protocheck::{
+  render::{
+    field_path_json_pointer, field_path_pointer, violations_to_json, FieldNames, ProblemDetails,
+  },
+  types::protovalidate::{field_path_element::Subscript, FieldPath, FieldPathElement},
+};
+
+use crate::myapp::v1::{json_names_tests::Inner, ExampleTests, JsonNamesTests};
+
+// The valid example with violations in the items of a list and in the keys of a map
+fn message_with_item_violations() -> ExampleTests {
+  let mut message = ExampleTests::valid_example();
+  message.tags[1] = "x".to_string();
+  message.scores.insert("ab".to_string(), 1);
+  message
+}
+
+#[test]
+fn canonical_json() {
+  let violations = message_with_item_violations()
+    .validate()
+    .unwrap_err();
+
+  let json = violations_to_json(&violations);
+  let tags_violation = json["violations"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|v| v["ruleId"] == "string.min_len" && v["field"]["elements"][0]["fieldName"] == "tags")
+    .unwrap();
+
+  let element = &tags_violation["field"]["elements"][0];
+
+  assert_eq!(element["fieldNumber"], 11);
+  assert_eq!(element["fieldType"], "TYPE_STRING");
+  assert_eq!(element["index"], "1");
+  assert_eq!(
+    tags_violation["rule"]["elements"][0]["fieldName"],
+    "repeated"
+  );
+}
+
+#[test]
+fn problem_details() {
+  let violations = message_with_item_violations()
+    .validate()
+    .unwrap_err();
+
+  let document = ProblemDetails::new()
+    .with_instance("/examples")
+    .render(&violations);
+
+  assert_eq!(document["type"], "about:blank");
+  assert_eq!(document["status"], 422);
+  assert_eq!(document["instance"], "/examples");
+
+  let mut pointers: Vec<(&str, &str)> = document["errors"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|error| {
+      (
+        error["pointer"].as_str().unwrap(),
+        error["rule"].as_str().unwrap(),
+      )
+    })
+    .collect();
+
+  pointers.sort_unstable();
+
+  assert_eq!(
+    pointers,
+    [
+      ("/scores/ab", "string.min_len"),
+      ("/tags/1", "string.min_len")
+    ]
+  );
+}
+
+#[test]
+fn pointers() {
+  let path = FieldPath {
+    elements: vec![
+      FieldPathElement {
+        field_name: Some("user_labels".to_string()),
+        subscript: Some(Subscript::StringKey("a/b~c".to_string())),
+        ..Default::default()
+      },
+      FieldPathElement {
+        field_name: Some("display_name".to_string()),
+        ..Default::default()
+      },
+    ],
+  };
+
+  assert_eq!(
+    field_path_pointer(&path, FieldNames::Proto),
+    "/user_labels/a~1b~0c/display_name"
+  );
+  assert_eq!(
+    field_path_pointer(&path, FieldNames::Json),
+    "/userLabels/a~1b~0c/displayName"
+  );
+  assert_eq!(
+    field_path_pointer(&FieldPath::default(), FieldNames::Proto),
+    ""
+  );
+}
+
+#[test]
+fn custom_json_names() {
+  let message = JsonNamesTests {
+    display_name: "a".to_string(),
+    inner: Some(Inner {
+      label: "b".to_string(),
+    }),
+    label: "c".to_string(),
+  };
+
+  let violations = message.validate().unwrap_err();
+
+  let mut pointers: Vec<String> = violations
+    .violations
+    .iter()
+    .map(|violation| {
+      field_path_json_pointer(
+        violation.field.as_ref().unwrap(),
+        JsonNamesTests::validation_schema(),
+      )
+    })
+    .collect();
+
+  pointers.sort_unstable();
+
+  assert_eq!(pointers, ["/inner/innerLabel", "/outerLabel", "/title"]);
+
+  let document = ProblemDetails::new()
+    .with_field_names(FieldNames::Json)
+    .with_schema(JsonNamesTests::validation_schema())
+    .render(&violations);
+
+  assert!(document["errors"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .any(|error| error["pointer"] == "/inner/innerLabel"));
+}
@@ -0,0 +1,126 @@
+use protocheck::{
+  schema::{CelRuleInfo, RuleValue},
+  ValidationSchema,
+};
+
+use crate::myapp::v1::{RuleFreeNode, SchemaTests};
+
+#[test]
+fn schema_fields() {
+  let schema = SchemaTests::validation_schema();
+
+  assert_eq!(schema.full_name, "myapp.v1.SchemaTests");
+  assert_eq!(schema.fields.len(), 8);
+
+  let name = schema.field("name").unwrap();
+
+  assert_eq!(name.tag, 1);
+  assert_eq!(name.field_type, "string");
+  assert!(!name.required);
+  assert_eq!(
+    name.rule("string.min_len").unwrap().value,
+    RuleValue::Uint(2)
+  );
+  assert_eq!(
+    name.rule("string.max_len").unwrap().value,
+    RuleValue::Uint(50)
+  );
+
+  let age = schema.field("age").unwrap();
+
+  assert_eq!(age.rule("int32.gte").unwrap().value, RuleValue::Int(18));
+  assert_eq!(
+    age.cel,
+    &[CelRuleInfo {
+      id: "schema_tests.age_even",
+      message: "age must be even",
+      expression: "this % 2 == 0",
+    }]
+  );
+
+  let timeout = schema.field("timeout").unwrap();
+
+  assert!(timeout.required);
+  assert_eq!(
+    timeout.rule("duration.lt").unwrap().value,
+    RuleValue::Duration {
+      seconds: 60,
+      nanos: 0
+    }
+  );
+
+  let notes = schema.field("notes").unwrap();
+
+  assert!(notes.rules.is_empty());
+  assert!(notes.cel.is_empty());
+}
+
+#[test]
+fn schema_lists_and_maps() {
+  let schema = SchemaTests::validation_schema();
+
+  let tags = schema.field("tags").unwrap();
+
+  assert!(tags.is_repeated);
+  assert_eq!(
+    tags.rule("repeated.max_items").unwrap().value,
+    RuleValue::Uint(3)
+  );
+  assert_eq!(
+    tags
+      .items
+      .unwrap()
+      .rule("string.in")
+      .unwrap()
+      .value,
+    RuleValue::List(&[RuleValue::String("a"), RuleValue::String("b")])
+  );
+
+  let scores = schema.field("scores").unwrap();
+
+  assert!(scores.is_map);
+  assert_eq!(scores.field_type, "int32");
+  assert!(scores.values.is_none());
+
+  let keys = scores.keys.unwrap();
+
+  assert_eq!(keys.field_type, "string");
+  assert_eq!(
+    keys.rule("string.min_len").unwrap().value,
+    RuleValue::Uint(1)
+  );
+}
+
+#[test]
+fn schema_oneofs_and_message_rules() {
+  let schema = <SchemaTests as ValidationSchema>::validation_schema();
+
+  assert_eq!(schema.oneofs.len(), 1);
+  assert_eq!(schema.oneofs[0].name, "contact");
+  assert!(schema.oneofs[0].required);
+  assert_eq!(schema.oneofs[0].fields, &["email", "phone"]);
+
+  assert_eq!(
+    schema
+      .field("email")
+      .unwrap()
+      .rule("string.email")
+      .unwrap()
+      .value,
+    RuleValue::Bool(true)
+  );
+
+  assert_eq!(schema.cel.len(), 1);
+  assert_eq!(schema.cel[0].id, "schema_tests.tags_or_scores");
+}
+
+#[test]
+fn schema_without_rules() {
+  let schema = RuleFreeNode::validation_schema();
+
+  assert_eq!(schema.fields.len(), 3);
+  assert!(schema
+    .fields
+    .iter()
+    .all(|field| field.rules.is_empty() && field.items.is_none()));
+}
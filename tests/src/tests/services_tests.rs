@@ -0,0 +1,111 @@
+use std::pin::Pin;
+
+use futures_util::{stream, Stream, StreamExt};
+use protocheck::types::Empty;
+use tonic::{Code, Request, Response, Status};
+
+use super::invalid_message;
+use crate::{
+  myapp::v1::{example_service_server::ExampleService, ExampleTests, ValidatedExampleService},
+  unpackaged::{
+    unpackaged_service_server::UnpackagedService, UnpackagedTests, ValidatedUnpackagedService,
+  },
+};
+
+struct TestService;
+
+#[tonic::async_trait]
+impl ExampleService for TestService {
+  async fn create(&self, _: Request<ExampleTests>) -> Result<Response<Empty>, Status> {
+    Ok(Response::new(Empty {}))
+  }
+
+  async fn create_unchecked(&self, _: Request<ExampleTests>) -> Result<Response<Empty>, Status> {
+    Ok(Response::new(Empty {}))
+  }
+
+  type WatchStream = Pin<Box<dyn Stream<Item = Result<ExampleTests, Status>> + Send>>;
+
+  async fn watch(
+    &self,
+    request: Request<ExampleTests>,
+  ) -> Result<Response<Self::WatchStream>, Status> {
+    Ok(Response::new(
+      stream::iter([Ok(request.into_inner())]).boxed(),
+    ))
+  }
+}
+
+struct TestUnpackagedService;
+
+#[tonic::async_trait]
+impl UnpackagedService for TestUnpackagedService {
+  async fn create(&self, _: Request<UnpackagedTests>) -> Result<Response<Empty>, Status> {
+    Ok(Response::new(Empty {}))
+  }
+
+  async fn create_unchecked(&self, _: Request<UnpackagedTests>) -> Result<Response<Empty>, Status> {
+    Ok(Response::new(Empty {}))
+  }
+}
+
+#[tokio::test]
+async fn valid_requests_are_delegated() {
+  let service = ValidatedExampleService::new(TestService);
+
+  assert!(service
+    .create(Request::new(ExampleTests::valid_example()))
+    .await
+    .is_ok());
+}
+
+#[tokio::test]
+async fn invalid_requests_are_rejected() {
+  let service = ValidatedExampleService::new(TestService);
+
+  let status = service
+    .create(Request::new(invalid_message()))
+    .await
+    .unwrap_err();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+
+  let status = service
+    .watch(Request::new(invalid_message()))
+    .await
+    .err()
+    .unwrap();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn methods_can_opt_out_of_validation() {
+  let service = ValidatedExampleService::new(TestService);
+
+  assert!(service
+    .create_unchecked(Request::new(invalid_message()))
+    .await
+    .is_ok());
+}
+
+#[tokio::test]
+async fn services_without_a_package_are_validated() {
+  let service = ValidatedUnpackagedService::new(TestUnpackagedService);
+
+  let invalid = UnpackagedTests {
+    name: "ab".to_string(),
+  };
+
+  let status = service
+    .create(Request::new(invalid.clone()))
+    .await
+    .unwrap_err();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+
+  assert!(service
+    .create_unchecked(Request::new(invalid))
+    .await
+    .is_ok());
+}
@@ -0,0 +1,42 @@
+use crate::myapp::v1::{masked_tests::Address, MaskedTests, MaskedTestsField};
+
+#[test]
+fn single_field_validation() {
+  assert!(MaskedTests::validate_field(MaskedTestsField::FirstName, &"ab".to_string()).is_ok());
+
+  let violations = MaskedTests::validate_field(MaskedTestsField::FirstName, &"a".to_string())
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "string.min_len");
+  assert_eq!(violations[0].field_path_str().unwrap(), "first_name");
+}
+
+#[test]
+fn single_message_field_validation() {
+  let address = Some(Address {
+    street: "a".to_string(),
+    city: "rome".to_string(),
+  });
+
+  let violations = MaskedTests::validate_field(MaskedTestsField::Address, &address)
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].field_path_str().unwrap(), "address.street");
+
+  let violations = MaskedTests::validate_field(MaskedTestsField::Address, &None::<Address>)
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "field.required");
+}
+
+#[test]
+#[should_panic(expected = "first_name")]
+fn single_field_validation_wrong_type() {
+  let _ = MaskedTests::validate_field(MaskedTestsField::FirstName, &1_i32);
+}
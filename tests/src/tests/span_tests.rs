@@ -0,0 +1,149 @@
+use protocheck::{
+  span::JsonLocator,
+  types::protovalidate::{
+    field_path_element::Subscript, FieldPath, FieldPathElement, Violation, Violations,
+  },
+};
+
+use crate::myapp::v1::{ExampleTests, JsonNamesTests};
+
+fn spanned_text<'a>(json: &'a str, locator: &JsonLocator, violation: &Violation) -> &'a str {
+  &json[locator.locate(violation).unwrap().span.range()]
+}
+
+fn field_violation(elements: Vec<FieldPathElement>, for_key: bool) -> Violation {
+  Violation {
+    field: Some(FieldPath { elements }),
+    for_key: Some(for_key),
+    ..Default::default()
+  }
+}
+
+fn element(name: &str, subscript: Option<Subscript>) -> FieldPathElement {
+  FieldPathElement {
+    field_name: Some(name.to_string()),
+    subscript,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn locates_violations() {
+  let mut message = ExampleTests::valid_example();
+  message.tags[1] = "x".to_string();
+  message.scores.insert("ab".to_string(), 1);
+
+  let json = serde_json::to_string_pretty(&message).unwrap();
+  let violations = message.validate().unwrap_err();
+
+  let locator = JsonLocator::new(&json);
+  let located = locator.locate_all(&violations);
+
+  assert_eq!(located.len(), violations.violations.len());
+
+  for item in &located {
+    assert!(item.is_exact);
+
+    let expected = match item.violation.field_path_str().unwrap().as_str() {
+      "tags.1" => "\"x\"",
+      // The violations of map keys point to the key
+      _ => "\"ab\"",
+    };
+
+    assert_eq!(&json[item.span.range()], expected);
+
+    let line = json.lines().nth(item.span.line - 1).unwrap();
+    assert_eq!(line.chars().nth(item.span.column - 1), Some('"'));
+  }
+}
+
+#[test]
+fn handles_indices_keys_and_json_names() {
+  let json = r#"{
+  "userLabels": { "a\"b": [1, {"x": true}], "plain": "value" },
+  "custom": [ "first", "second" ]
+}"#;
+
+  let locator = JsonLocator::new(json).with_json_name("renamed_field", "custom");
+
+  let violation = field_violation(
+    vec![element(
+      "user_labels",
+      Some(Subscript::StringKey("a\"b".to_string())),
+    )],
+    false,
+  );
+  assert_eq!(
+    spanned_text(json, &locator, &violation),
+    r#"[1, {"x": true}]"#
+  );
+
+  let violation = field_violation(
+    vec![element(
+      "user_labels",
+      Some(Subscript::StringKey("plain".to_string())),
+    )],
+    true,
+  );
+  assert_eq!(spanned_text(json, &locator, &violation), "\"plain\"");
+
+  let violation = field_violation(
+    vec![element("renamed_field", Some(Subscript::Index(1)))],
+    false,
+  );
+  let located = locator.locate(&violation).unwrap();
+
+  assert_eq!(&json[located.span.range()], "\"second\"");
+  assert_eq!(located.span.line, 3);
+  assert_eq!(located.span.column, 24);
+}
+
+#[test]
+fn falls_back_to_the_closest_parent() {
+  let json = r#"{ "nested": { "other": 1 } }"#;
+  let locator = JsonLocator::new(json);
+
+  let violation = field_violation(vec![element("nested", None), element("code", None)], false);
+  let located = locator.locate(&violation).unwrap();
+
+  assert!(!located.is_exact);
+  assert_eq!(&json[located.span.range()], r#"{ "other": 1 }"#);
+
+  let violations = Violations {
+    violations: vec![Violation::default()],
+  };
+  let located = locator.locate_all(&violations);
+
+  assert!(located[0].is_exact);
+  assert_eq!(located[0].span.range(), 0..json.len());
+}
+
+#[test]
+fn json_names_are_scoped_to_their_message() {
+  let json = r#"{
+  "title": "a",
+  "inner": { "outerLabel": "x", "innerLabel": "b" },
+  "outerLabel": "c"
+}"#;
+
+  let locator = JsonLocator::new(json).with_schema(JsonNamesTests::validation_schema());
+
+  let violation = field_violation(vec![element("display_name", None)], false);
+  assert_eq!(spanned_text(json, &locator, &violation), "\"a\"");
+
+  let violation = field_violation(vec![element("inner", None), element("label", None)], false);
+  assert_eq!(spanned_text(json, &locator, &violation), "\"b\"");
+
+  let violation = field_violation(vec![element("label", None)], false);
+  assert_eq!(spanned_text(json, &locator, &violation), "\"c\"");
+}
+
+#[test]
+fn partially_valid_documents_are_located() {
+  let json = r#"{ "other": tru, "title": "a" }"#;
+
+  let locator = JsonLocator::new(json).with_schema(JsonNamesTests::validation_schema());
+
+  let violation = field_violation(vec![element("display_name", None)], false);
+  assert_eq!(spanned_text(json, &locator, &violation), "\"a\"");
+}
@@ -0,0 +1,92 @@
+use prost::Message;
+use proto_types::{BadRequest, ErrorInfo, Status};
+use protocheck::{
+  status::{IntoStatus, ERROR_INFO_DOMAIN},
+  tonic::Code,
+};
+
+use crate::myapp::v1::ExampleTests;
+
+fn decode_details(status: &protocheck::tonic::Status) -> (BadRequest, Vec<ErrorInfo>) {
+  let details = Status::decode(status.details()).unwrap();
+
+  assert_eq!(details.code, Code::InvalidArgument as i32);
+  assert_eq!(
+    details.details[0].type_url,
+    "type.googleapis.com/google.rpc.BadRequest"
+  );
+
+  let bad_request = BadRequest::decode(details.details[0].value.as_slice()).unwrap();
+  let error_infos = details.details[1..]
+    .iter()
+    .map(|any| {
+      assert_eq!(any.type_url, "type.googleapis.com/google.rpc.ErrorInfo");
+      ErrorInfo::decode(any.value.as_slice()).unwrap()
+    })
+    .collect();
+
+  (bad_request, error_infos)
+}
+
+#[test]
+fn violations_into_status() {
+  let mut message = ExampleTests::valid_example();
+  message.age = 10;
+  message.name = "ex".to_string();
+
+  let status = message.validate().unwrap_err().into_status();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+  assert_eq!(status.message(), "validation failed with 3 violations");
+
+  let (bad_request, error_infos) = decode_details(&status);
+
+  let mut entries: Vec<(&str, &str)> = bad_request
+    .field_violations
+    .iter()
+    .zip(&error_infos)
+    .map(|(field_violation, info)| {
+      assert_eq!(info.domain, ERROR_INFO_DOMAIN);
+      assert_eq!(info.metadata["field"], field_violation.field);
+
+      (field_violation.field.as_str(), info.reason.as_str())
+    })
+    .collect();
+
+  entries.sort_unstable();
+
+  assert_eq!(
+    entries,
+    [
+      ("age", "int32.gte"),
+      ("name", "string.min_len"),
+      ("name", "string.prefix")
+    ]
+  );
+}
+
+#[test]
+fn single_violation_into_status() {
+  let mut message = ExampleTests::valid_example();
+  message.age = 10;
+
+  let violation = message.validate_first().unwrap_err();
+  let violation_message = violation.message().to_string();
+
+  let status = violation.into_status();
+
+  assert_eq!(
+    status.message(),
+    format!("validation failed: {}", violation_message)
+  );
+
+  let (bad_request, error_infos) = decode_details(&status);
+
+  assert_eq!(bad_request.field_violations.len(), 1);
+  assert_eq!(bad_request.field_violations[0].field, "age");
+  assert_eq!(
+    bad_request.field_violations[0].description,
+    violation_message
+  );
+  assert_eq!(error_infos[0].reason, "int32.gte");
+}
@@ -0,0 +1,48 @@
+use protocheck::proptest::prelude::*;
+
+use crate::myapp::v1::{ExampleTests, ExampleTestsField, TreeNode};
+
+proptest! {
+  #[test]
+  fn arbitrary_messages_are_valid(message in any::<ExampleTests>()) {
+    prop_assert!(message.validate().is_ok());
+  }
+
+  #[test]
+  fn recursive_required_fields_are_generated(message in any::<TreeNode>()) {
+    prop_assert!(message.branch.is_some());
+    prop_assert!(message.validate().is_ok());
+  }
+
+  #[test]
+  fn violating_strategy_violates_the_rule(
+    message in ExampleTests::violating_strategy(ExampleTestsField::Age, "int32.gte")
+  ) {
+    let violations = message.validate().unwrap_err();
+
+    prop_assert!(violations
+      .violations
+      .iter()
+      .any(|v| v.rule_id() == "int32.gte"));
+  }
+}
+
+#[test]
+fn violate_rule_changes_the_field() {
+  let mut message = ExampleTests::valid_example();
+
+  assert!(message.violate_rule(ExampleTestsField::Name, "string.prefix"));
+  assert!(!message.name.starts_with("ex_"));
+
+  assert!(message.violate_rule(ExampleTestsField::Count, "uint64.not_in"));
+  assert_eq!(message.count, 11);
+
+  assert!(!message.violate_rule(ExampleTestsField::Sku, "string.pattern"));
+  assert!(!message.violate_rule(ExampleTestsField::Age, "string.min_len"));
+}
+
+#[test]
+#[should_panic(expected = "cannot be violated automatically")]
+fn violating_strategy_rejects_unsupported_rules() {
+  let _ = ExampleTests::violating_strategy(ExampleTestsField::Sku, "string.pattern");
+}
@@ -0,0 +1,66 @@
+use futures_util::{stream, StreamExt};
+use protocheck::{
+  stream::ValidatedStream,
+  tonic::{Code, Status},
+};
+
+use crate::myapp::v1::ExampleTests;
+
+fn messages() -> Vec<Result<ExampleTests, Status>> {
+  let mut invalid = ExampleTests::valid_example();
+  invalid.age = 10;
+
+  vec![
+    Ok(ExampleTests::valid_example()),
+    Ok(invalid),
+    Ok(ExampleTests::valid_example()),
+  ]
+}
+
+#[tokio::test]
+async fn ends_on_the_first_invalid_message() {
+  let mut validated = ValidatedStream::new(stream::iter(messages()));
+
+  assert!(validated.message().await.unwrap().is_some());
+
+  let status = validated.message().await.unwrap_err();
+
+  assert_eq!(status.code(), Code::InvalidArgument);
+  assert!(status
+    .message()
+    .starts_with("validation failed for message 1 of the stream"));
+
+  assert!(validated.next().await.is_none());
+  assert_eq!(validated.received_count(), 2);
+  assert_eq!(validated.invalid_count(), 1);
+}
+
+#[tokio::test]
+async fn skips_invalid_messages() {
+  let results: Vec<Result<ExampleTests, Status>> = ValidatedStream::new(stream::iter(messages()))
+    .skip_invalid()
+    .collect()
+    .await;
+
+  assert_eq!(results.len(), 3);
+  assert!(results[0].is_ok());
+  assert!(results[1].is_err());
+  assert!(results[2].is_ok());
+}
+
+#[tokio::test]
+async fn passes_through_the_errors_of_the_stream() {
+  let items = vec![
+    Err(Status::cancelled("cancelled")),
+    Ok(ExampleTests::valid_example()),
+  ];
+
+  let mut validated = ValidatedStream::new(stream::iter(items));
+
+  assert_eq!(
+    validated.message().await.unwrap_err().code(),
+    Code::Cancelled
+  );
+  assert!(validated.message().await.unwrap().is_some());
+  assert_eq!(validated.received_count(), 1);
+}
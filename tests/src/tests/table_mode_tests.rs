@@ -0,0 +1,152 @@
+use protocheck::{
+  context::{ValidationContext, ValidationOptions},
+  macros::protobuf_validate,
+};
+
+use crate::myapp::v1::{TableModeTests as InlineTableModeTests, TestEnum};
+
+#[protobuf_validate("myapp.v1.TableModeTests", table)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TableModeTests {
+  #[prost(string, tag = "1")]
+  pub name: String,
+  #[prost(string, tag = "2")]
+  pub email: String,
+  #[prost(int32, tag = "3")]
+  pub age: i32,
+  #[prost(string, tag = "10")]
+  pub alias: String,
+  #[prost(uint64, optional, tag = "4")]
+  pub count: Option<u64>,
+  #[prost(double, tag = "5")]
+  pub ratio: f64,
+  #[prost(enumeration = "crate::myapp::v1::TestEnum", tag = "6")]
+  pub kind: i32,
+  #[prost(bool, tag = "7")]
+  pub active: bool,
+  #[prost(string, tag = "8")]
+  pub nickname: String,
+  #[prost(string, tag = "9")]
+  pub code: String,
+}
+
+impl From<&TableModeTests> for InlineTableModeTests {
+  fn from(value: &TableModeTests) -> Self {
+    InlineTableModeTests {
+      name: value.name.clone(),
+      email: value.email.clone(),
+      age: value.age,
+      alias: value.alias.clone(),
+      count: value.count,
+      ratio: value.ratio,
+      kind: value.kind,
+      active: value.active,
+      nickname: value.nickname.clone(),
+      code: value.code.clone(),
+    }
+  }
+}
+
+#[test]
+fn table_mode_valid() {
+  let msg = TableModeTests {
+    name: "tm_abc".to_string(),
+    email: "me@example.com".to_string(),
+    age: 30,
+    alias: "luke".to_string(),
+    count: Some(5),
+    ratio: 0.5,
+    kind: TestEnum::Active as i32,
+    active: true,
+    nickname: String::new(),
+    code: "a".to_string(),
+  };
+
+  assert!(msg.validate().is_ok());
+  assert!(InlineTableModeTests::from(&msg)
+    .validate()
+    .is_ok());
+}
+
+#[test]
+fn table_mode_matches_inline() {
+  let msg = TableModeTests {
+    name: "ab".to_string(),
+    email: "not_an_email".to_string(),
+    age: 150,
+    alias: "admin".to_string(),
+    count: None,
+    ratio: f64::NAN,
+    kind: 15,
+    active: false,
+    nickname: "x".to_string(),
+    code: "c".to_string(),
+  };
+
+  let table_violations = msg.validate().unwrap_err().violations;
+  let inline_violations = InlineTableModeTests::from(&msg)
+    .validate()
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(table_violations.len(), 12);
+  // The violations are also reported in the same order
+  assert_eq!(table_violations, inline_violations);
+}
+
+#[test]
+fn table_mode_rule_filters() {
+  let msg = TableModeTests {
+    name: "ab".to_string(),
+    age: 150,
+    count: Some(5),
+    ratio: 0.5,
+    kind: 15,
+    active: true,
+    code: "a".to_string(),
+    ..Default::default()
+  };
+
+  let context = ValidationContext::new().with_options(
+    ValidationOptions::new()
+      .skip_rule("string.*")
+      .skip_rule("enum.defined_only"),
+  );
+
+  let violations = msg
+    .validate_with_context(&context)
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "int32.lt");
+}
+
+#[test]
+fn table_mode_fail_fast() {
+  let msg = TableModeTests {
+    name: "ab".to_string(),
+    age: 10,
+    code: "a".to_string(),
+    ..Default::default()
+  };
+
+  assert!(!msg.is_valid());
+  assert_eq!(
+    msg.validate_first().unwrap_err().rule_id(),
+    "string.min_len"
+  );
+}
+
+#[test]
+fn table_mode_single_field() {
+  assert!(TableModeTests::validate_field(TableModeTestsField::Age, &30).is_ok());
+
+  let violations = TableModeTests::validate_field(TableModeTestsField::Age, &150)
+    .unwrap_err()
+    .violations;
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "int32.lt");
+  assert_eq!(violations[0].field_path_str().unwrap(), "age");
+}
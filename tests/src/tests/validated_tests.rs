@@ -0,0 +1,86 @@
+use prost::{DecodeError, Message};
+use protocheck::Validated;
+
+use super::invalid_message;
+use crate::myapp::v1::ExampleTests;
+
+#[test]
+fn into_validated() {
+  let message = ExampleTests::valid_example();
+
+  let validated = message.clone().into_validated().unwrap();
+
+  assert_eq!(validated.name, message.name);
+  assert_eq!(validated.into_inner(), message);
+
+  let violations = invalid_message().into_validated().unwrap_err();
+
+  assert_eq!(violations.violations[0].rule_id(), "int32.gte");
+}
+
+#[test]
+fn serde_passthrough() {
+  let validated = ExampleTests::valid_example()
+    .into_validated()
+    .unwrap();
+
+  let json = serde_json::to_string(&validated).unwrap();
+
+  assert_eq!(
+    json,
+    serde_json::to_string(&ExampleTests::valid_example()).unwrap()
+  );
+
+  let deserialized: Validated<ExampleTests> = serde_json::from_str(&json).unwrap();
+
+  assert_eq!(deserialized, validated);
+}
+
+#[test]
+fn deserializing_runs_validation() {
+  let json = serde_json::to_string(&invalid_message()).unwrap();
+
+  let error = serde_json::from_str::<Validated<ExampleTests>>(&json).unwrap_err();
+
+  assert!(error
+    .to_string()
+    .starts_with("validation failed: age: "));
+}
+
+// Decodes any message type, like the codecs that only know about prost::Message
+fn decode<M: Message + Default>(bytes: &[u8]) -> Result<M, DecodeError> {
+  M::decode(bytes)
+}
+
+#[test]
+fn prost_passthrough() {
+  let validated = ExampleTests::valid_example()
+    .into_validated()
+    .unwrap();
+
+  let bytes = Message::encode_to_vec(&validated);
+
+  assert_eq!(bytes, ExampleTests::valid_example().encode_to_vec());
+
+  let decoded: Validated<ExampleTests> = decode(&bytes).unwrap();
+
+  assert_eq!(decoded, validated);
+}
+
+#[test]
+fn decoding_runs_validation() {
+  let bytes = invalid_message().encode_to_vec();
+
+  let error = decode::<Validated<ExampleTests>>(&bytes).unwrap_err();
+
+  assert!(error
+    .to_string()
+    .contains("validation failed: age: "));
+
+  let mut validated = ExampleTests::valid_example()
+    .into_validated()
+    .unwrap();
+
+  assert!(validated.merge(bytes.as_slice()).is_err());
+  assert_eq!(validated.age, ExampleTests::valid_example().age);
+}
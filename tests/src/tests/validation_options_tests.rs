@@ -0,0 +1,151 @@
+use protocheck::{
+  context::{RuleCategory, ValidationContext, ValidationOptions},
+  types::protovalidate::Violations,
+};
+
+use crate::myapp::v1::{NativeCelTests, Recursive};
+
+fn invalid_msg() -> NativeCelTests {
+  NativeCelTests {
+    quantity: 100,
+    unit_price: 3,
+    limit: 200,
+    note: None,
+    code: "xxxx".to_string(),
+  }
+}
+
+#[test]
+fn max_depth() {
+  let mut msg = Recursive {
+    id: 2,
+    recursive: None,
+    oneofs: None,
+  };
+
+  for _ in 0..5 {
+    msg = Recursive {
+      id: 2,
+      recursive: Some(Box::new(msg)),
+      oneofs: None,
+    };
+  }
+
+  let options = ValidationOptions::new().with_max_depth(2);
+
+  let Violations { violations } = msg.validate_with(&options).unwrap_err();
+
+  let depth_violation = violations
+    .iter()
+    .find(|v| v.rule_id() == "max_depth")
+    .unwrap();
+
+  assert_eq!(
+    depth_violation
+      .field
+      .as_ref()
+      .unwrap()
+      .elements
+      .len(),
+    3
+  );
+}
+
+#[test]
+fn max_depth_ignores_rule_filters() {
+  let mut msg = Recursive {
+    id: 2,
+    recursive: None,
+    oneofs: None,
+  };
+
+  for _ in 0..5 {
+    msg = Recursive {
+      id: 2,
+      recursive: Some(Box::new(msg)),
+      oneofs: None,
+    };
+  }
+
+  // All of the ids are 2, so the only violation is the one for the depth limit
+  let options = ValidationOptions::new()
+    .with_max_depth(2)
+    .include_rule("id.is_2");
+
+  let Violations { violations } = msg.validate_with(&options).unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "max_depth");
+}
+
+#[test]
+fn max_violations() {
+  let options = ValidationOptions::new().with_max_violations(2);
+
+  let Violations { violations } = invalid_msg().validate_with(&options).unwrap_err();
+
+  assert_eq!(violations.len(), 2);
+
+  let no_limit = ValidationOptions::new().with_max_violations(0);
+
+  let Violations { violations } = invalid_msg()
+    .validate_with(&no_limit)
+    .unwrap_err();
+
+  assert_eq!(violations.len(), 3);
+}
+
+#[test]
+fn fail_fast_and_max_violations() {
+  let options = ValidationOptions::new().with_max_violations(2);
+
+  let options_first = ValidationContext::new()
+    .with_options(options.clone())
+    .with_fail_fast(false);
+  let fail_fast_first = ValidationContext::new()
+    .with_fail_fast(false)
+    .with_options(options.clone());
+
+  for context in [options_first, fail_fast_first] {
+    let Violations { violations } = invalid_msg()
+      .validate_with_context(&context)
+      .unwrap_err();
+
+    assert_eq!(violations.len(), 2);
+  }
+
+  let fail_fast = ValidationContext::new()
+    .with_fail_fast(true)
+    .with_options(options);
+
+  let Violations { violations } = invalid_msg()
+    .validate_with_context(&fail_fast)
+    .unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+}
+
+#[test]
+fn rule_filters() {
+  let msg = invalid_msg();
+
+  let skip_cel = ValidationOptions::new().skip_category(RuleCategory::Cel);
+
+  assert!(msg.validate_with(&skip_cel).is_ok());
+
+  let skip_quantity = ValidationOptions::new().skip_rule("quantity.*");
+
+  let Violations { violations } = msg.validate_with(&skip_quantity).unwrap_err();
+
+  assert_eq!(violations.len(), 2);
+  assert!(violations
+    .iter()
+    .all(|v| v.rule_id() != "quantity.total"));
+
+  let only_code = ValidationOptions::new().include_rule("*.format");
+
+  let Violations { violations } = msg.validate_with(&only_code).unwrap_err();
+
+  assert_eq!(violations.len(), 1);
+  assert_eq!(violations[0].rule_id(), "code.format");
+}